@@ -1,6 +1,5 @@
 #[allow(dead_code)]
 mod dispatch;
-mod journal;
 #[allow(dead_code)]
 mod merge;
 #[allow(dead_code)]
@@ -17,10 +16,12 @@ use std::time::Duration;
 
 use anyhow::Context;
 
+use crate::commands::journal::{Journal, JournalEntry, Outcome};
+use crate::commands::kb::{self, KbDocument, KbKind, KnowledgeBase};
+use crate::commands::protocol::adapters;
 use crate::config::Config;
 use crate::subprocess::Tool;
 
-use journal::Journal;
 use status::StatusSnapshot;
 
 /// Run the dev-loop (lead agent).
@@ -175,9 +176,14 @@ pub fn run(
     // Capture baseline commits for release tracking
     let baseline_commits = get_commits_since_origin();
 
-    // Initialize journal
+    // Journal persists across sessions; retention is applied on append
     let journal = Journal::new(&project_root);
-    journal.truncate();
+
+    // Knowledge base: pick up close reasons for bones finished since last session
+    let knowledge = KnowledgeBase::new(&project_root);
+    if let Err(e) = kb::refresh_bone_closures(&knowledge) {
+        eprintln!("Warning: failed to refresh knowledge base: {e:#}");
+    }
 
     // Install signal handler for cleanup
     let cleanup_agent = agent.clone();
@@ -274,15 +280,19 @@ pub fn run(
             Vec::new()
         };
         let status_snapshot = StatusSnapshot::gather(&agent, &project);
+        let past_learnings = current_bone(&agent)
+            .and_then(|bone| kb::learnings_for_bone(&project_root, &bone));
 
         let prompt_text = prompt::build(
             &ctx,
             last_iteration.as_ref(),
             &sibling_leads,
             status_snapshot.as_deref(),
+            past_learnings.as_deref(),
         );
 
         let agent_start = crate::telemetry::metrics::time_start();
+        let started_at = chrono::Utc::now();
         match run_agent_subprocess(&prompt_text, &ctx.model, timeout_secs) {
             Ok(output) => {
                 // Check completion signals in the tail of the output
@@ -293,28 +303,60 @@ pub fn run(
                     &output
                 };
 
-                if signal_region.contains("<promise>COMPLETE</promise>") {
-                    eprintln!("\u{2713} Dev cycle complete - no more work");
-                    break;
+                let outcome = if signal_region.contains("<promise>COMPLETE</promise>") {
+                    Outcome::Complete
                 } else if signal_region.contains("<promise>END_OF_STORY</promise>") {
-                    eprintln!("\u{2713} Iteration complete - more work remains");
-                    // Verify work actually remains
-                    if !has_work(&agent, &project)? {
-                        eprintln!("No remaining work found despite END_OF_STORY — exiting cleanly");
-                        break;
-                    }
+                    Outcome::EndOfStory
                 } else {
-                    eprintln!("Warning: No completion signal found in output");
-                }
+                    Outcome::NoSignal
+                };
 
-                // Extract and append iteration summary to journal
-                if let Some(summary) = extract_iteration_summary(&output) {
-                    journal.append(&summary);
+                // Record the iteration before acting on the completion signal
+                let summary = extract_iteration_summary(&output).unwrap_or_default();
+                let entry = JournalEntry::finish(i, &agent, started_at, outcome, &summary, &output);
+                knowledge.record(&[
+                    KbDocument::new(
+                        format!("iteration:{agent}:{}", entry.finished_at),
+                        KbKind::Iteration,
+                        format!("{agent} iteration {i}"),
+                        entry.summary.clone(),
+                        entry.bones.clone(),
+                    ),
+                    KbDocument::transcript(&agent, entry.bones.clone(), &output),
+                ]);
+                journal.append(&entry);
+
+                match outcome {
+                    Outcome::Complete => {
+                        eprintln!("\u{2713} Dev cycle complete - no more work");
+                        break;
+                    }
+                    Outcome::EndOfStory => {
+                        eprintln!("\u{2713} Iteration complete - more work remains");
+                        // Verify work actually remains
+                        if !has_work(&agent, &project)? {
+                            eprintln!(
+                                "No remaining work found despite END_OF_STORY — exiting cleanly"
+                            );
+                            break;
+                        }
+                    }
+                    Outcome::NoSignal | Outcome::Error => {
+                        eprintln!("Warning: No completion signal found in output");
+                    }
                 }
             }
             Err(err) => {
                 eprintln!("Error running Claude: {err:#}");
                 let err_str = format!("{err:#}");
+                journal.append(&JournalEntry::finish(
+                    i,
+                    &agent,
+                    started_at,
+                    Outcome::Error,
+                    &err_str,
+                    "",
+                ));
                 let is_fatal = err_str.contains("API Error")
                     || err_str.contains("rate limit")
                     || err_str.contains("overloaded");
@@ -633,6 +675,33 @@ fn parse_comments(json: &str) -> Vec<String> {
     bodies
 }
 
+/// The bone this lead is most likely working on: its first doing bone, else the top `bn next` pick.
+fn current_bone(agent: &str) -> Option<String> {
+    let doing = Tool::new("bn")
+        .args(&["list", "--state", "doing", "--assignee", agent, "--json"])
+        .in_workspace("default")
+        .ok()?
+        .run()
+        .ok()
+        .filter(|o| o.success())
+        .and_then(|o| adapters::parse_bone_list(&o.stdout).ok())
+        .and_then(|rows| rows.into_iter().next())
+        .map(|row| row.id);
+    if doing.is_some() {
+        return doing;
+    }
+
+    let next = Tool::new("bn")
+        .args(&["next", "--json"])
+        .in_workspace("default")
+        .ok()?
+        .run()
+        .ok()
+        .filter(|o| o.success())?;
+    let v: serde_json::Value = serde_json::from_str(&next.stdout).ok()?;
+    v["assignments"][0]["id"].as_str().map(String::from)
+}
+
 /// Discover sibling lead agents (multi-lead mode).
 fn discover_sibling_leads(agent: &str) -> anyhow::Result<Vec<SiblingLead>> {
     let output = Tool::new("rite")
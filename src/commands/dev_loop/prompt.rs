@@ -1,4 +1,4 @@
-use super::journal::LastIteration;
+use crate::commands::journal::LastIteration;
 use super::{LoopContext, SiblingLead};
 
 /// Build the dev-loop prompt for Claude.
@@ -10,6 +10,7 @@ pub fn build(
     last_iteration: Option<&LastIteration>,
     sibling_leads: &[SiblingLead],
     status_snapshot: Option<&str>,
+    past_learnings: Option<&str>,
 ) -> String {
     let agent = &ctx.agent;
     let project = &ctx.project;
@@ -49,6 +50,14 @@ pub fn build(
         })
         .unwrap_or_default();
 
+    let learnings_section = past_learnings
+        .map(|l| {
+            format!(
+                "\n## PAST LEARNINGS (from the project knowledge base — check before re-solving)\n\n{l}"
+            )
+        })
+        .unwrap_or_default();
+
     let status_section = status_snapshot
         .map(|s| format!("\n## CURRENT STATUS (pre-gathered — no need to re-fetch)\n\n{s}\n"))
         .unwrap_or_default();
@@ -283,7 +292,7 @@ COMMAND PATTERN — maw exec: All bn commands run in the default workspace. All
 Inside `maw exec <ws>`, CWD is already `ws/<ws>/`. Use `maw exec default -- ls src/`, NOT `maw exec default -- ls ws/default/src/`
 For file reads/edits outside maw exec, use the full absolute path: `ws/<ws>/src/...`
 VERSION CONTROL: This project uses Git + maw. Do NOT run jj commands.
-{previous_context}{status_section}{sibling_section}Execute exactly ONE dev cycle. Triage inbox, assess ready bones, either work on one yourself
+{previous_context}{status_section}{learnings_section}{sibling_section}Execute exactly ONE dev cycle. Triage inbox, assess ready bones, either work on one yourself
 or dispatch multiple workers in parallel, monitor progress, merge results. Then STOP.
 
 At the end of your work, output:
@@ -769,7 +778,7 @@ mod tests {
     #[test]
     fn prompt_contains_all_protocol_commands() {
         let ctx = test_ctx();
-        let prompt = build(&ctx, None, &[], None);
+        let prompt = build(&ctx, None, &[], None, None);
 
         // All 5 protocol commands must be referenced in the dev-loop prompt
         assert!(
@@ -794,10 +803,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn prompt_includes_past_learnings_when_present() {
+        let ctx = test_ctx();
+        let without = build(&ctx, None, &[], None, None);
+        assert!(!without.contains("PAST LEARNINGS"));
+
+        let learnings = "- [bone_close] bn-abc: fix divergence (2026-01-01): jj abandon\n";
+        let with = build(&ctx, None, &[], None, Some(learnings));
+        assert!(with.contains("## PAST LEARNINGS"));
+        assert!(with.contains("jj abandon"));
+    }
+
     #[test]
     fn prompt_contains_protocol_fallback_wording() {
         let ctx = test_ctx();
-        let prompt = build(&ctx, None, &[], None);
+        let prompt = build(&ctx, None, &[], None, None);
 
         // Verify fallback wording is present for protocol transitions
         // This prevents silent regressions where protocol fallback guidance is removed
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -8,22 +8,31 @@ use serde_json::json;
 
 use crate::config::Config;
 use crate::error::ExitError;
-use crate::hooks::HookRegistry;
+use crate::hooks::harness::{self, Harness, Integration};
+use crate::hooks::simulate::{self, SimHarness};
 use crate::subprocess::run_command;
 
-pub(crate) const PI_EDICT_HOOKS_EXTENSION: &str =
-    include_str!("../templates/extensions/edict-hooks.ts");
-
 #[derive(Debug, Subcommand)]
 pub enum HooksCommand {
-    /// Install/update global agent hooks in ~/.claude/settings.json
+    /// Install/update agent hooks (Claude Code, Pi, and any detected Codex CLI, Gemini
+    /// CLI or opencode)
     Install {
-        /// Project root directory (for rite hook registration only)
+        /// Project root directory (for rite hook registration and --scope project)
+        #[arg(long)]
+        project_root: Option<PathBuf>,
+        /// Where to install: the user's home directory or this project's settings
+        #[arg(long, value_enum, default_value_t = HookScope::User)]
+        scope: HookScope,
+    },
+    /// Remove agent hooks from every harness
+    Uninstall {
+        /// Project root directory (for --scope project)
         #[arg(long)]
         project_root: Option<PathBuf>,
+        /// Which installation to remove
+        #[arg(long, value_enum, default_value_t = HookScope::User)]
+        scope: HookScope,
     },
-    /// Remove global agent hooks from ~/.claude/settings.json
-    Uninstall,
     /// Audit hook registrations and report issues
     Audit {
         /// Project root directory
@@ -32,10 +41,13 @@ pub enum HooksCommand {
         /// Output format
         #[arg(long, value_enum, default_value_t = super::doctor::OutputFormat::Pretty)]
         format: super::doctor::OutputFormat,
+        /// Reinstall outdated, duplicated, hand-edited or missing edict hook entries
+        #[arg(long)]
+        fix: bool,
     },
     /// Run a hook directly (called by Claude Code / Pi hooks infrastructure)
     Run {
-        /// Hook name (session-start, post-tool-call, session-end)
+        /// Hook name (session-start, pre-compact, pre-tool-call, post-tool-call, session-end)
         hook_name: String,
         /// Project root directory (deprecated, ignored — hooks auto-detect context)
         #[arg(long)]
@@ -43,41 +55,239 @@ pub enum HooksCommand {
         /// Release claims (for Pi session shutdown)
         #[arg(long)]
         release: bool,
+        /// Tool that triggered the hook (defaults to tool_name from the hook input)
+        #[arg(long)]
+        tool: Option<String>,
+        /// Hook input JSON (defaults to stdin)
+        #[arg(long)]
+        input: Option<String>,
+        /// Print session-start / pre-compact context as hookSpecificOutput JSON
+        #[arg(long)]
+        json: bool,
+        /// Version of edict that installed the hook (stamped by `hooks install`)
+        #[arg(long, hide = true)]
+        edict_version: Option<String>,
+    },
+    /// Run a hook offline with a synthetic payload and stubbed companion tools, and show
+    /// what would be injected or blocked and which claims/messages it would touch
+    Simulate {
+        /// Hook name (session-start, pre-compact, pre-tool-call, post-tool-call, session-end)
+        event: String,
+        /// Harness whose invocation to reproduce
+        #[arg(long, value_enum, default_value_t = SimHarness::Claude)]
+        harness: SimHarness,
+        /// Tool name for pre-tool-call / post-tool-call
+        #[arg(long, default_value = "Bash")]
+        tool: String,
+        /// Tool input JSON (defaults to a typical input for the tool)
+        #[arg(long)]
+        input: Option<String>,
+        /// Session ID in the payload
+        #[arg(long, default_value = "sim-session")]
+        session_id: String,
+        /// Agent name (defaults to the project's dev agent)
+        #[arg(long)]
+        agent: Option<String>,
+        /// Claimed workspace ($EDICT_WORKSPACE)
+        #[arg(long)]
+        workspace: Option<String>,
+        /// Unread mentions the stubbed rite inbox reports
+        #[arg(long, default_value_t = 1)]
+        unread: u32,
+        /// Seconds until the agent claim expires (under 120 triggers a refresh)
+        #[arg(long, default_value_t = 600)]
+        claim_expires_in: i64,
+        /// Directory to run the hook in (defaults to the current directory)
+        #[arg(long)]
+        project_root: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = super::doctor::OutputFormat::Pretty)]
+        format: super::doctor::OutputFormat,
     },
 }
 
+/// Where agent hooks are installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum HookScope {
+    /// The project's harness settings (e.g. .claude/settings.json in the repo), so only
+    /// sessions in this project run edict hooks
+    Project,
+    /// The home directory (e.g. ~/.claude/settings.json), for every session
+    User,
+}
+
 impl HooksCommand {
     pub fn execute(&self) -> anyhow::Result<()> {
         match self {
-            HooksCommand::Install { project_root } => install_hooks(project_root.as_deref()),
-            HooksCommand::Uninstall => uninstall_hooks(),
+            HooksCommand::Install {
+                project_root,
+                scope,
+            } => install_hooks(project_root.as_deref(), *scope),
+            HooksCommand::Uninstall {
+                project_root,
+                scope,
+            } => uninstall_hooks(project_root.as_deref(), *scope),
             HooksCommand::Audit {
                 project_root,
                 format,
-            } => audit_hooks(project_root.as_deref(), *format),
+                fix,
+            } => audit_hooks(project_root.as_deref(), *format, *fix),
             HooksCommand::Run {
                 hook_name,
                 release,
+                tool,
+                input,
+                json,
                 ..
-            } => run_hook(hook_name, *release),
+            } => run_hook(
+                hook_name,
+                *release,
+                tool.as_deref(),
+                input.as_deref(),
+                *json,
+            ),
+            HooksCommand::Simulate {
+                event,
+                harness,
+                tool,
+                input,
+                session_id,
+                agent,
+                workspace,
+                unread,
+                claim_expires_in,
+                project_root,
+                format,
+            } => {
+                let tool_input = match input {
+                    Some(input) => serde_json::from_str(input)
+                        .map_err(|e| ExitError::Config(format!("--input is not JSON: {e}")))?,
+                    None => simulate::default_tool_input(tool),
+                };
+                let cwd = match project_root {
+                    Some(root) => root.clone(),
+                    None => std::env::current_dir()?,
+                };
+                let agent = match agent {
+                    Some(agent) => agent.clone(),
+                    None => resolve_project_root(Some(&cwd))
+                        .and_then(|root| load_config(&root))
+                        .map_or_else(|_| "sim-dev".to_string(), |c| c.default_agent()),
+                };
+                simulate_hook(
+                    &simulate::Scenario {
+                        event: event.clone(),
+                        harness: *harness,
+                        tool: tool.clone(),
+                        tool_input,
+                        session_id: session_id.clone(),
+                        agent,
+                        workspace: workspace.clone(),
+                        cwd,
+                        unread: *unread,
+                        claim_expires_in: *claim_expires_in,
+                    },
+                    *format,
+                )
+            }
+        }
+    }
+}
+
+fn simulate_hook(scenario: &simulate::Scenario, format: super::doctor::OutputFormat) -> Result<()> {
+    if !crate::hooks::HookRegistry::all()
+        .iter()
+        .any(|h| h.name == scenario.event)
+    {
+        return Err(ExitError::Config(format!("unknown hook: {}", scenario.event)).into());
+    }
+    let outcome = simulate::simulate(scenario)?;
+    match format {
+        super::doctor::OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&outcome)?);
+        }
+        super::doctor::OutputFormat::Pretty | super::doctor::OutputFormat::Text => {
+            print!("{}", outcome.render());
+        }
+    }
+    Ok(())
+}
+
+/// A harness integration file at one scope.
+struct Location {
+    harness: Harness,
+    path: PathBuf,
+    /// Path as shown in messages (`~/...` or project-relative).
+    display: String,
+}
+
+impl Location {
+    fn user(home: &Path, harness: Harness) -> Self {
+        Self {
+            path: home.join(harness.path),
+            display: format!("~/{}", harness.path),
+            harness,
+        }
+    }
+
+    /// `None` for harnesses without project-level settings.
+    fn project(root: &Path, harness: Harness) -> Option<Self> {
+        let rel = harness.project_path?;
+        Some(Self {
+            path: root.join(rel),
+            display: rel.to_string(),
+            harness,
+        })
+    }
+
+    /// Whether edict hooks are installed here.
+    fn installed(&self) -> bool {
+        match &self.harness.integration {
+            Integration::Settings(_) => read_settings(&self.path)
+                .is_ok_and(|settings| !edict_commands(&settings["hooks"]).is_empty()),
+            Integration::Plugin(_) => self.path.exists(),
+        }
+    }
+
+    fn install(&self) -> Result<()> {
+        match &self.harness.integration {
+            Integration::Settings(bindings) => install_settings_hooks(&self.path, bindings),
+            Integration::Plugin(source) => install_plugin(&self.path, &harness::plugin_source(source)),
         }
     }
 }
 
-/// Install global agent hooks into ~/.claude/settings.json (and Pi extensions).
+/// Install agent hooks for every detected harness (Claude Code and Pi always).
 ///
 /// If project_root is provided, also registers rite hooks (router + reviewers).
-fn install_hooks(project_root: Option<&Path>) -> Result<()> {
-    // Install global Claude Code hooks
+fn install_hooks(project_root: Option<&Path>, scope: HookScope) -> Result<()> {
     let home = dirs::home_dir().context("could not determine home directory")?;
-    let settings_path = home.join(".claude/settings.json");
-    install_global_claude_hooks(&settings_path)?;
-    println!("Installed global hooks in {}", settings_path.display());
+    let root = match scope {
+        HookScope::Project => Some(resolve_project_root(project_root)?),
+        HookScope::User => None,
+    };
 
-    // Install Pi extension globally
-    let pi_ext_path = home.join(".pi/agent/extensions/edict-hooks.ts");
-    install_pi_extension(&pi_ext_path)?;
-    println!("Installed Pi extension at {}", pi_ext_path.display());
+    for harness in harness::all() {
+        if harness.detect.is_some_and(|dir| !home.join(dir).exists()) {
+            continue;
+        }
+        let label = harness.label;
+        let location = match &root {
+            Some(root) => match Location::project(root, harness) {
+                Some(location) => location,
+                None => {
+                    println!("Skipped {label} (no project-level settings; use --scope user)");
+                    continue;
+                }
+            },
+            None => Location::user(&home, harness),
+        };
+        location.install()?;
+        println!("Installed {label} hooks in {}", location.path.display());
+        if !location.harness.limitations.is_empty() {
+            println!("  note: {}", location.harness.limitations);
+        }
+    }
 
     // If in a botbox project, also register rite hooks (router + reviewers)
     if let Some(root) = project_root {
@@ -94,99 +304,130 @@ fn install_hooks(project_root: Option<&Path>) -> Result<()> {
     Ok(())
 }
 
-/// Remove global agent hooks from ~/.claude/settings.json and Pi extensions.
-fn uninstall_hooks() -> Result<()> {
+/// Remove agent hooks from every harness at one scope.
+fn uninstall_hooks(project_root: Option<&Path>, scope: HookScope) -> Result<()> {
     let home = dirs::home_dir().context("could not determine home directory")?;
+    let root = match scope {
+        HookScope::Project => Some(resolve_project_root(project_root)?),
+        HookScope::User => None,
+    };
 
-    // Remove from ~/.claude/settings.json
-    let settings_path = home.join(".claude/settings.json");
-    if settings_path.exists() {
-        let content = fs::read_to_string(&settings_path)
-            .with_context(|| format!("reading {}", settings_path.display()))?;
-        let mut settings: serde_json::Value =
-            serde_json::from_str(&content).unwrap_or_else(|_| json!({}));
-
-        if let Some(hooks) = settings.get_mut("hooks").and_then(|h| h.as_object_mut()) {
-            for (_event, entries) in hooks.iter_mut() {
-                if let Some(arr) = entries.as_array_mut() {
-                    arr.retain(|entry| !is_botbox_hook_entry(entry));
+    for harness in harness::all() {
+        let location = match &root {
+            Some(root) => Location::project(root, harness),
+            None => Some(Location::user(&home, harness)),
+        };
+        let Some(location) = location.filter(|l| l.path.exists()) else {
+            continue;
+        };
+        match location.harness.integration {
+            Integration::Settings(_) => {
+                if remove_settings_hooks(&location.path)? {
+                    println!("Removed botbox hooks from {}", location.path.display());
                 }
             }
-            // Remove empty event arrays
-            hooks.retain(|_, v| {
-                v.as_array().map(|a| !a.is_empty()).unwrap_or(true)
-            });
-        }
-
-        // Remove hooks key entirely if empty
-        if settings
-            .get("hooks")
-            .and_then(|h| h.as_object())
-            .is_some_and(|h| h.is_empty())
-        {
-            settings.as_object_mut().unwrap().remove("hooks");
+            Integration::Plugin(source) => {
+                let contents = fs::read_to_string(&location.path)
+                    .with_context(|| format!("reading {}", location.path.display()))?;
+                if let Some(reason) = plugin_kept(&contents, source) {
+                    eprintln!("Left {} in place: {reason}", location.path.display());
+                    continue;
+                }
+                fs::remove_file(&location.path)?;
+                println!("Removed {}", location.path.display());
+            }
         }
-
-        fs::write(&settings_path, serde_json::to_string_pretty(&settings)?)?;
-        println!("Removed botbox hooks from {}", settings_path.display());
-    }
-
-    // Remove Pi extension
-    let pi_ext_path = home.join(".pi/agent/extensions/edict-hooks.ts");
-    if pi_ext_path.exists() {
-        fs::remove_file(&pi_ext_path)?;
-        println!("Removed {}", pi_ext_path.display());
     }
 
     println!("Hooks uninstalled successfully");
     Ok(())
 }
 
-fn audit_hooks(project_root: Option<&Path>, format: super::doctor::OutputFormat) -> Result<()> {
+/// Why an installed plugin file must not be deleted, if it mustn't: it lacks the header
+/// `hooks install` writes, or it was edited after this version of edict installed it.
+fn plugin_kept(contents: &str, source: &str) -> Option<&'static str> {
+    match harness::plugin_version(contents) {
+        None => Some("it was not generated by edict"),
+        Some(version)
+            if version == harness::VERSION && contents != harness::plugin_source(source) =>
+        {
+            Some("it was edited by hand")
+        }
+        Some(_) => None,
+    }
+}
+
+fn audit_hooks(
+    project_root: Option<&Path>,
+    format: super::doctor::OutputFormat,
+    fix: bool,
+) -> Result<()> {
     let home = dirs::home_dir().context("could not determine home directory")?;
     let mut issues = Vec::new();
+    let mut fixed = Vec::new();
+    let mut fixable = false;
 
-    // Check global settings.json
-    let settings_path = home.join(".claude/settings.json");
-    if !settings_path.exists() {
-        issues.push("Missing ~/.claude/settings.json".to_string());
-    } else {
-        let content = fs::read_to_string(&settings_path)
-            .with_context(|| format!("reading {}", settings_path.display()))?;
-        let settings: serde_json::Value = serde_json::from_str(&content)
-            .with_context(|| format!("parsing {}", settings_path.display()))?;
-
-        for hook_entry in &HookRegistry::all() {
-            let found = hook_entry.events.iter().any(|event| {
-                settings["hooks"][event.as_str()]
-                    .as_array()
-                    .is_some_and(|arr| {
-                        arr.iter().any(|entry| {
-                            entry["hooks"].as_array().is_some_and(|hooks| {
-                                hooks.iter().any(|h| is_botbox_hook_command(h, hook_entry.name))
-                            })
-                        })
-                    })
-            });
+    let root = project_root
+        .and_then(|p| resolve_project_root(Some(p)).ok())
+        .or_else(|| resolve_project_root(None).ok());
+
+    // Check each harness integration, at user and project scope
+    for harness in harness::all() {
+        let project = root
+            .as_deref()
+            .and_then(|root| Location::project(root, harness.clone()))
+            .filter(Location::installed);
+        let user = Location::user(&home, harness);
+        let label = user.harness.label;
+
+        if let Some(ref project) = project
+            && user.installed()
+        {
+            issues.push(format!(
+                "{label} hooks are installed in both {} and {}; sessions in this project run them twice",
+                user.display, project.display
+            ));
+        }
 
-            if !found {
-                issues.push(format!(
-                    "Hook '{}' not registered in ~/.claude/settings.json",
-                    hook_entry.name
-                ));
+        // Claude Code hooks are expected somewhere
+        let required = user.harness.detect.is_none()
+            && matches!(user.harness.integration, Integration::Settings(_))
+            && project.is_none();
+        for (location, required) in [(Some(user), required), (project, false)] {
+            let Some(location) = location else {
+                continue;
+            };
+            let before = issues.len();
+            check_integration(&location, required, &mut issues)?;
+            if issues.len() == before {
+                continue;
+            }
+            if fix {
+                location.install()?;
+                issues.truncate(before);
+                fixed.push(location.path.display().to_string());
+            } else {
+                fixable = true;
             }
         }
     }
 
     // Check rite hooks (if in a botbox project)
-    if let Some(root) = project_root
-        .and_then(|p| resolve_project_root(Some(p)).ok())
-        .or_else(|| resolve_project_root(None).ok())
-    {
+    if let Some(root) = root {
         if let Ok(config) = load_config(&root) {
             if config.tools.rite {
                 check_rite_hooks(&root, &config, &mut issues)?;
             }
+            for problem in crate::hooks::policy::validate(&config.hooks.policy) {
+                issues.push(format!("hooks.policy: {problem}"));
+            }
+            for (event, hooks) in config.hooks.events() {
+                for (i, hook) in hooks.iter().enumerate() {
+                    for problem in crate::hooks::user::validate(event, hook) {
+                        issues.push(format!("hooks.{event}[{i}]: {problem}"));
+                    }
+                }
+            }
         }
     }
 
@@ -194,11 +435,16 @@ fn audit_hooks(project_root: Option<&Path>, format: super::doctor::OutputFormat)
         super::doctor::OutputFormat::Json => {
             let result = json!({
                 "issues": issues,
+                "fixed": fixed,
+                "fixable": fixable,
                 "status": if issues.is_empty() { "ok" } else { "issues_found" }
             });
             println!("{}", serde_json::to_string_pretty(&result)?);
         }
         super::doctor::OutputFormat::Pretty | super::doctor::OutputFormat::Text => {
+            for path in &fixed {
+                println!("Reinstalled edict hooks in {path}");
+            }
             if issues.is_empty() {
                 println!("✓ All hooks configured correctly");
             } else {
@@ -206,6 +452,9 @@ fn audit_hooks(project_root: Option<&Path>, format: super::doctor::OutputFormat)
                 for issue in &issues {
                     eprintln!("  - {issue}");
                 }
+                if fixable {
+                    eprintln!("Run `edict hooks audit --fix` to reinstall the affected hook entries.");
+                }
                 return Err(ExitError::AuditFailed.into());
             }
         }
@@ -214,29 +463,49 @@ fn audit_hooks(project_root: Option<&Path>, format: super::doctor::OutputFormat)
     Ok(())
 }
 
-fn run_hook(hook_name: &str, release: bool) -> Result<()> {
-    // Read stdin with a size limit (64KB) for defense-in-depth
-    let stdin_input = {
+fn run_hook(
+    hook_name: &str,
+    release: bool,
+    tool: Option<&str>,
+    input: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    // Read stdin with a size limit (64KB) for defense-in-depth. The policy check reads
+    // it whole: a truncated payload would not parse, and would be refused.
+    let stdin_input = if let Some(input) = input {
+        Some(input.to_string())
+    } else {
         use std::io::Read;
+        let limit = if hook_name == "pre-tool-call" {
+            u64::MAX
+        } else {
+            64 * 1024
+        };
         let mut buf = String::new();
-        let mut handle = std::io::stdin().take(64 * 1024);
-        handle.read_to_string(&mut buf).ok();
-        if buf.is_empty() { None } else { Some(buf) }
+        let mut handle = std::io::stdin().take(limit);
+        match handle.read_to_string(&mut buf) {
+            Ok(_) if buf.is_empty() => None,
+            Ok(_) => Some(buf),
+            // Pass unreadable input on (as empty) so the policy check refuses it
+            Err(_) => Some(buf),
+        }
     };
 
     match hook_name {
-        "session-start" => crate::hooks::run_session_start(),
-        "post-tool-call" => crate::hooks::run_post_tool_call(stdin_input.as_deref()),
+        "session-start" => crate::hooks::run_session_start(stdin_input.as_deref(), json),
+        "pre-compact" => crate::hooks::run_pre_compact(json),
+        "pre-tool-call" => crate::hooks::run_pre_tool_call(stdin_input.as_deref(), tool),
+        "post-tool-call" => crate::hooks::run_post_tool_call(stdin_input.as_deref(), tool),
         "session-end" => crate::hooks::run_session_end(),
         // Backwards compat: old hook names map to new ones
-        "init-agent" | "check-jj" => crate::hooks::run_session_start(),
-        "check-rite-inbox" => crate::hooks::run_post_tool_call(stdin_input.as_deref()),
+        "init-agent" | "check-jj" => crate::hooks::run_session_start(stdin_input.as_deref(), json),
+        "check-rite-inbox" => crate::hooks::run_post_tool_call(stdin_input.as_deref(), tool),
         "claim-agent" => {
             if release {
                 crate::hooks::run_session_end()
             } else {
                 // claim-agent on SessionStart/PostToolUse — handled by session-start/post-tool-call
-                crate::hooks::run_session_start()
+                crate::hooks::run_session_start(stdin_input.as_deref(), json)
             }
         }
         _ => Err(ExitError::Config(format!("unknown hook: {hook_name}")).into()),
@@ -267,75 +536,225 @@ fn load_config(root: &Path) -> Result<Config> {
     Config::load(&config_path)
 }
 
-/// Install global Claude Code hooks into ~/.claude/settings.json
-fn install_global_claude_hooks(settings_path: &Path) -> Result<()> {
-    let hooks = HookRegistry::all();
+fn read_settings(path: &Path) -> Result<serde_json::Value> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+}
 
+/// Settings to merge hooks into: empty when the file doesn't exist yet.
+///
+/// A file that isn't a JSON object is an error rather than a fresh start, so a
+/// hand-edited config with a typo is never overwritten.
+fn load_settings(path: &Path) -> Result<serde_json::Value> {
+    if !path.exists() {
+        return Ok(json!({}));
+    }
+    let settings = read_settings(path)
+        .with_context(|| format!("leaving {} untouched; fix it and retry", path.display()))?;
+    if !settings.is_object() {
+        anyhow::bail!(
+            "{} is not a JSON object; leaving it untouched",
+            path.display()
+        );
+    }
+    Ok(settings)
+}
+
+fn write_settings(path: &Path, settings: &serde_json::Value) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+    }
+    fs::write(path, serde_json::to_string_pretty(settings)?)
+        .with_context(|| format!("writing {}", path.display()))
+}
+
+/// Install hooks into a Claude-style settings file (Claude Code, Codex CLI, Gemini CLI).
+fn install_settings_hooks(settings_path: &Path, bindings: &[harness::Binding]) -> Result<()> {
     let mut hooks_config: HashMap<String, Vec<serde_json::Value>> = HashMap::new();
-    for hook_entry in &hooks {
-        for event in hook_entry.events.iter() {
-            let entry = json!({
-                "matcher": "",
-                "hooks": [{
-                    "type": "command",
-                    "command": format!("edict hooks run {}", hook_entry.name)
-                }]
-            });
-            hooks_config
-                .entry(event.as_str().to_string())
-                .or_default()
-                .push(entry);
-        }
+    for binding in bindings {
+        let entry = json!({
+            "matcher": "",
+            "hooks": [{
+                "type": "command",
+                "command": binding.command()
+            }]
+        });
+        hooks_config
+            .entry(binding.event.to_string())
+            .or_default()
+            .push(entry);
     }
 
-    // Load existing settings or create new
-    let mut settings = if settings_path.exists() {
-        let content = fs::read_to_string(settings_path)
-            .with_context(|| format!("reading {}", settings_path.display()))?;
-        serde_json::from_str::<serde_json::Value>(&content).unwrap_or_else(|_| json!({}))
-    } else {
-        json!({})
-    };
+    let mut settings = load_settings(settings_path)?;
 
-    // Merge: preserve non-botbox hooks, replace botbox hooks
+    // Merge: preserve non-botbox hooks, replace botbox hooks (in every event, so stale
+    // and duplicated entries go too)
     let existing_hooks = settings.get("hooks").cloned().unwrap_or_else(|| json!({}));
     let mut merged_hooks = existing_hooks.as_object().cloned().unwrap_or_default();
-
-    for (event, new_entries) in &hooks_config {
-        let existing_entries: Vec<serde_json::Value> = merged_hooks
-            .get(event)
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter(|entry| !is_botbox_hook_entry(entry))
-                    .cloned()
-                    .collect()
-            })
-            .unwrap_or_default();
-
-        let mut combined = existing_entries;
-        combined.extend(new_entries.iter().cloned());
-        merged_hooks.insert(event.clone(), serde_json::Value::Array(combined));
+    for entries in merged_hooks.values_mut() {
+        if let Some(arr) = entries.as_array_mut() {
+            arr.retain(|entry| !is_botbox_hook_entry(entry));
+        }
+    }
+    merged_hooks.retain(|_, v| v.as_array().is_none_or(|a| !a.is_empty()));
+
+    for (event, new_entries) in hooks_config {
+        let combined = merged_hooks
+            .entry(event)
+            .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+        if let Some(arr) = combined.as_array_mut() {
+            arr.extend(new_entries);
+        }
     }
 
     settings["hooks"] = serde_json::Value::Object(merged_hooks);
+    write_settings(settings_path, &settings)
+}
 
-    if let Some(parent) = settings_path.parent() {
-        fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+/// Remove edict hooks from a settings file. Returns whether any were removed.
+fn remove_settings_hooks(settings_path: &Path) -> Result<bool> {
+    let mut settings = load_settings(settings_path)?;
+    let mut removed = false;
+
+    if let Some(hooks) = settings.get_mut("hooks").and_then(|h| h.as_object_mut()) {
+        for (_event, entries) in hooks.iter_mut() {
+            if let Some(arr) = entries.as_array_mut() {
+                let before = arr.len();
+                arr.retain(|entry| !is_botbox_hook_entry(entry));
+                removed |= arr.len() != before;
+            }
+        }
+        // Remove empty event arrays
+        hooks.retain(|_, v| v.as_array().map(|a| !a.is_empty()).unwrap_or(true));
+    }
+    if !removed {
+        return Ok(false);
     }
 
-    fs::write(settings_path, serde_json::to_string_pretty(&settings)?)
-        .with_context(|| format!("writing {}", settings_path.display()))?;
+    // Remove hooks key entirely if empty
+    if settings
+        .get("hooks")
+        .and_then(|h| h.as_object())
+        .is_some_and(|h| h.is_empty())
+        && let Some(obj) = settings.as_object_mut()
+    {
+        obj.remove("hooks");
+    }
 
-    Ok(())
+    write_settings(settings_path, &settings)?;
+    Ok(true)
 }
 
-fn install_pi_extension(path: &Path) -> Result<()> {
+fn install_plugin(path: &Path, source: &str) -> Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
     }
-    fs::write(path, PI_EDICT_HOOKS_EXTENSION)
-        .with_context(|| format!("writing {}", path.display()))?;
+    fs::write(path, source).with_context(|| format!("writing {}", path.display()))?;
+    Ok(())
+}
+
+/// Edict hook commands in a settings `hooks` object (or one event's entry list).
+fn edict_commands(hooks: &serde_json::Value) -> Vec<(String, String)> {
+    let events: Vec<(String, &serde_json::Value)> = match hooks {
+        serde_json::Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        _ => Vec::new(),
+    };
+    let mut commands = Vec::new();
+    for (event, entries) in events {
+        for entry in entries.as_array().into_iter().flatten() {
+            if !is_botbox_hook_entry(entry) {
+                continue;
+            }
+            for h in entry["hooks"].as_array().into_iter().flatten() {
+                let command = match &h["command"] {
+                    serde_json::Value::String(cmd) => cmd.clone(),
+                    serde_json::Value::Array(parts) => parts
+                        .iter()
+                        .filter_map(|p| p.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    _ => continue,
+                };
+                commands.push((event.clone(), command));
+            }
+        }
+    }
+    commands
+}
+
+/// Audit one harness integration: missing, outdated, duplicated and hand-edited edict
+/// entries. A missing file is only an issue when `required`.
+fn check_integration(location: &Location, required: bool, issues: &mut Vec<String>) -> Result<()> {
+    let label = location.harness.label;
+    let display = &location.display;
+    if !location.path.exists() {
+        if required {
+            issues.push(format!("Missing {display}"));
+        }
+        return Ok(());
+    }
+
+    match &location.harness.integration {
+        Integration::Settings(bindings) => {
+            let settings = read_settings(&location.path)?;
+            let commands = edict_commands(&settings["hooks"]);
+            if commands.is_empty() && !required {
+                return Ok(());
+            }
+
+            let mut counts: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+            for (event, command) in &commands {
+                let hook = harness::command_hook(command).unwrap_or_default();
+                *counts.entry((event, hook)).or_default() += 1;
+                if bindings
+                    .iter()
+                    .any(|b| b.event == event && b.command() == *command)
+                {
+                    continue;
+                }
+                match harness::command_version(command) {
+                    Some(version) if version == harness::VERSION => issues.push(format!(
+                        "{label} {event} entry `{command}` in {display} was edited by hand"
+                    )),
+                    version => issues.push(format!(
+                        "{label} {event} entry `{command}` in {display} is outdated (installed by edict {})",
+                        version.unwrap_or("before versioned hooks")
+                    )),
+                }
+            }
+            for ((event, hook), count) in &counts {
+                if *count > 1 {
+                    issues.push(format!(
+                        "{label} {event} has {count} entries for '{hook}' in {display}"
+                    ));
+                }
+            }
+            for binding in bindings {
+                if !counts.contains_key(&(binding.event, binding.hook())) {
+                    issues.push(format!(
+                        "{label} hook '{}' not registered for {} in {display}",
+                        binding.hook(),
+                        binding.event
+                    ));
+                }
+            }
+        }
+        Integration::Plugin(source) => {
+            let contents = fs::read_to_string(&location.path)?;
+            if contents != harness::plugin_source(source) {
+                match harness::plugin_version(&contents) {
+                    Some(version) if version == harness::VERSION => {
+                        issues.push(format!("{label} plugin {display} was edited by hand"));
+                    }
+                    version => issues.push(format!(
+                        "{label} plugin {display} is outdated (installed by edict {})",
+                        version.unwrap_or("before versioned hooks")
+                    )),
+                }
+            }
+        }
+    }
     Ok(())
 }
 
@@ -361,22 +780,6 @@ fn is_botbox_hook_entry(entry: &serde_json::Value) -> bool {
         })
 }
 
-/// Check if a specific hook command matches a hook name (edict or legacy botbox)
-fn is_botbox_hook_command(h: &serde_json::Value, name: &str) -> bool {
-    let cmd = &h["command"];
-    if let Some(cmd_str) = cmd.as_str() {
-        cmd_str.contains(&format!("run {name}"))
-    } else if let Some(cmd_arr) = cmd.as_array() {
-        cmd_arr.len() >= 4
-            && (cmd_arr[0].as_str() == Some("edict") || cmd_arr[0].as_str() == Some("botbox"))
-            && cmd_arr[1].as_str() == Some("hooks")
-            && cmd_arr[2].as_str() == Some("run")
-            && cmd_arr[3].as_str() == Some(name)
-    } else {
-        false
-    }
-}
-
 /// Validates a name against `[a-z0-9][a-z0-9-]*` to prevent shell injection.
 fn validate_name(name: &str, label: &str) -> Result<()> {
     if name.is_empty()
@@ -401,7 +804,9 @@ fn register_rite_hooks(root: &Path, config: &Config) -> Result<()> {
 
     validate_name(project_name, "project name")?;
     validate_name(&channel, "channel name")?;
-    for reviewer in &config.review.reviewers {
+    // Built-in reviewers plus any [review.roles.<name>] entries
+    let reviewers = config.review.all_roles();
+    for reviewer in &reviewers {
         validate_name(reviewer, "reviewer name")?;
     }
 
@@ -465,7 +870,7 @@ fn register_rite_hooks(root: &Path, config: &Config) -> Result<()> {
         .as_ref()
         .and_then(|r| r.memory_limit.as_deref());
 
-    for reviewer in &config.review.reviewers {
+    for reviewer in &reviewers {
         let reviewer_agent = format!("{project_name}-{reviewer}");
         let claim_uri = format!("agent://{reviewer_agent}");
         let desc = format!("edict:{project_name}:reviewer-{reviewer}");
@@ -552,7 +957,7 @@ fn check_rite_hooks(root: &Path, config: &Config, issues: &mut Vec<String>) -> R
         ));
     }
 
-    for reviewer in &config.review.reviewers {
+    for reviewer in &config.review.all_roles() {
         let mention_name = format!("{}-{reviewer}", config.project.name);
         let has_reviewer = hooks.iter().any(|h| {
             h["condition"]["mention"]
@@ -637,6 +1042,151 @@ mod tests {
         assert!(!is_botbox_hook_entry(&entry));
     }
 
+    fn location(label: &str, path: &Path) -> Location {
+        let harness = harness::all()
+            .into_iter()
+            .find(|h| h.label == label)
+            .unwrap();
+        Location {
+            harness,
+            path: path.to_path_buf(),
+            display: "settings".to_string(),
+        }
+    }
+
+    fn audit(location: &Location, required: bool) -> Vec<String> {
+        let mut issues = Vec::new();
+        check_integration(location, required, &mut issues).unwrap();
+        issues
+    }
+
+    #[test]
+    fn uninstall_keeps_foreign_and_hand_edited_plugins() {
+        let source = harness::PI_EDICT_HOOKS_EXTENSION;
+        let installed = harness::plugin_source(source);
+        assert_eq!(plugin_kept(&installed, source), None);
+        assert_eq!(
+            plugin_kept(&format!("{installed}// mine\n"), source),
+            Some("it was edited by hand")
+        );
+        assert_eq!(
+            plugin_kept("export default () => {};\n", source),
+            Some("it was not generated by edict")
+        );
+        let outdated = format!("// edict 0.0.1: installed by `edict hooks install`\n{source}");
+        assert_eq!(plugin_kept(&outdated, source), None);
+    }
+
+    #[test]
+    fn unparseable_settings_are_left_alone() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(".codex/hooks.json");
+        let codex = location("Codex CLI", &path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{\"hooks\": {,}").unwrap();
+
+        assert!(codex.install().is_err());
+        assert!(remove_settings_hooks(&path).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"hooks\": {,}");
+    }
+
+    #[test]
+    fn settings_hooks_install_audit_and_remove() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(".gemini/settings.json");
+        let gemini = location("Gemini CLI", &path);
+
+        // Settings without edict hooks are only an issue when hooks are required
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, r#"{"theme":"dark","hooks":{"AfterTool":[{"matcher":"","hooks":[{"type":"command","command":"my-hook"}]}]}}"#).unwrap();
+        assert!(!gemini.installed());
+        assert!(audit(&gemini, false).is_empty());
+        assert_eq!(audit(&gemini, true).len(), 4);
+
+        gemini.install().unwrap();
+        assert!(gemini.installed());
+        let settings = read_settings(&path).unwrap();
+        assert_eq!(settings["theme"], "dark");
+        assert_eq!(settings["hooks"]["AfterTool"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            settings["hooks"]["SessionStart"][0]["hooks"][0]["command"],
+            format!(
+                "edict hooks run session-start --json --edict-version {}",
+                harness::VERSION
+            )
+        );
+        assert!(audit(&gemini, true).is_empty());
+
+        // A hand-removed event is reported
+        let mut settings = settings;
+        settings["hooks"].as_object_mut().unwrap().remove("SessionEnd");
+        write_settings(&path, &settings).unwrap();
+        assert_eq!(
+            audit(&gemini, false),
+            vec!["Gemini CLI hook 'session-end' not registered for SessionEnd in settings"]
+        );
+
+        assert!(remove_settings_hooks(&path).unwrap());
+        let settings = read_settings(&path).unwrap();
+        assert_eq!(settings["hooks"]["AfterTool"].as_array().unwrap().len(), 1);
+        assert!(settings["hooks"].get("SessionStart").is_none());
+        assert!(!remove_settings_hooks(&path).unwrap());
+    }
+
+    #[test]
+    fn audit_flags_outdated_duplicated_and_edited_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("settings.json");
+        let claude = location("Claude Code", &path);
+        claude.install().unwrap();
+        assert!(audit(&claude, true).is_empty());
+
+        let mut settings = read_settings(&path).unwrap();
+        let hooks = &mut settings["hooks"];
+        // Unversioned entry from an older edict, next to the current one
+        hooks["PostToolUse"].as_array_mut().unwrap().push(json!({
+            "matcher": "",
+            "hooks": [{"type": "command", "command": "edict hooks run post-tool-call"}]
+        }));
+        // Current version, but the command was changed
+        hooks["SessionEnd"][0]["hooks"][0]["command"] = json!(format!(
+            "edict hooks run session-end --release --edict-version {}",
+            harness::VERSION
+        ));
+        write_settings(&path, &settings).unwrap();
+
+        let issues = audit(&claude, true);
+        assert_eq!(issues.len(), 3, "{issues:?}");
+        assert!(issues[0].contains("outdated (installed by edict before versioned hooks)"));
+        assert!(issues[1].contains("SessionEnd") && issues[1].contains("edited by hand"));
+        assert!(issues[2].contains("PostToolUse has 2 entries for 'post-tool-call'"));
+
+        // --fix reinstalls
+        claude.install().unwrap();
+        assert!(audit(&claude, true).is_empty());
+    }
+
+    #[test]
+    fn plugins_are_versioned() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("edict-hooks.ts");
+        let opencode = location("opencode", &path);
+        assert!(audit(&opencode, false).is_empty());
+
+        opencode.install().unwrap();
+        assert!(audit(&opencode, false).is_empty());
+
+        fs::write(&path, harness::OPENCODE_EDICT_HOOKS_PLUGIN).unwrap();
+        assert!(audit(&opencode, false)[0].contains("outdated"));
+
+        let edited = format!(
+            "{}\n// local tweak",
+            harness::plugin_source(harness::OPENCODE_EDICT_HOOKS_PLUGIN)
+        );
+        fs::write(&path, edited).unwrap();
+        assert!(audit(&opencode, false)[0].contains("edited by hand"));
+    }
+
     #[test]
     fn is_botbox_hook_entry_detects_old_format() {
         let entry = json!({
@@ -11,13 +11,14 @@ use crate::config::{
 };
 use crate::error::ExitError;
 use crate::subprocess::{Tool, run_command};
-use crate::template::render_agents_md;
+use crate::template::{TemplateContext, render_agents_md};
 
 const PROJECT_TYPES: &[&str] = &["api", "cli", "frontend", "library", "monorepo", "tui"];
 const AVAILABLE_TOOLS: &[&str] = &["bones", "maw", "seal", "rite", "vessel"];
 const REVIEWER_ROLES: &[&str] = &["security"];
 const LANGUAGES: &[&str] = &["rust", "python", "node", "go", "typescript", "java"];
-const CONFIG_VERSION: &str = "1.0.16";
+/// New projects start with every sync migration applied.
+const CONFIG_VERSION: &str = super::migrations::LATEST;
 
 /// Validate that a name (project, reviewer role) matches [a-z0-9][a-z0-9-]* and is ≤64 chars.
 /// Prevents command injection and path traversal via user-supplied names.
@@ -170,21 +171,27 @@ impl InitArgs {
             println!("Generated {}", config::CONFIG_TOML);
         }
 
-        // Copy workflow docs (reuse sync logic)
-        sync_workflow_docs(&agents_dir)?;
+        // Copy workflow docs (reuse sync logic: rendered for the config, with
+        // .agents/edict-local/ overrides)
+        let ctx = TemplateContext::from_config(&config);
+        DocSet::layer_rendered(&project_dir, "", WORKFLOW_DOCS, &ctx)?
+            .write(&agents_dir, ".version")?;
         println!("Copied workflow docs");
 
         // Copy prompt templates
-        sync_prompts(&agents_dir)?;
+        DocSet::layer(&project_dir, "prompts", REVIEWER_PROMPTS)?
+            .write(&agents_dir.join("prompts"), ".prompts-version")?;
         println!("Copied prompt templates");
 
         // Copy design docs
-        sync_design_docs(&agents_dir)?;
+        DocSet::layer_rendered(&project_dir, "design", DESIGN_DOCS, &ctx)?
+            .write(&agents_dir.join("design"), ".design-docs-version")?;
         println!("Copied design docs");
 
         // Install global agent hooks (idempotent)
         crate::commands::hooks::HooksCommand::Install {
             project_root: Some(project_dir.clone()),
+            scope: crate::commands::hooks::HookScope::User,
         }
         .execute()
         .unwrap_or_else(|e| eprintln!("Warning: failed to install global hooks: {e}"));
@@ -709,6 +716,7 @@ fn build_config(choices: &InitChoices) -> Config {
             release_instructions: None,
             check_command: choices.check_command.clone(),
             critical_approvers: None,
+            trunk: None,
         },
         tools: ToolsConfig {
             bones: choices.tools.contains(&"bones".to_string()),
@@ -720,6 +728,11 @@ fn build_config(choices: &InitChoices) -> Config {
         review: ReviewConfig {
             enabled: !choices.reviewers.is_empty(),
             reviewers: choices.reviewers.clone(),
+            roles: Default::default(),
+            rules: Default::default(),
+            critical: Default::default(),
+            policy: Default::default(),
+            checks: Default::default(),
         },
         push_main: false,
         agents: AgentsConfig {
@@ -752,6 +765,8 @@ fn build_config(choices: &InitChoices) -> Config {
             responder: None,
         },
         models: Default::default(),
+        secrets: Default::default(),
+        hooks: Default::default(),
         env: build_default_env(&choices.languages),
     }
 }
@@ -770,70 +785,9 @@ fn build_default_env(languages: &[String]) -> std::collections::HashMap<String,
 
 // --- Sync helpers (reuse embedded content from sync.rs) ---
 
-// Re-embed the same workflow docs as sync.rs
+use crate::commands::overrides::DocSet;
 use crate::commands::sync::{DESIGN_DOCS, REVIEWER_PROMPTS, WORKFLOW_DOCS};
 
-fn sync_workflow_docs(agents_dir: &Path) -> Result<()> {
-    for (name, content) in WORKFLOW_DOCS {
-        let path = agents_dir.join(name);
-        fs::write(&path, content).with_context(|| format!("writing {}", path.display()))?;
-    }
-
-    // Write version marker
-    use sha2::{Digest, Sha256};
-    let mut hasher = Sha256::new();
-    for (name, content) in WORKFLOW_DOCS {
-        hasher.update(name.as_bytes());
-        hasher.update(content.as_bytes());
-    }
-    let version = format!("{:x}", hasher.finalize());
-    fs::write(agents_dir.join(".version"), &version[..32])?;
-
-    Ok(())
-}
-
-fn sync_prompts(agents_dir: &Path) -> Result<()> {
-    let prompts_dir = agents_dir.join("prompts");
-    fs::create_dir_all(&prompts_dir)?;
-
-    for (name, content) in REVIEWER_PROMPTS {
-        let path = prompts_dir.join(name);
-        fs::write(&path, content).with_context(|| format!("writing {}", path.display()))?;
-    }
-
-    use sha2::{Digest, Sha256};
-    let mut hasher = Sha256::new();
-    for (name, content) in REVIEWER_PROMPTS {
-        hasher.update(name.as_bytes());
-        hasher.update(content.as_bytes());
-    }
-    let version = format!("{:x}", hasher.finalize());
-    fs::write(prompts_dir.join(".prompts-version"), &version[..32])?;
-
-    Ok(())
-}
-
-fn sync_design_docs(agents_dir: &Path) -> Result<()> {
-    let design_dir = agents_dir.join("design");
-    fs::create_dir_all(&design_dir)?;
-
-    for (name, content) in DESIGN_DOCS {
-        let path = design_dir.join(name);
-        fs::write(&path, content).with_context(|| format!("writing {}", path.display()))?;
-    }
-
-    use sha2::{Digest, Sha256};
-    let mut hasher = Sha256::new();
-    for (name, content) in DESIGN_DOCS {
-        hasher.update(name.as_bytes());
-        hasher.update(content.as_bytes());
-    }
-    let version = format!("{:x}", hasher.finalize());
-    fs::write(design_dir.join(".design-docs-version"), &version[..32])?;
-
-    Ok(())
-}
-
 // sync_hooks removed — hooks are now installed globally via `edict hooks install`
 
 // --- Hook registration ---
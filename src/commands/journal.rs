@@ -0,0 +1,671 @@
+//! Structured iteration journal shared by the dev-loop and reviewer-loop.
+//!
+//! Each loop iteration appends one JSON object per line (JSONL) to a journal file
+//! in the project cache dir. Entries are pruned by a retention policy rather than
+//! truncated at session start, so `edict journal show/search` can answer "what did
+//! this agent do last night?".
+
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use chrono::{DateTime, SecondsFormat, Utc};
+use clap::Subcommand;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::doctor::OutputFormat;
+use super::state_file;
+use crate::subprocess::Tool;
+
+/// File name of the dev-loop journal inside the project cache dir.
+pub const DEV_LOOP_JOURNAL: &str = "dev-loop.jsonl";
+
+/// File name of a reviewer-loop journal for the given role.
+pub fn review_loop_journal(role: &str) -> String {
+    format!("review-loop-{role}.jsonl")
+}
+
+/// Whether a cache-dir file name is one of the loop journals (not some other JSONL store).
+fn is_journal_file(name: &str) -> bool {
+    name == DEV_LOOP_JOURNAL
+        || name
+            .strip_prefix("review-loop-")
+            .and_then(|rest| rest.strip_suffix(".jsonl"))
+            .is_some_and(|role| !role.is_empty())
+}
+
+/// Entries older than this are dropped when the journal is appended to.
+const RETENTION_MAX_AGE_DAYS: i64 = 30;
+/// At most this many entries are kept per journal file (newest win).
+const RETENTION_MAX_ENTRIES: usize = 500;
+
+/// How an iteration ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// Agent emitted `<promise>COMPLETE</promise>` (or finished cleanly).
+    Complete,
+    /// Agent emitted `<promise>END_OF_STORY</promise>` — more work remains.
+    EndOfStory,
+    /// Agent exited without a completion signal.
+    NoSignal,
+    /// The agent subprocess failed.
+    Error,
+}
+
+impl Outcome {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Complete => "complete",
+            Self::EndOfStory => "end_of_story",
+            Self::NoSignal => "no_signal",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// One journal line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub iteration: u32,
+    pub agent: String,
+    /// RFC 3339 UTC timestamp when the iteration started.
+    pub started_at: String,
+    /// RFC 3339 UTC timestamp when the iteration finished.
+    pub finished_at: String,
+    /// Short git commit of the default workspace at the end of the iteration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change: Option<String>,
+    pub outcome: Outcome,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bones: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workers: Vec<String>,
+    #[serde(default)]
+    pub summary: String,
+}
+
+impl JournalEntry {
+    /// Build an entry for an iteration that started at `started_at` and finishes now.
+    ///
+    /// Bone IDs are extracted from the summary; worker names are extracted from the
+    /// full agent output (`<agent>/<suffix>` spawn names).
+    pub fn finish(
+        iteration: u32,
+        agent: &str,
+        started_at: DateTime<Utc>,
+        outcome: Outcome,
+        summary: &str,
+        output: &str,
+    ) -> Self {
+        Self {
+            iteration,
+            agent: agent.to_string(),
+            started_at: started_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            finished_at: now_rfc3339(),
+            change: get_git_change_id(),
+            outcome,
+            bones: extract_bone_ids(summary),
+            workers: extract_worker_names(output, agent),
+            summary: summary.trim().to_string(),
+        }
+    }
+
+    /// Render the entry as the previous-iteration block injected into loop prompts.
+    pub fn to_prompt_text(&self) -> String {
+        let mut header = format!("Iteration {} ({})", self.iteration, self.outcome.as_str());
+        if let Some(change) = &self.change {
+            header.push_str(&format!(" | git:{change}"));
+        }
+        let mut text = header;
+        if !self.bones.is_empty() {
+            text.push_str(&format!("\nBones: {}", self.bones.join(", ")));
+        }
+        if !self.workers.is_empty() {
+            text.push_str(&format!("\nWorkers: {}", self.workers.join(", ")));
+        }
+        if !self.summary.is_empty() {
+            text.push_str(&format!("\n{}", self.summary));
+        }
+        text
+    }
+
+    fn matches(&self, needle: &str) -> bool {
+        let needle = needle.to_lowercase();
+        self.summary.to_lowercase().contains(&needle)
+            || self.bones.iter().any(|b| b.to_lowercase().contains(&needle))
+            || self.workers.iter().any(|w| w.to_lowercase().contains(&needle))
+            || self.outcome.as_str().contains(&needle)
+            || self.change.as_deref().is_some_and(|c| c.contains(&needle))
+    }
+}
+
+/// Journal for recording loop iteration history.
+///
+/// Stored under `~/.cache/edict/projects/<slug>/` (XDG-compliant).
+pub struct Journal {
+    path: PathBuf,
+}
+
+/// Data from a previous iteration journal entry.
+pub struct LastIteration {
+    pub content: String,
+    pub age: String,
+}
+
+impl Journal {
+    /// The dev-loop journal for the given project root.
+    pub fn new(project_root: &Path) -> Self {
+        Self::at(cache_dir(project_root).join(DEV_LOOP_JOURNAL))
+    }
+
+    /// A journal at an explicit path (used by the reviewer-loop).
+    pub const fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Append an entry, then apply the retention policy.
+    pub fn append(&self, entry: &JournalEntry) {
+        let line = match serde_json::to_string(entry) {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("Warning: Failed to serialize journal entry: {e}");
+                return;
+            }
+        };
+
+        if let Err(e) = state_file::append_line(&self.path, &line) {
+            eprintln!("Warning: Failed to append to journal: {e:#}");
+            return;
+        }
+
+        self.prune(Utc::now());
+    }
+
+    /// All parseable entries, oldest first. Malformed lines are skipped.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        let Ok(content) = fs::read_to_string(&self.path) else {
+            return Vec::new();
+        };
+        content
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect()
+    }
+
+    /// Read the last iteration entry from the journal, with age info.
+    pub fn read_last(&self) -> Option<LastIteration> {
+        let last = self.entries().pop()?;
+        let age = DateTime::parse_from_rfc3339(&last.finished_at)
+            .map(|t| format_age(Utc::now() - t.with_timezone(&Utc)))
+            .unwrap_or_else(|_| "unknown age".to_string());
+        Some(LastIteration {
+            content: last.to_prompt_text(),
+            age,
+        })
+    }
+
+    /// Drop entries older than the retention window and cap the entry count. The
+    /// rewrite holds the journal's lock, so concurrent appends are never lost.
+    fn prune(&self, now: DateTime<Utc>) {
+        if let Err(e) = state_file::rewrite(&self.path, |content| prune_lines(content, now)) {
+            eprintln!("Warning: Failed to prune journal: {e:#}");
+        }
+    }
+}
+
+/// The journal content with the retention policy applied, or `None` if nothing
+/// needs dropping.
+fn prune_lines(content: &str, now: DateTime<Utc>) -> Option<String> {
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    let cutoff = now - chrono::Duration::days(RETENTION_MAX_AGE_DAYS);
+
+    let mut kept: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|l| {
+            serde_json::from_str::<JournalEntry>(l).is_ok_and(|e| {
+                DateTime::parse_from_rfc3339(&e.finished_at)
+                    .is_ok_and(|t| t.with_timezone(&Utc) >= cutoff)
+            })
+        })
+        .collect();
+    if kept.len() > RETENTION_MAX_ENTRIES {
+        kept.drain(..kept.len() - RETENTION_MAX_ENTRIES);
+    }
+    if kept.len() == lines.len() {
+        return None;
+    }
+
+    let mut out = kept.join("\n");
+    out.push('\n');
+    Some(out)
+}
+
+/// Get the XDG-compliant cache directory for a project.
+pub fn cache_dir(project_root: &Path) -> PathBuf {
+    let base = if let Ok(xdg) = std::env::var("XDG_CACHE_HOME") {
+        PathBuf::from(xdg)
+    } else if cfg!(target_os = "macos") {
+        dirs_home().join("Library/Caches")
+    } else {
+        dirs_home().join(".cache")
+    };
+
+    // Slugify the project path
+    let canonical = project_root
+        .canonicalize()
+        .unwrap_or_else(|_| project_root.to_path_buf());
+    let slug = canonical
+        .to_string_lossy()
+        .replace(['/', '\\'], "-")
+        .trim_start_matches('-')
+        .to_string();
+
+    base.join("edict").join("projects").join(slug)
+}
+
+/// Get the home directory.
+fn dirs_home() -> PathBuf {
+    std::env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("/tmp"))
+}
+
+/// Get the current git HEAD short hash from the default workspace.
+fn get_git_change_id() -> Option<String> {
+    let output = Tool::new("git")
+        .args(&["rev-parse", "--short", "HEAD"])
+        .in_workspace("default")
+        .ok()?
+        .run()
+        .ok()?;
+
+    if output.success() {
+        let id = output.stdout.trim().to_string();
+        if id.is_empty() { None } else { Some(id) }
+    } else {
+        None
+    }
+}
+
+fn now_rfc3339() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Extract unique bone IDs (`bn-xxxx`, legacy `bd-xxxx`) in order of first mention.
+pub(crate) fn extract_bone_ids(text: &str) -> Vec<String> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"\b(?:bn|bd)-[a-z0-9]{3,}\b").unwrap());
+    let mut ids: Vec<String> = Vec::new();
+    for m in re.find_iter(text) {
+        if !ids.iter().any(|id| id == m.as_str()) {
+            ids.push(m.as_str().to_string());
+        }
+    }
+    ids
+}
+
+/// Extract unique worker names spawned under this agent (`<agent>/<suffix>`).
+fn extract_worker_names(text: &str, agent: &str) -> Vec<String> {
+    let Ok(re) = Regex::new(&format!(r"{}/[A-Za-z0-9][A-Za-z0-9_-]*", regex::escape(agent)))
+    else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = Vec::new();
+    for m in re.find_iter(text) {
+        if !names.iter().any(|n| n == m.as_str()) {
+            names.push(m.as_str().to_string());
+        }
+    }
+    names
+}
+
+/// Format an elapsed duration as a human-readable age.
+fn format_age(elapsed: chrono::Duration) -> String {
+    let mins = elapsed.num_minutes().max(0);
+    let hours = mins / 60;
+
+    if hours > 0 {
+        format!("{hours}h ago")
+    } else {
+        format!("{mins}m ago")
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum JournalCommand {
+    /// Show recent journal entries (dev-loop and reviewer-loop)
+    Show {
+        /// Only show entries written by this agent
+        #[arg(long)]
+        agent: Option<String>,
+        /// Maximum number of entries to show (newest last)
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        /// Project root directory
+        #[arg(long)]
+        project_root: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Search journal entries by summary, bone ID, worker, or outcome
+    Search {
+        /// Case-insensitive text to search for
+        query: String,
+        /// Only search entries written by this agent
+        #[arg(long)]
+        agent: Option<String>,
+        /// Maximum number of matches to show (newest last)
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Project root directory
+        #[arg(long)]
+        project_root: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Show messages the responder processed: outcome, why it didn't act, and what it spawned
+    Messages {
+        /// Only show this message ID
+        #[arg(long)]
+        id: Option<String>,
+        /// Only show messages from this sender
+        #[arg(long)]
+        sender: Option<String>,
+        /// Case-insensitive text to search message bodies, reasons, bones, and agents for
+        #[arg(long)]
+        search: Option<String>,
+        /// Maximum number of messages to show (newest last)
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Project root directory
+        #[arg(long)]
+        project_root: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+}
+
+impl JournalCommand {
+    pub fn execute(&self) -> anyhow::Result<()> {
+        match self {
+            Self::Show {
+                agent,
+                limit,
+                project_root,
+                format,
+            } => show(project_root.as_deref(), agent.as_deref(), None, *limit, *format),
+            Self::Search {
+                query,
+                agent,
+                limit,
+                project_root,
+                format,
+            } => show(
+                project_root.as_deref(),
+                agent.as_deref(),
+                Some(query),
+                *limit,
+                *format,
+            ),
+            Self::Messages {
+                id,
+                sender,
+                search,
+                limit,
+                project_root,
+                format,
+            } => crate::commands::responder::processed::show(
+                project_root.as_deref(),
+                id.as_deref(),
+                sender.as_deref(),
+                search.as_deref(),
+                *limit,
+                *format,
+            ),
+        }
+    }
+}
+
+fn show(
+    project_root: Option<&Path>,
+    agent: Option<&str>,
+    query: Option<&str>,
+    limit: usize,
+    format: Option<OutputFormat>,
+) -> anyhow::Result<()> {
+    let root = match project_root {
+        Some(p) => p.to_path_buf(),
+        None => std::env::current_dir().context("getting current directory")?,
+    };
+    let format = format.unwrap_or_else(|| {
+        if std::io::stdout().is_terminal() {
+            OutputFormat::Pretty
+        } else {
+            OutputFormat::Text
+        }
+    });
+
+    let entries = select_entries(&collect_entries(&cache_dir(&root)), agent, query, limit);
+
+    match format {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({ "entries": entries }))?
+            );
+        }
+        OutputFormat::Pretty | OutputFormat::Text => {
+            if entries.is_empty() {
+                println!("No journal entries found.");
+            }
+            for entry in &entries {
+                let mut line = format!(
+                    "{}  {}  #{}  {}",
+                    entry.finished_at,
+                    entry.agent,
+                    entry.iteration,
+                    entry.outcome.as_str()
+                );
+                if let Some(change) = &entry.change {
+                    line.push_str(&format!("  git:{change}"));
+                }
+                println!("{line}");
+                if !entry.bones.is_empty() {
+                    println!("  bones:   {}", entry.bones.join(", "));
+                }
+                if !entry.workers.is_empty() {
+                    println!("  workers: {}", entry.workers.join(", "));
+                }
+                for summary_line in entry.summary.lines() {
+                    println!("  {summary_line}");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Read the dev-loop and reviewer-loop journals in the cache dir, merged and sorted by finish time.
+///
+/// Other JSONL stores share the cache dir (snapshots, overrides, ...) and are skipped.
+fn collect_entries(dir: &Path) -> Vec<JournalEntry> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<JournalEntry> = read_dir
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(is_journal_file)
+        })
+        .flat_map(|p| Journal::at(p).entries())
+        .collect();
+    entries.sort_by(|a, b| a.finished_at.cmp(&b.finished_at));
+    entries
+}
+
+fn select_entries(
+    entries: &[JournalEntry],
+    agent: Option<&str>,
+    query: Option<&str>,
+    limit: usize,
+) -> Vec<JournalEntry> {
+    let matched: Vec<&JournalEntry> = entries
+        .iter()
+        .filter(|e| agent.is_none_or(|a| e.agent == a))
+        .filter(|e| query.is_none_or(|q| e.matches(q)))
+        .collect();
+    let skip = matched.len().saturating_sub(limit);
+    matched.into_iter().skip(skip).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(iteration: u32, agent: &str, finished_at: &str, summary: &str) -> JournalEntry {
+        JournalEntry {
+            iteration,
+            agent: agent.to_string(),
+            started_at: finished_at.to_string(),
+            finished_at: finished_at.to_string(),
+            change: None,
+            outcome: Outcome::Complete,
+            bones: extract_bone_ids(summary),
+            workers: Vec::new(),
+            summary: summary.to_string(),
+        }
+    }
+
+    #[test]
+    fn extract_bone_ids_dedupes_in_order() {
+        assert_eq!(
+            extract_bone_ids("Worked bn-3smm, reviewed bn-abc and bn-3smm again. bd-old1"),
+            vec!["bn-3smm", "bn-abc", "bd-old1"]
+        );
+        assert!(extract_bone_ids("no bones here").is_empty());
+    }
+
+    #[test]
+    fn extract_worker_names_scoped_to_agent() {
+        let output = "vessel spawn --name myapp-dev/amber-reef ...\n\
+                      myapp-dev/amber-reef done; other-dev/x ignored; myapp-dev/blue-fox";
+        assert_eq!(
+            extract_worker_names(output, "myapp-dev"),
+            vec!["myapp-dev/amber-reef", "myapp-dev/blue-fox"]
+        );
+    }
+
+    #[test]
+    fn append_and_read_last_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Journal::at(dir.path().join(DEV_LOOP_JOURNAL));
+        assert!(journal.read_last().is_none());
+
+        let now = now_rfc3339();
+        journal.append(&entry(1, "myapp-dev", &now, "first"));
+        journal.append(&entry(2, "myapp-dev", &now, "Closed bn-abc"));
+
+        assert_eq!(journal.entries().len(), 2);
+        let last = journal.read_last().unwrap();
+        assert!(last.content.starts_with("Iteration 2 (complete)"));
+        assert!(last.content.contains("Bones: bn-abc"));
+        assert!(last.content.contains("Closed bn-abc"));
+        assert_eq!(last.age, "0m ago");
+    }
+
+    #[test]
+    fn prune_drops_expired_and_malformed_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(DEV_LOOP_JOURNAL);
+        let old = serde_json::to_string(&entry(1, "a", "2020-01-01T00:00:00Z", "old")).unwrap();
+        let new = serde_json::to_string(&entry(2, "a", &now_rfc3339(), "new")).unwrap();
+        fs::write(&path, format!("{old}\nnot json\n{new}\n")).unwrap();
+
+        let journal = Journal::at(path);
+        journal.prune(Utc::now());
+
+        let entries = journal.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].summary, "new");
+    }
+
+    #[test]
+    fn concurrent_appends_survive_pruning() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(DEV_LOOP_JOURNAL);
+        let now = now_rfc3339();
+        let full: String = (0..RETENTION_MAX_ENTRIES)
+            .map(|i| serde_json::to_string(&entry(0, "old", &now, &i.to_string())).unwrap() + "\n")
+            .collect();
+        fs::write(&path, full).unwrap();
+
+        // At the cap, every append prunes the oldest entry by rewriting the file
+        let threads: Vec<_> = (0..4)
+            .map(|t| {
+                let journal = Journal::at(path.clone());
+                let now = now.clone();
+                std::thread::spawn(move || {
+                    for i in 0..25 {
+                        journal.append(&entry(i, &format!("agent-{t}"), &now, "new"));
+                    }
+                })
+            })
+            .collect();
+        for t in threads {
+            t.join().unwrap();
+        }
+
+        let entries = Journal::at(path).entries();
+        assert_eq!(entries.len(), RETENTION_MAX_ENTRIES);
+        assert_eq!(entries.iter().filter(|e| e.summary == "new").count(), 100);
+    }
+
+    #[test]
+    fn collect_entries_reads_only_loop_journals() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = now_rfc3339();
+        Journal::at(dir.path().join(DEV_LOOP_JOURNAL)).append(&entry(1, "dev", &now, "dev"));
+        Journal::at(dir.path().join(review_loop_journal("security")))
+            .append(&entry(1, "sec", &now, "review"));
+        Journal::at(dir.path().join("review-snapshots.jsonl"))
+            .append(&entry(1, "other", &now, "snapshot"));
+
+        let mut agents: Vec<String> = collect_entries(dir.path())
+            .into_iter()
+            .map(|e| e.agent)
+            .collect();
+        agents.sort();
+        assert_eq!(agents, vec!["dev", "sec"]);
+    }
+
+    #[test]
+    fn select_entries_filters_by_agent_and_query() {
+        let entries = vec![
+            entry(1, "myapp-dev", "2026-01-01T00:00:00Z", "Fixed jj divergence on bn-abc"),
+            entry(2, "myapp-security", "2026-01-01T00:01:00Z", "Reviewed bn-abc"),
+            entry(3, "myapp-dev", "2026-01-01T00:02:00Z", "Dispatched workers"),
+        ];
+
+        let by_agent = select_entries(&entries, Some("myapp-dev"), None, 10);
+        assert_eq!(by_agent.len(), 2);
+
+        let by_query = select_entries(&entries, None, Some("BN-ABC"), 10);
+        assert_eq!(by_query.len(), 2);
+
+        let limited = select_entries(&entries, None, None, 1);
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].iteration, 3);
+    }
+}
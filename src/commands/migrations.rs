@@ -0,0 +1,1004 @@
+//! Versioned migrations run by `edict sync` (see notes/migration-system.md).
+//!
+//! Each migration has a version id. The config's `version` records the last one
+//! applied; `edict sync` runs the newer ones in order and records each as it
+//! succeeds, so a failed run resumes where it stopped. Migrations are idempotent and
+//! treat missing tools and files as nothing to do. `edict init` writes the latest
+//! version, since a fresh project needs none of them.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::config::Config;
+use crate::error::ExitError;
+use crate::subprocess::{Tool, run_command};
+
+/// What a migration runs against.
+pub struct MigrationContext<'a> {
+    pub project_root: &'a Path,
+    pub config_path: &'a Path,
+    pub config: &'a Config,
+}
+
+pub struct Migration {
+    /// Version (`x.y.z`) recorded in the config once applied.
+    pub id: &'static str,
+    pub title: &'static str,
+    up: fn(&MigrationContext) -> Result<()>,
+}
+
+/// All migrations, in ascending version order.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        id: "1.0.17",
+        title: "Re-register legacy rite hooks as `edict run` commands",
+        up: |ctx| {
+            migrate_rite_hooks(ctx.config);
+            Ok(())
+        },
+    },
+    Migration {
+        id: "1.0.18",
+        title: "Rename botbox: rite hooks to edict:",
+        up: |ctx| {
+            migrate_botbox_rite_hooks_to_edict(ctx.config, ctx.project_root);
+            Ok(())
+        },
+    },
+    Migration {
+        id: "1.0.19",
+        title: "Point maw v2 hook --cwd at the repo root",
+        up: |ctx| {
+            migrate_hook_cwd(ctx.config, ctx.project_root);
+            Ok(())
+        },
+    },
+    Migration {
+        id: "1.0.20",
+        title: "Move the router hook claim from -router to -dev",
+        up: |ctx| {
+            migrate_router_hook_claim(ctx.config, ctx.project_root);
+            Ok(())
+        },
+    },
+    Migration {
+        id: "1.0.21",
+        title: "Replace botty with vessel (config and rite hooks)",
+        up: |ctx| {
+            migrate_vessel_hooks(ctx.config, ctx.project_root, ctx.config_path);
+            Ok(())
+        },
+    },
+    Migration {
+        id: "1.0.22",
+        title: "Replace beads with bones (config, data, tooling files)",
+        up: |ctx| migrate_beads_to_bones(ctx.project_root, ctx.config_path),
+    },
+];
+
+/// Version of the newest migration.
+pub const LATEST: &str = "1.0.22";
+
+/// Parse `x.y.z`; anything unreadable counts as `0.0.0` (before all migrations).
+fn parse_version(version: &str) -> (u64, u64, u64) {
+    let mut parts = version.trim().split('.').map(|p| p.parse::<u64>());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => (major, minor, patch),
+        _ => (0, 0, 0),
+    }
+}
+
+/// Migrations newer than `applied`, in order.
+pub fn pending<'a>(registry: &'a [Migration], applied: &str) -> Vec<&'a Migration> {
+    let applied = parse_version(applied);
+    registry
+        .iter()
+        .filter(|m| parse_version(m.id) > applied)
+        .collect()
+}
+
+/// Run the migrations newer than the config's version, recording each in the config
+/// file as it succeeds. Stops at the first failure. Returns the versions applied.
+pub fn run_pending(
+    registry: &'static [Migration],
+    ctx: &MigrationContext,
+) -> Result<Vec<&'static str>> {
+    let mut applied = Vec::new();
+    for migration in pending(registry, &ctx.config.version) {
+        println!("Migration {}: {}", migration.id, migration.title);
+        (migration.up)(ctx).map_err(|e| {
+            ExitError::Other(format!(
+                "migration {} ({}) failed: {e:#}. Fix the cause and re-run `edict sync`; \
+                 completed migrations are not repeated",
+                migration.id, migration.title
+            ))
+        })?;
+        record_version(ctx.config_path, migration.id)?;
+        applied.push(migration.id);
+    }
+    Ok(applied)
+}
+
+/// Set the config's `version`, leaving the rest of the file as written.
+pub fn record_version(config_path: &Path, version: &str) -> Result<()> {
+    let content = fs::read_to_string(config_path)
+        .with_context(|| format!("reading {}", config_path.display()))?;
+    let updated = if config_path.extension().is_some_and(|e| e == "json") {
+        let mut value: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("parsing {}", config_path.display()))?;
+        value["version"] = serde_json::Value::from(version);
+        serde_json::to_string_pretty(&value)? + "\n"
+    } else {
+        let mut doc: toml_edit::DocumentMut = content
+            .parse()
+            .with_context(|| format!("parsing {}", config_path.display()))?;
+        doc["version"] = toml_edit::value(version);
+        doc.to_string()
+    };
+    fs::write(config_path, updated).with_context(|| format!("writing {}", config_path.display()))
+}
+
+/// `edict sync --list-migrations`: every migration and whether the project has it.
+pub fn list(applied: &str) {
+    let pending = pending(MIGRATIONS, applied);
+    for migration in MIGRATIONS {
+        let state = if pending.iter().any(|m| m.id == migration.id) {
+            "pending"
+        } else {
+            "applied"
+        };
+        println!("{}\t{state}\t{}", migration.id, migration.title);
+    }
+    println!();
+    println!(
+        "Recorded version: {applied} ({} pending, latest {LATEST})",
+        pending.len()
+    );
+}
+
+/// Migrate rite hooks from `botbox:` descriptions to `edict:` descriptions.
+///
+/// Finds hooks with `botbox:{name}:responder` or `botbox:{name}:reviewer-*` descriptions,
+/// removes them, and re-registers with `edict:` prefix and `edict run` commands.
+/// Called during `edict sync` on projects that were previously set up with `botbox`.
+fn migrate_botbox_rite_hooks_to_edict(config: &Config, project_root: &Path) {
+    let output = match Tool::new("rite")
+        .args(&["hooks", "list", "--format", "json"])
+        .run()
+    {
+        Ok(o) if o.success() => o,
+        _ => return,
+    };
+
+    let parsed: serde_json::Value = match serde_json::from_str(&output.stdout) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let hooks = match parsed.get("hooks").and_then(|h| h.as_array()) {
+        Some(h) => h,
+        None => return,
+    };
+
+    let name = &config.project.name;
+
+    // Resolve the correct cwd (bare root or project root)
+    let bare_root = if project_root.ends_with("ws/default") {
+        project_root
+            .parent()
+            .and_then(Path::parent)
+            .filter(|r| r.join(".manifold").exists())
+    } else if project_root.join(".manifold").exists() {
+        Some(project_root)
+    } else {
+        None
+    };
+    let root_str = bare_root
+        .map(|r| r.display().to_string())
+        .unwrap_or_else(|| project_root.display().to_string());
+
+    for hook in hooks {
+        let desc = hook
+            .get("description")
+            .and_then(|d| d.as_str())
+            .unwrap_or("");
+
+        // Only process botbox-era hooks for this project
+        if !desc.starts_with(&format!("botbox:{name}:")) {
+            continue;
+        }
+
+        let id = match hook.get("id").and_then(|i| i.as_str()) {
+            Some(id) => id,
+            None => continue,
+        };
+
+        // Remove old botbox hook
+        if Tool::new("rite")
+            .args(&["hooks", "remove", id])
+            .run()
+            .is_err()
+        {
+            tracing::warn!(hook_id = %id, "failed to remove botbox-era hook during edict migration");
+            continue;
+        }
+
+        let agent = config.default_agent();
+        if desc.ends_with(":responder") {
+            let responder_ml = config
+                .agents
+                .responder
+                .as_ref()
+                .and_then(|r| r.memory_limit.as_deref());
+            super::init::register_router_hook(&root_str, &root_str, name, &agent, responder_ml);
+            println!("  Migrated hook {desc} → edict:{name}:responder");
+        } else if let Some(role) = desc.strip_prefix(&format!("botbox:{name}:reviewer-")) {
+            let reviewer_agent = format!("{name}-{role}");
+            let reviewer_ml = config
+                .agents
+                .reviewer
+                .as_ref()
+                .and_then(|r| r.memory_limit.as_deref());
+            super::init::register_reviewer_hook(
+                &root_str,
+                &root_str,
+                name,
+                &agent,
+                &reviewer_agent,
+                reviewer_ml,
+            );
+            println!("  Migrated hook {desc} → edict:{name}:reviewer-{role}");
+        }
+    }
+}
+
+/// Migrate rite hooks from legacy formats to current `edict run` commands with descriptions.
+///
+/// Lists all hooks for this project's channel, identifies legacy hooks
+/// (bun-based, old naming, missing descriptions), removes them, and
+/// re-registers via `ensure_rite_hook` with proper descriptions for
+/// future idempotent management.
+fn migrate_rite_hooks(config: &Config) {
+    let output = match Tool::new("rite")
+        .args(&["hooks", "list", "--format", "json"])
+        .run()
+    {
+        Ok(o) if o.success() => o,
+        _ => return, // rite not available, skip silently
+    };
+
+    let parsed: serde_json::Value = match serde_json::from_str(&output.stdout) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let hooks = match parsed.get("hooks").and_then(|h| h.as_array()) {
+        Some(h) => h,
+        None => return,
+    };
+
+    let name = &config.project.name;
+    let agent = config.default_agent();
+    let env_inherit = "RITE_CHANNEL,RITE_MESSAGE_ID,RITE_HOOK_ID,SSH_AUTH_SOCK,OTEL_EXPORTER_OTLP_ENDPOINT,TRACEPARENT";
+
+    for hook in hooks {
+        let id = match hook.get("id").and_then(|i| i.as_str()) {
+            Some(id) => id.to_string(),
+            None => continue,
+        };
+
+        let channel = hook.get("channel").and_then(|c| c.as_str()).unwrap_or("");
+
+        // Only migrate hooks for this project's channel
+        if channel != name {
+            continue;
+        }
+
+        // Skip hooks that already have an edict: or botbox: description (already migrated by
+        // migrate_rite_hooks or migrate_botbox_rite_hooks_to_edict respectively)
+        let existing_desc = hook
+            .get("description")
+            .and_then(|d| d.as_str())
+            .unwrap_or("");
+        if existing_desc.starts_with("edict:") || existing_desc.starts_with("botbox:") {
+            continue;
+        }
+
+        let cmd = hook.get("command").and_then(|c| c.as_array());
+        let cmd = match cmd {
+            Some(c) => c,
+            None => continue,
+        };
+
+        let cmd_strs: Vec<&str> = cmd.iter().filter_map(|v| v.as_str()).collect();
+
+        // Determine what kind of hook this is
+        let is_router = cmd_strs.iter().any(|s| {
+            s.contains("responder") || s.contains("respond.mjs") || s.contains("router.mjs")
+        });
+        let is_reviewer = cmd_strs
+            .iter()
+            .any(|s| s.contains("reviewer-loop") || s.contains("reviewer-loop.mjs"));
+
+        if !is_router && !is_reviewer {
+            continue;
+        }
+
+        let spawn_cwd = cmd_strs
+            .windows(2)
+            .find(|w| w[0] == "--cwd")
+            .map(|w| w[1])
+            .unwrap_or(".");
+
+        // Remove old hook (ensure_rite_hook handles dedup by description,
+        // but these legacy hooks have no description so we remove manually)
+        let remove = Tool::new("rite").args(&["hooks", "remove", &id]).run();
+
+        if remove.is_err() || !remove.as_ref().unwrap().success() {
+            tracing::warn!(hook_id = %id, "failed to remove legacy hook");
+            continue;
+        }
+
+        if is_router {
+            let claim_uri = format!("agent://{name}-dev");
+            let spawn_name = format!("{name}-responder");
+            let description = format!("edict:{name}:responder");
+            let responder_ml = config
+                .agents
+                .responder
+                .as_ref()
+                .and_then(|r| r.memory_limit.as_deref());
+
+            let mut router_args: Vec<&str> = vec![
+                "--agent",
+                &agent,
+                "--channel",
+                name,
+                "--claim",
+                &claim_uri,
+                "--claim-owner",
+                &agent,
+                "--cwd",
+                spawn_cwd,
+                "--ttl",
+                "600",
+                "--",
+                "vessel",
+                "spawn",
+                "--env-inherit",
+                env_inherit,
+            ];
+            if let Some(limit) = responder_ml {
+                router_args.push("--memory-limit");
+                router_args.push(limit);
+            }
+            router_args.extend_from_slice(&[
+                "--name",
+                &spawn_name,
+                "--cwd",
+                spawn_cwd,
+                "--",
+                "edict",
+                "run",
+                "responder",
+            ]);
+
+            match crate::subprocess::ensure_rite_hook(&description, &router_args) {
+                Ok(_) => println!("  Migrated router hook {id} → edict run responder"),
+                Err(e) => tracing::warn!("failed to re-register router hook: {e}"),
+            }
+        } else if is_reviewer {
+            let reviewer_agent = hook
+                .get("condition")
+                .and_then(|c| c.get("agent"))
+                .and_then(|a| a.as_str())
+                .unwrap_or("")
+                .to_string();
+
+            if reviewer_agent.is_empty() {
+                tracing::warn!(hook_id = %id, "could not determine reviewer agent for hook");
+                continue;
+            }
+
+            let role = reviewer_agent
+                .strip_prefix(&format!("{name}-"))
+                .unwrap_or(&reviewer_agent);
+            let claim_uri = format!("agent://{reviewer_agent}");
+            let description = format!("edict:{name}:reviewer-{role}");
+            let reviewer_ml = config
+                .agents
+                .reviewer
+                .as_ref()
+                .and_then(|r| r.memory_limit.as_deref());
+
+            let mut reviewer_args: Vec<&str> = vec![
+                "--agent",
+                &agent,
+                "--channel",
+                name,
+                "--mention",
+                &reviewer_agent,
+                "--claim",
+                &claim_uri,
+                "--claim-owner",
+                &reviewer_agent,
+                "--ttl",
+                "600",
+                "--priority",
+                "1",
+                "--cwd",
+                spawn_cwd,
+                "--",
+                "vessel",
+                "spawn",
+                "--env-inherit",
+                env_inherit,
+            ];
+            if let Some(limit) = reviewer_ml {
+                reviewer_args.push("--memory-limit");
+                reviewer_args.push(limit);
+            }
+            reviewer_args.extend_from_slice(&[
+                "--name",
+                &reviewer_agent,
+                "--cwd",
+                spawn_cwd,
+                "--",
+                "edict",
+                "run",
+                "reviewer-loop",
+                "--agent",
+                &reviewer_agent,
+            ]);
+
+            match crate::subprocess::ensure_rite_hook(&description, &reviewer_args) {
+                Ok(_) => println!(
+                    "  Migrated reviewer hook {id} → edict run reviewer-loop --agent {reviewer_agent}"
+                ),
+                Err(e) => {
+                    tracing::warn!(agent = %reviewer_agent, "failed to re-register reviewer hook: {e}")
+                }
+            }
+        }
+    }
+}
+
+/// Fix hook --cwd for maw v2 bare repos.
+///
+/// Earlier versions of `detect_hook_paths` checked for `.jj` to identify bare repos,
+/// which broke after the migration to Git+manifold. This re-registers hooks that have
+/// `--cwd .../ws/default` with `--cwd .../` (the repo root) instead.
+fn migrate_hook_cwd(config: &Config, project_root: &Path) {
+    // Detect maw v2: project_root may be ws/default/ (inner sync) or the bare root
+    let bare_root = if project_root.ends_with("ws/default") {
+        project_root.parent().and_then(Path::parent)
+    } else if project_root.join(".manifold").exists() {
+        Some(project_root)
+    } else {
+        None
+    };
+
+    let bare_root = match bare_root {
+        Some(r) if r.join(".manifold").exists() => r,
+        _ => return,
+    };
+
+    let ws_default_str = bare_root.join("ws").join("default").display().to_string();
+    let root_str = bare_root.display().to_string();
+
+    let output = match Tool::new("rite")
+        .args(&["hooks", "list", "--format", "json"])
+        .run()
+    {
+        Ok(o) if o.success() => o,
+        _ => return,
+    };
+
+    let parsed: serde_json::Value = match serde_json::from_str(&output.stdout) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let hooks = match parsed.get("hooks").and_then(|h| h.as_array()) {
+        Some(h) => h,
+        None => return,
+    };
+
+    let name = &config.project.name;
+    let agent = config.default_agent();
+    let reviewers: Vec<String> = config
+        .review
+        .reviewers
+        .iter()
+        .map(|r| format!("{name}-{r}"))
+        .collect();
+
+    for hook in hooks {
+        let desc = hook
+            .get("description")
+            .and_then(|d| d.as_str())
+            .unwrap_or("");
+        // Accept both current and legacy description prefixes
+        let is_ours = desc.starts_with(&format!("edict:{name}:"))
+            || desc.starts_with(&format!("botbox:{name}:"));
+        if !is_ours {
+            continue;
+        }
+
+        let cmd = match hook.get("command").and_then(|c| c.as_array()) {
+            Some(c) => c,
+            None => continue,
+        };
+        let cmd_strs: Vec<&str> = cmd.iter().filter_map(|v| v.as_str()).collect();
+
+        // Check if any --cwd arg still points to ws/default
+        let has_stale_cwd = cmd_strs
+            .windows(2)
+            .any(|w| w[0] == "--cwd" && w[1] == ws_default_str);
+        if !has_stale_cwd {
+            continue;
+        }
+
+        // Re-register with the correct cwd via the init helpers
+        let id = match hook.get("id").and_then(|i| i.as_str()) {
+            Some(id) => id,
+            None => continue,
+        };
+
+        // Remove old hook first
+        if Tool::new("rite")
+            .args(&["hooks", "remove", id])
+            .run()
+            .is_err()
+        {
+            continue;
+        }
+
+        let is_router = desc.ends_with(":responder");
+        if is_router {
+            let responder_ml = config
+                .agents
+                .responder
+                .as_ref()
+                .and_then(|r| r.memory_limit.as_deref());
+            super::init::register_router_hook(&root_str, &root_str, name, &agent, responder_ml);
+            println!("  Fixed hook --cwd: {desc} → repo root");
+        } else {
+            let reviewer_ml = config
+                .agents
+                .reviewer
+                .as_ref()
+                .and_then(|r| r.memory_limit.as_deref());
+            // Find which reviewer this is for
+            for reviewer in &reviewers {
+                if desc.contains(&reviewer.replace(&format!("{name}-"), "")) {
+                    super::init::register_reviewer_hook(
+                        &root_str,
+                        &root_str,
+                        name,
+                        &agent,
+                        reviewer,
+                        reviewer_ml,
+                    );
+                    println!("  Fixed hook --cwd: {desc} → repo root");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Migrate router hook claim pattern from `agent://{name}-router` to `agent://{name}-dev`
+/// and spawn name from `{name}-router` to `{name}-responder`.
+///
+/// Earlier versions used a vestigial `-router` claim that nobody actually staked.
+/// The new pattern uses `-dev` which matches the responder's own agent claim,
+/// preventing re-trigger while processing.
+fn migrate_router_hook_claim(config: &Config, project_root: &Path) {
+    let output = match Tool::new("rite")
+        .args(&["hooks", "list", "--format", "json"])
+        .run()
+    {
+        Ok(o) if o.success() => o,
+        _ => return,
+    };
+
+    let parsed: serde_json::Value = match serde_json::from_str(&output.stdout) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let hooks = match parsed.get("hooks").and_then(|h| h.as_array()) {
+        Some(h) => h,
+        None => return,
+    };
+
+    let name = &config.project.name;
+    let old_claim = format!("agent://{name}-router");
+
+    for hook in hooks {
+        let desc = hook
+            .get("description")
+            .and_then(|d| d.as_str())
+            .unwrap_or("");
+        if desc != format!("edict:{name}:responder") && desc != format!("botbox:{name}:responder") {
+            continue;
+        }
+
+        // Check if the hook still uses the old claim pattern
+        let claim = hook
+            .get("condition")
+            .and_then(|c| c.get("pattern"))
+            .and_then(|p| p.as_str())
+            .unwrap_or("");
+        if claim != old_claim {
+            continue;
+        }
+
+        let id = match hook.get("id").and_then(|i| i.as_str()) {
+            Some(id) => id,
+            None => continue,
+        };
+
+        // Remove old hook and re-register with new claim pattern
+        if Tool::new("rite")
+            .args(&["hooks", "remove", id])
+            .run()
+            .is_err()
+        {
+            continue;
+        }
+
+        let agent = config.default_agent();
+        // Resolve hook paths the same way migrate_hook_cwd does
+        let bare_root = if project_root.ends_with("ws/default") {
+            project_root
+                .parent()
+                .and_then(Path::parent)
+                .filter(|r| r.join(".manifold").exists())
+        } else if project_root.join(".manifold").exists() {
+            Some(project_root)
+        } else {
+            None
+        };
+        let root_str = bare_root
+            .map(|r| r.display().to_string())
+            .unwrap_or_else(|| project_root.display().to_string());
+        let responder_ml = config
+            .agents
+            .responder
+            .as_ref()
+            .and_then(|r| r.memory_limit.as_deref());
+        super::init::register_router_hook(&root_str, &root_str, name, &agent, responder_ml);
+        println!("  Migrated router hook claim: agent://{name}-router → agent://{name}-dev");
+    }
+}
+
+/// Migrate botty → vessel: update config key on disk and re-register rite hooks.
+///
+/// Idempotent — skips steps already done.
+fn migrate_vessel_hooks(config: &Config, project_root: &Path, config_path: &Path) {
+    // 1. Update config TOML on disk: botty = true → vessel = true
+    if let Ok(content) = fs::read_to_string(config_path) {
+        if content.contains("botty = ") {
+            let updated = content.replace("botty = ", "vessel = ");
+            if let Err(e) = fs::write(config_path, updated) {
+                tracing::warn!("failed to update config botty→vessel: {e}");
+            } else {
+                println!("Migrated config: tools.botty → tools.vessel");
+            }
+        }
+    }
+
+    // 2. Re-register edict hooks that still call `botty spawn` with `vessel spawn`.
+    //    ensure_rite_hook deduplicates by description, so calling register_*_hook
+    //    will remove the old hook and re-add it with the updated command.
+    let output = match Tool::new("rite")
+        .args(&["hooks", "list", "--format", "json"])
+        .run()
+    {
+        Ok(o) if o.success() => o,
+        _ => return,
+    };
+
+    let parsed: serde_json::Value = match serde_json::from_str(&output.stdout) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let hooks = match parsed.get("hooks").and_then(|h| h.as_array()) {
+        Some(h) => h.to_vec(),
+        None => return,
+    };
+
+    let name = &config.project.name;
+
+    // Resolve root path (same logic as other hook migrations)
+    let bare_root = if project_root.ends_with("ws/default") {
+        project_root
+            .parent()
+            .and_then(Path::parent)
+            .filter(|r| r.join(".manifold").exists())
+    } else if project_root.join(".manifold").exists() {
+        Some(project_root)
+    } else {
+        None
+    };
+    let root_str = bare_root
+        .map(|r| r.display().to_string())
+        .unwrap_or_else(|| project_root.display().to_string());
+    let agent = config.default_agent();
+
+    for hook in &hooks {
+        // Only migrate hooks whose command array contains "botty"
+        let uses_botty = hook
+            .get("command")
+            .and_then(|c| c.as_array())
+            .map(|arr| arr.iter().any(|v| v.as_str() == Some("botty")))
+            .unwrap_or(false);
+        if !uses_botty {
+            continue;
+        }
+
+        let desc = hook
+            .get("description")
+            .and_then(|d| d.as_str())
+            .unwrap_or("");
+
+        if desc == format!("edict:{name}:responder") {
+            let ml = config
+                .agents
+                .responder
+                .as_ref()
+                .and_then(|r| r.memory_limit.as_deref());
+            super::init::register_router_hook(&root_str, &root_str, name, &agent, ml);
+            println!("  Migrated router hook: vessel spawn (was botty)");
+        } else if let Some(role) = desc
+            .strip_prefix(&format!("edict:{name}:reviewer-"))
+            .filter(|r| !r.is_empty())
+        {
+            let reviewer_agent = format!("{name}-{role}");
+            let ml = config
+                .agents
+                .reviewer
+                .as_ref()
+                .and_then(|r| r.memory_limit.as_deref());
+            super::init::register_reviewer_hook(
+                &root_str,
+                &root_str,
+                name,
+                &agent,
+                &reviewer_agent,
+                ml,
+            );
+            println!("  Migrated reviewer hook {role}: vessel spawn (was botty)");
+        }
+    }
+}
+
+/// Migrate beads → bones: config key, data directory, .maw.toml, .sealignore, .gitignore.
+///
+/// This is idempotent — checks each step before acting.
+fn migrate_beads_to_bones(project_root: &Path, config_path: &Path) -> Result<()> {
+    let beads_dir = project_root.join(".beads");
+    let bones_dir = project_root.join(".bones");
+
+    // 1. If config has `tools.beads` (in TOML), rename to `tools.bones`
+    //    The serde alias handles deserialization, but we want the file itself updated.
+    if config_path.exists() {
+        let content = fs::read_to_string(config_path)?;
+        if content.contains("beads") && !content.contains("bones") {
+            let updated = content.replace("beads = ", "bones = ");
+            fs::write(config_path, updated)?;
+            println!("Migrated config: tools.beads → tools.bones");
+        }
+    }
+
+    // 2. If .beads/ exists and .bones/ doesn't → run `bn init` + migrate data
+    if beads_dir.exists() && !bones_dir.exists() {
+        let beads_db = beads_dir.join("beads.db");
+        // Initialize bones first
+        match run_command("bn", &["init"], Some(project_root)) {
+            Ok(_) => println!("Initialized bones"),
+            Err(e) => tracing::warn!("bn init failed: {e}"),
+        }
+        // Migrate data if beads.db exists
+        if beads_db.exists() {
+            let db_path = beads_db.to_string_lossy().to_string();
+            match run_command(
+                "bn",
+                &["data", "migrate-from-beads", "--beads-db", &db_path],
+                Some(project_root),
+            ) {
+                Ok(_) => println!("Migrated beads data to bones"),
+                Err(e) => tracing::warn!("beads data migration failed: {e}"),
+            }
+        }
+    }
+
+    // 3. Update .maw.toml: remove .beads/** entry (set auto_resolve_from_main to empty)
+    let maw_toml = project_root.join(".maw.toml");
+    if maw_toml.exists() {
+        let content = fs::read_to_string(&maw_toml)?;
+        if content.contains(".beads/") {
+            // Remove the .beads/** line and set to empty array if it was the only entry
+            let updated = content
+                .lines()
+                .map(|line| {
+                    if line.contains(".beads/") {
+                        // Skip this line
+                        None
+                    } else {
+                        Some(line)
+                    }
+                })
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("\n");
+            // If the array is now effectively empty, replace with empty
+            let updated = updated.replace(
+                "auto_resolve_from_main = [\n]",
+                "auto_resolve_from_main = []",
+            );
+            fs::write(&maw_toml, format!("{updated}\n"))?;
+            println!("Updated .maw.toml: removed .beads/** entry");
+        }
+    }
+
+    // 4. Update .sealignore: remove .beads/ line (bones handles its own sealignore)
+    let sealignore = project_root.join(".sealignore");
+    if sealignore.exists() {
+        let content = fs::read_to_string(&sealignore)?;
+        if content.contains(".beads/") {
+            let updated: String = content
+                .lines()
+                .filter(|line| line.trim() != ".beads/")
+                .collect::<Vec<_>>()
+                .join("\n");
+            let updated = if content.ends_with('\n') {
+                format!("{updated}\n")
+            } else {
+                updated
+            };
+            fs::write(&sealignore, updated)?;
+            println!("Updated .sealignore: removed .beads/ entry");
+        }
+    }
+
+    // 5. Update .gitignore: remove .bv/ line (bones is tracked, not ignored)
+    let gitignore = project_root.join(".gitignore");
+    if gitignore.exists() {
+        let content = fs::read_to_string(&gitignore)?;
+        if content.contains(".bv/") {
+            let updated: String = content
+                .lines()
+                .filter(|line| line.trim() != ".bv/")
+                .collect::<Vec<_>>()
+                .join("\n");
+            // Preserve trailing newline if original had one
+            let updated = if content.ends_with('\n') {
+                format!("{updated}\n")
+            } else {
+                updated
+            };
+            fs::write(&gitignore, updated)?;
+            println!("Updated .gitignore: removed .bv/ entry");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "# project config\nversion = \"1.0.16\"\n\n[project]\nname = \"demo\"\n";
+
+    fn ok(_: &MigrationContext) -> Result<()> {
+        Ok(())
+    }
+
+    fn fail(_: &MigrationContext) -> Result<()> {
+        anyhow::bail!("boom")
+    }
+
+    static TEST_MIGRATIONS: &[Migration] = &[
+        Migration {
+            id: "2.0.0",
+            title: "first",
+            up: ok,
+        },
+        Migration {
+            id: "2.0.1",
+            title: "second",
+            up: fail,
+        },
+        Migration {
+            id: "2.0.2",
+            title: "third",
+            up: ok,
+        },
+    ];
+
+    #[test]
+    fn registry_is_ordered_and_ends_at_latest() {
+        let versions: Vec<_> = MIGRATIONS.iter().map(|m| parse_version(m.id)).collect();
+        assert!(versions.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(MIGRATIONS.last().unwrap().id, LATEST);
+    }
+
+    #[test]
+    fn pending_compares_versions_numerically() {
+        assert_eq!(parse_version("1.0.9"), (1, 0, 9));
+        assert_eq!(parse_version("garbage"), (0, 0, 0));
+        assert_eq!(pending(MIGRATIONS, "1.0.16").len(), MIGRATIONS.len());
+        assert_eq!(pending(MIGRATIONS, "").len(), MIGRATIONS.len());
+        assert_eq!(pending(MIGRATIONS, "1.0.20")[0].id, "1.0.21");
+        assert!(pending(MIGRATIONS, LATEST).is_empty());
+        assert!(pending(MIGRATIONS, "1.0.100").is_empty());
+    }
+
+    #[test]
+    fn record_version_keeps_the_rest_of_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".edict.toml");
+        fs::write(&path, CONFIG).unwrap();
+        record_version(&path, "1.0.22").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            CONFIG.replace("1.0.16", "1.0.22")
+        );
+
+        let json = dir.path().join(".botbox.json");
+        fs::write(
+            &json,
+            r#"{"version": "1.0.3", "project": {"name": "demo"}}"#,
+        )
+        .unwrap();
+        record_version(&json, "1.0.22").unwrap();
+        let value: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&json).unwrap()).unwrap();
+        assert_eq!(value["version"], "1.0.22");
+        assert_eq!(value["project"]["name"], "demo");
+    }
+
+    #[test]
+    fn failed_migration_stops_without_advancing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".edict.toml");
+        fs::write(&path, CONFIG).unwrap();
+        let config = Config::load(&path).unwrap();
+        let ctx = MigrationContext {
+            project_root: dir.path(),
+            config_path: &path,
+            config: &config,
+        };
+
+        let err = run_pending(TEST_MIGRATIONS, &ctx).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("migration 2.0.1 (second) failed: boom")
+        );
+        assert_eq!(Config::load(&path).unwrap().version, "2.0.0");
+
+        // Resumes after the last recorded migration; nothing left to do afterwards
+        let config = Config::load(&path).unwrap();
+        let ctx = MigrationContext {
+            config: &config,
+            ..ctx
+        };
+        assert_eq!(
+            run_pending(&TEST_MIGRATIONS[2..], &ctx).unwrap(),
+            vec!["2.0.2"]
+        );
+        let config = Config::load(&path).unwrap();
+        let ctx = MigrationContext {
+            config: &config,
+            ..ctx
+        };
+        assert!(run_pending(&TEST_MIGRATIONS[2..], &ctx).unwrap().is_empty());
+    }
+}
@@ -4,12 +4,20 @@ pub mod doctor;
 pub mod hooks;
 pub mod init;
 pub mod iteration_start;
+pub mod journal;
+pub mod kb;
+pub mod migrations;
+pub mod overrides;
 pub mod protocol;
 pub mod responder;
+pub mod review_checks;
 pub mod run;
 pub mod run_agent;
 pub mod run_reviewer_loop;
+pub mod secret_scan;
+pub mod state_file;
 pub mod status;
 pub mod sync;
 pub mod triage;
 pub mod worker_loop;
+pub mod workspace_diff;
@@ -0,0 +1,423 @@
+//! Project-local overrides layered on the embedded workflow docs.
+//!
+//! `.agents/edict-local/` mirrors `.agents/edict/` (`prompts/` and `design/` included).
+//! For an embedded doc such as `start.md`, sync applies, in order:
+//!
+//! - `start.md` — replaces the embedded doc
+//! - `start.patch` — unified diff applied to it
+//! - `start.append.md` — appended to it
+//!
+//! Docs that are templates are rendered for the project config first, so patches apply
+//! to the rendered doc (as written to `.agents/edict/`), not to the template.
+//!
+//! Version markers record the upstream (embedded) hash, a hash of the rendered output
+//! for rendered docs and, when overrides exist, a separate hash of the override files,
+//! so staleness can tell which side changed.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+
+use crate::template::{TemplateContext, render_docs};
+
+/// Overrides directory, relative to the project root.
+pub const OVERRIDES_DIR: &str = ".agents/edict-local";
+
+const PATCH_SUFFIX: &str = ".patch";
+const APPEND_SUFFIX: &str = ".append.md";
+
+/// Embedded docs with local overrides applied.
+#[derive(Debug)]
+pub struct DocSet {
+    /// File name and merged content, in embedded order.
+    pub files: Vec<(String, String)>,
+    pub version: Version,
+}
+
+impl DocSet {
+    /// Merge overrides from `.agents/edict-local/<subdir>` over `embedded` (name, content)
+    /// pairs, either the compiled-in docs or their rendered output.
+    pub fn layer(
+        project_root: &Path,
+        subdir: &str,
+        embedded: &[(impl AsRef<str>, impl AsRef<str>)],
+    ) -> Result<Self> {
+        let dir = project_root.join(OVERRIDES_DIR).join(subdir);
+        Self::layer_dir(&dir, embedded)
+    }
+
+    /// `layer` over `templates` rendered for `ctx`. The marker hashes the templates and
+    /// the rendered docs separately, so a config change isn't reported as an edict
+    /// upgrade, and config no template reads doesn't mark the docs stale.
+    pub fn layer_rendered(
+        project_root: &Path,
+        subdir: &str,
+        templates: &[(&str, &str)],
+        ctx: &TemplateContext,
+    ) -> Result<Self> {
+        let rendered = render_docs(templates, ctx)?;
+        let mut docs = Self::layer(project_root, subdir, &rendered)?;
+        docs.version.upstream = version_hash(templates.iter().copied());
+        docs.version.config = Some(version_hash(
+            rendered.iter().map(|(n, c)| (n.as_str(), c.as_str())),
+        ));
+        Ok(docs)
+    }
+
+    fn layer_dir(dir: &Path, embedded: &[(impl AsRef<str>, impl AsRef<str>)]) -> Result<Self> {
+        let embedded: Vec<(&str, &str)> = embedded
+            .iter()
+            .map(|(name, content)| (name.as_ref(), content.as_ref()))
+            .collect();
+        let overrides = read_overrides(dir, &embedded)?;
+
+        let mut files = Vec::with_capacity(embedded.len());
+        for &(name, content) in &embedded {
+            let mut content = content.to_string();
+            let file = |suffix: &str| {
+                let stem = name.strip_suffix(".md").unwrap_or(name);
+                let file_name = if suffix.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{stem}{suffix}")
+                };
+                overrides.iter().find(|(n, _)| *n == file_name)
+            };
+
+            if let Some((_, replacement)) = file("") {
+                content.clone_from(replacement);
+            }
+            if let Some((patch_name, patch)) = file(PATCH_SUFFIX) {
+                let path = dir.join(patch_name);
+                let patch = diffy::Patch::from_str(patch)
+                    .with_context(|| format!("Failed to parse {}", path.display()))?;
+                content = diffy::apply(&content, &patch).with_context(|| {
+                    format!(
+                        "Failed to apply {} to {name} (upstream or project config changed? \
+                         regenerate the patch against the rendered doc)",
+                        path.display()
+                    )
+                })?;
+            }
+            if let Some((_, append)) = file(APPEND_SUFFIX) {
+                if !content.ends_with('\n') {
+                    content.push('\n');
+                }
+                content.push_str(append);
+            }
+            files.push((name.to_string(), content));
+        }
+
+        let local = (!overrides.is_empty())
+            .then(|| version_hash(overrides.iter().map(|(n, c)| (n.as_str(), c.as_str()))));
+        Ok(Self {
+            files,
+            version: Version {
+                upstream: version_hash(embedded.iter().copied()),
+                config: None,
+                local,
+            },
+        })
+    }
+
+    /// Compare against the version marker at `path`.
+    pub fn staleness(&self, path: &Path) -> Result<Staleness> {
+        if !path.exists() {
+            return Ok(Staleness {
+                upstream: true,
+                config: self.version.config.is_some(),
+                local: self.version.local.is_some(),
+            });
+        }
+        let installed = Version::parse(&fs::read_to_string(path)?);
+        let upstream = installed.upstream != self.version.upstream;
+        Ok(Staleness {
+            upstream,
+            // New templates render differently too; only blame the config when they match
+            config: !upstream && installed.config != self.version.config,
+            local: installed.local != self.version.local,
+        })
+    }
+
+    /// Write the merged files into `dir` and the version marker to `dir/<marker>`.
+    pub fn write(&self, dir: &Path, marker: &str) -> Result<()> {
+        fs::create_dir_all(dir)?;
+        for (name, content) in &self.files {
+            let path = dir.join(name);
+            fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        fs::write(dir.join(marker), self.version.render())?;
+        Ok(())
+    }
+}
+
+/// Contents of a version marker: `<upstream hash>`, then `config <hash>` for rendered
+/// docs and `local <hash>` when overrides exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub upstream: String,
+    pub config: Option<String>,
+    pub local: Option<String>,
+}
+
+impl Version {
+    fn parse(s: &str) -> Self {
+        let mut lines = s.lines().map(str::trim);
+        let upstream = lines.next().unwrap_or_default().to_string();
+        let rest: Vec<&str> = lines.collect();
+        let field = |prefix: &str| {
+            rest.iter()
+                .find_map(|l| l.strip_prefix(prefix))
+                .map(str::to_string)
+        };
+        Self {
+            upstream,
+            config: field("config "),
+            local: field("local "),
+        }
+    }
+
+    /// Markers with neither stay a bare hash, as written before overrides existed.
+    fn render(&self) -> String {
+        if self.config.is_none() && self.local.is_none() {
+            return self.upstream.clone();
+        }
+        let mut marker = format!("{}\n", self.upstream);
+        if let Some(config) = &self.config {
+            marker.push_str(&format!("config {config}\n"));
+        }
+        if let Some(local) = &self.local {
+            marker.push_str(&format!("local {local}\n"));
+        }
+        marker
+    }
+}
+
+/// Which side of a doc set changed since the last sync.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Staleness {
+    /// Embedded docs changed (edict upgrade)
+    pub upstream: bool,
+    /// Rendered docs changed under the same templates (project config: channel, tools, ...)
+    pub config: bool,
+    /// Override files changed
+    pub local: bool,
+}
+
+impl Staleness {
+    pub fn is_stale(self) -> bool {
+        self.upstream || self.config || self.local
+    }
+
+    /// `label`, qualified by what changed, e.g. "workflow docs (local overrides)". An
+    /// upstream change alone goes unqualified.
+    pub fn describe(self, label: &str) -> String {
+        let mut changed = Vec::new();
+        if self.upstream && (self.config || self.local) {
+            changed.push("upstream");
+        }
+        if self.config {
+            changed.push("project config");
+        }
+        if self.local {
+            changed.push("local overrides");
+        }
+        if changed.is_empty() {
+            label.to_string()
+        } else {
+            format!("{label} ({})", changed.join(", "))
+        }
+    }
+}
+
+/// First 32 hex chars of SHA-256 over each file's name and content.
+pub fn version_hash<'a>(files: impl IntoIterator<Item = (&'a str, &'a str)>) -> String {
+    let mut hasher = Sha256::new();
+    for (name, content) in files {
+        hasher.update(name.as_bytes());
+        hasher.update(content.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())[..32].to_string()
+}
+
+/// Override files in `dir` that target an embedded doc, sorted by name.
+fn read_overrides(dir: &Path, embedded: &[(&str, &str)]) -> Result<Vec<(String, String)>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let mut overrides = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if file_name.starts_with('.') {
+            continue;
+        }
+        if override_target(file_name, embedded).is_none() {
+            tracing::warn!(path = %path.display(), "override does not match an embedded doc, ignoring");
+            continue;
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        overrides.push((file_name.to_string(), content));
+    }
+    overrides.sort();
+    Ok(overrides)
+}
+
+/// Embedded doc an override file applies to.
+fn override_target<'a>(file_name: &str, embedded: &[(&'a str, &str)]) -> Option<&'a str> {
+    embedded.iter().map(|(name, _)| *name).find(|name| {
+        let stem = name.strip_suffix(".md").unwrap_or(name);
+        file_name == *name
+            || file_name == format!("{stem}{PATCH_SUFFIX}")
+            || file_name == format!("{stem}{APPEND_SUFFIX}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMBEDDED: &[(&str, &str)] = &[
+        (
+            "start.md",
+            "# Start\n\nClaim the bone.\nCreate a workspace.\n",
+        ),
+        ("finish.md", "# Finish\n\nMerge.\n"),
+        ("triage.md", "# Triage\n"),
+    ];
+
+    #[test]
+    fn no_overrides_matches_embedded() {
+        let dir = tempfile::tempdir().unwrap();
+        let docs = DocSet::layer(dir.path(), "", EMBEDDED).unwrap();
+        assert_eq!(docs.files[0].1, EMBEDDED[0].1);
+        assert_eq!(docs.version.local, None);
+        assert_eq!(
+            docs.version.render(),
+            version_hash(EMBEDDED.iter().copied())
+        );
+    }
+
+    #[test]
+    fn replace_patch_and_append() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join(OVERRIDES_DIR);
+        fs::create_dir_all(&local).unwrap();
+
+        fs::write(local.join("triage.md"), "# Our triage\n").unwrap();
+        let patched = EMBEDDED[0]
+            .1
+            .replace("Create a workspace.", "Use the default workspace.");
+        let patch = diffy::create_patch(EMBEDDED[0].1, &patched).to_string();
+        fs::write(local.join("start.patch"), patch).unwrap();
+        fs::write(local.join("start.append.md"), "\nAsk in #ops first.\n").unwrap();
+        fs::write(local.join("unknown.md"), "ignored").unwrap();
+
+        let docs = DocSet::layer(dir.path(), "", EMBEDDED).unwrap();
+        assert_eq!(docs.files[2].1, "# Our triage\n");
+        assert_eq!(
+            docs.files[0].1,
+            "# Start\n\nClaim the bone.\nUse the default workspace.\n\nAsk in #ops first.\n"
+        );
+        assert_eq!(docs.files[1].1, EMBEDDED[1].1);
+        assert!(docs.version.local.is_some());
+
+        let upstream = DocSet::layer(dir.path(), "design", EMBEDDED).unwrap();
+        assert_eq!(docs.version.upstream, upstream.version.upstream);
+    }
+
+    #[test]
+    fn patch_against_changed_upstream_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let patch = diffy::create_patch("a\nb\nc\n", "a\nB\nc\n").to_string();
+        fs::write(dir.path().join("finish.patch"), patch).unwrap();
+        let err = DocSet::layer_dir(dir.path(), EMBEDDED).unwrap_err();
+        assert!(format!("{err:#}").contains("regenerate the patch"));
+    }
+
+    #[test]
+    fn staleness_distinguishes_upstream_and_local() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        let marker = out.join(".version");
+        let docs = DocSet::layer_dir(&dir.path().join("local"), EMBEDDED).unwrap();
+        assert_eq!(
+            docs.staleness(&marker).unwrap(),
+            Staleness {
+                upstream: true,
+                config: false,
+                local: false
+            }
+        );
+        docs.write(&out, ".version").unwrap();
+        assert!(!docs.staleness(&marker).unwrap().is_stale());
+
+        fs::create_dir_all(dir.path().join("local")).unwrap();
+        fs::write(
+            dir.path().join("local/finish.append.md"),
+            "Then clean up.\n",
+        )
+        .unwrap();
+        let local = DocSet::layer_dir(&dir.path().join("local"), EMBEDDED).unwrap();
+        let stale = local.staleness(&marker).unwrap();
+        assert_eq!(
+            stale.describe("workflow docs"),
+            "workflow docs (local overrides)"
+        );
+        local.write(&out, ".version").unwrap();
+        assert!(!local.staleness(&marker).unwrap().is_stale());
+
+        let upgraded = [("start.md", "# Start v2\n"), EMBEDDED[1]];
+        let upgraded = DocSet::layer_dir(&dir.path().join("local"), &upgraded).unwrap();
+        let stale = upgraded.staleness(&marker).unwrap();
+        assert!(stale.upstream);
+        assert_eq!(stale.describe("workflow docs"), "workflow docs");
+    }
+
+    #[test]
+    fn config_change_is_not_an_upstream_change() {
+        use crate::config::Config;
+
+        const TEMPLATES: &[(&str, &str)] = &[("start.md", "Announce in #{{ channel }}.\n")];
+        let config = |channel: &str| {
+            Config::parse_toml(&format!(
+                "version = \"1.0.0\"\n[project]\nname = \"acme\"\nchannel = \"{channel}\"\n"
+            ))
+            .unwrap()
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        let marker = out.join(".version");
+
+        let ctx = TemplateContext::from_config(&config("acme"));
+        let docs = DocSet::layer_rendered(dir.path(), "", TEMPLATES, &ctx).unwrap();
+        assert_eq!(docs.files[0].1, "Announce in #acme.\n");
+        docs.write(&out, ".version").unwrap();
+        assert!(!docs.staleness(&marker).unwrap().is_stale());
+
+        // Config no template reads leaves the docs alone
+        let mut renamed = config("acme");
+        renamed.project.name = "other".to_string();
+        let ctx = TemplateContext::from_config(&renamed);
+        let same = DocSet::layer_rendered(dir.path(), "", TEMPLATES, &ctx).unwrap();
+        assert!(!same.staleness(&marker).unwrap().is_stale());
+
+        let ctx = TemplateContext::from_config(&config("ops"));
+        let moved = DocSet::layer_rendered(dir.path(), "", TEMPLATES, &ctx).unwrap();
+        let stale = moved.staleness(&marker).unwrap();
+        assert!(stale.config && !stale.upstream && !stale.local);
+        assert_eq!(
+            stale.describe("workflow docs"),
+            "workflow docs (project config)"
+        );
+    }
+}
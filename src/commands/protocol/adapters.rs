@@ -120,6 +120,26 @@ pub fn parse_bone_show(json: &str) -> Result<BoneInfo, AdapterError> {
     })
 }
 
+// --- Bones (bn list) ---
+
+/// Row of `bn list --json`, which prints a top-level array of bones.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BoneListRow {
+    pub id: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub close_reason: Option<String>,
+}
+
+/// Parse `bn list --json` output.
+pub fn parse_bone_list(json: &str) -> Result<Vec<BoneListRow>, AdapterError> {
+    serde_json::from_str(json).map_err(|e| AdapterError::ParseFailed {
+        tool: "bn list",
+        detail: e.to_string(),
+    })
+}
+
 // --- Seal Reviews ---
 
 /// Parsed output from `seal reviews list --format json`.
@@ -163,6 +183,9 @@ pub struct ReviewDetail {
     pub votes: Vec<ReviewVote>,
     #[serde(default)]
     pub open_thread_count: usize,
+    /// Comment threads (filled from the response's top-level `threads` by `review_status`)
+    #[serde(default)]
+    pub threads: Vec<ReviewThread>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -171,6 +194,10 @@ pub struct ReviewVote {
     pub vote: String,
     #[serde(default)]
     pub voted_at: Option<String>,
+    /// Commit the vote was cast against. Seal doesn't report this yet; until it
+    /// does, votes are tied to the workspace through vote snapshots.
+    #[serde(default, alias = "commit")]
+    pub commit_id: Option<String>,
 }
 
 impl ReviewVote {
@@ -183,7 +210,7 @@ impl ReviewVote {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct ReviewThread {
     pub thread_id: String,
     #[serde(default)]
@@ -196,7 +223,7 @@ pub struct ReviewThread {
     pub comments: Vec<ReviewComment>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct ReviewComment {
     #[serde(default)]
     pub author: String,
@@ -263,10 +290,32 @@ pub fn parse_review_detail(json: &str) -> Result<ReviewDetailResponse, AdapterEr
     })
 }
 
+/// Parse `git diff --name-only` / `git ls-files` output into a sorted, deduplicated path list.
+pub fn parse_name_only(output: &str) -> Vec<String> {
+    let mut paths: Vec<String> = output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // --- Bone list parsing ---
+
+    #[test]
+    fn parse_bone_list_fixture() {
+        let rows = parse_bone_list(include_str!("../testdata/bn-list-done.json")).unwrap();
+        assert!(!rows.is_empty());
+        assert!(rows.iter().all(|r| r.id.starts_with("bn-")));
+    }
+
     // --- Claims parsing ---
 
     #[test]
@@ -394,6 +443,15 @@ mod tests {
         assert_eq!(resp.reviews[0].review_id, "cr-abc");
     }
 
+    #[test]
+    fn parse_name_only_dedups_and_sorts() {
+        let out = "src/b.rs\n\nsrc/a.rs\n  docs/x.md \nsrc/b.rs\n";
+        assert_eq!(
+            parse_name_only(out),
+            vec!["docs/x.md", "src/a.rs", "src/b.rs"]
+        );
+    }
+
     #[test]
     fn parse_reviews_list_empty() {
         let json = r#"{"reviews": []}"#;
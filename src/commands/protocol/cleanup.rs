@@ -9,6 +9,7 @@
 use super::context::ProtocolContext;
 use super::executor;
 use super::exit_policy;
+use super::last_guidance::Recorder;
 use super::render::{ProtocolGuidance, ProtocolStatus};
 use super::shell;
 use crate::commands::doctor::OutputFormat;
@@ -25,6 +26,7 @@ pub fn execute(
     agent: &str,
     project: &str,
     format: OutputFormat,
+    recorder: &Recorder,
 ) -> anyhow::Result<()> {
     // Collect state from rite and maw
     let ctx = ProtocolContext::collect(project, agent)?;
@@ -44,7 +46,7 @@ pub fn execute(
         guidance.status = ProtocolStatus::Ready;
         guidance.advise("No cleanup needed.".to_string());
         // If execute is true but we're already clean, just report status (no execution needed)
-        return render_cleanup(&guidance, format, execute);
+        return render_cleanup(&guidance, format, execute, recorder);
     }
 
     // We have resources held
@@ -91,7 +93,7 @@ pub fn execute(
     );
     guidance.advise(summary);
 
-    render_cleanup(&guidance, format, execute)
+    render_cleanup(&guidance, format, execute, recorder)
 }
 
 /// Render cleanup guidance in the requested format.
@@ -108,6 +110,7 @@ fn render_cleanup(
     guidance: &ProtocolGuidance,
     format: OutputFormat,
     execute: bool,
+    recorder: &Recorder,
 ) -> anyhow::Result<()> {
     // If execute flag is set and we have resources to clean up, run the executor
     if execute && matches!(guidance.status, ProtocolStatus::HasResources) {
@@ -186,7 +189,9 @@ fn render_cleanup(
             // JSON format: use standard render path for consistency
             exit_policy::render_guidance(guidance, format)
         }
-    }
+    }?;
+    recorder.record(guidance);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -185,7 +185,65 @@ impl ProtocolContext {
         ])?;
         let review_resp: ReviewDetailResponse = serde_json::from_str(&output)
             .map_err(|e| ContextError::ParseFailed(format!("review {review_id}: {e}")))?;
-        Ok(review_resp.review)
+        let mut review = review_resp.review;
+        if review.threads.is_empty() {
+            review.threads = review_resp.threads;
+        }
+        Ok(review)
+    }
+
+    /// List files a workspace changes relative to trunk (`project.trunk`, or detected).
+    ///
+    /// Calls `git merge-base <trunk> HEAD`, then `git diff --name-only <base>` (committed and
+    /// uncommitted changes) and `git ls-files --others --exclude-standard` (new files),
+    /// all via `maw exec <ws> --`.
+    pub fn touched_paths(
+        &self,
+        workspace: &str,
+        trunk: Option<&str>,
+    ) -> Result<Vec<String>, ContextError> {
+        Self::validate_workspace_name(workspace)?;
+        let trunk = crate::commands::workspace_diff::trunk_branch(workspace, trunk)
+            .map_err(|e| ContextError::SubprocessFailed(format!("{e:#}")))?;
+        let base = Self::run_subprocess(&[
+            "maw",
+            "exec",
+            workspace,
+            "--",
+            "git",
+            "merge-base",
+            &trunk,
+            "HEAD",
+        ])?;
+        let base = base.trim();
+        if base.is_empty() || !base.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ContextError::ParseFailed(format!(
+                "merge-base in {workspace}: {base:?}"
+            )));
+        }
+        let changed = Self::run_subprocess(&[
+            "maw",
+            "exec",
+            workspace,
+            "--",
+            "git",
+            "diff",
+            "--name-only",
+            base,
+        ])?;
+        let untracked = Self::run_subprocess(&[
+            "maw",
+            "exec",
+            workspace,
+            "--",
+            "git",
+            "ls-files",
+            "--others",
+            "--exclude-standard",
+        ])?;
+        Ok(adapters::parse_name_only(&format!(
+            "{changed}\n{untracked}"
+        )))
     }
 
     /// Check for claim conflicts by querying all claims.
@@ -0,0 +1,532 @@
+//! Critical-change approval gate.
+//!
+//! A change is critical when its bone carries a label from `[review.critical] labels`
+//! (default `risk:critical`) or it touches a path in `[review.critical] paths`. Critical
+//! changes need a current LGTM from one of `project.critical_approvers` — a human, not
+//! a reviewer agent. The gate is only active when approvers are configured. An unknown
+//! diff or bone counts as critical, so the gate fails closed.
+//!
+//! Unlike the agent review gate, `--force` does not bypass this. Only
+//! `--override-critical <reason>` does, and every override is appended to
+//! `critical-overrides.jsonl` in the project cache dir and announced on the bone and
+//! the project channel.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::adapters::ReviewDetail;
+use super::render::ProtocolGuidance;
+use super::review_gate;
+use super::reviewer_rules;
+use super::shell;
+use super::vote_snapshots::{self, MawWorkspace, SnapshotStore, StaleApproval, WorkspaceProbe};
+use crate::commands::journal;
+use crate::config::Config;
+
+/// Override log file name in the project cache dir.
+pub const OVERRIDES_FILE: &str = "critical-overrides.jsonl";
+
+/// Why a change was classified as critical.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CriticalChange {
+    /// Bone labels that matched `[review.critical] labels`.
+    pub labels: Vec<String>,
+    /// Touched files that matched `[review.critical] paths`.
+    pub paths: Vec<String>,
+    /// Critical paths are configured but the workspace diff was unavailable.
+    pub diff_unknown: bool,
+    /// Critical labels are configured but the change's bone (or its labels) is unknown.
+    pub labels_unknown: bool,
+}
+
+impl CriticalChange {
+    /// Human-readable reason, e.g. "label risk:critical; paths migrations/001.sql".
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.labels.is_empty() {
+            parts.push(format!("label {}", self.labels.join(", ")));
+        }
+        if !self.paths.is_empty() {
+            parts.push(format!("paths {}", self.paths.join(", ")));
+        }
+        if self.diff_unknown {
+            parts.push("workspace diff unavailable, critical paths assumed touched".to_string());
+        }
+        if self.labels_unknown {
+            parts.push("bone labels unavailable, critical label assumed".to_string());
+        }
+        parts.join("; ")
+    }
+}
+
+/// Configured critical approvers (leading `@` stripped).
+pub fn approvers(config: &Config) -> Vec<String> {
+    config
+        .project
+        .critical_approvers
+        .iter()
+        .flatten()
+        .map(|a| a.trim().trim_start_matches('@').to_string())
+        .filter(|a| !a.is_empty())
+        .collect()
+}
+
+/// Classify a change. Returns `None` when it isn't critical or no approvers are configured.
+///
+/// `touched` is `None` when the diff is unavailable; with critical paths configured
+/// that counts as critical, matching how reviewer assignment treats an unknown diff.
+/// Likewise `bone_labels` is `None` when the change's bone can't be determined, which
+/// counts as critical when critical labels are configured.
+pub fn assess(
+    config: &Config,
+    bone_labels: Option<&[String]>,
+    touched: Option<&[String]>,
+) -> Option<CriticalChange> {
+    if approvers(config).is_empty() {
+        return None;
+    }
+    let critical = &config.review.critical;
+    let (labels, labels_unknown) = match bone_labels {
+        _ if critical.labels.is_empty() => (Vec::new(), false),
+        Some(bone_labels) => (
+            bone_labels
+                .iter()
+                .filter(|l| critical.labels.contains(l))
+                .cloned()
+                .collect(),
+            false,
+        ),
+        None => (Vec::new(), true),
+    };
+    let (paths, diff_unknown) = match touched {
+        _ if critical.paths.is_empty() => (Vec::new(), false),
+        Some(touched) => (
+            reviewer_rules::matching_paths(&critical.paths, touched),
+            false,
+        ),
+        None => (Vec::new(), true),
+    };
+    if labels.is_empty() && paths.is_empty() && !diff_unknown && !labels_unknown {
+        return None;
+    }
+    Some(CriticalChange {
+        labels,
+        paths,
+        diff_unknown,
+        labels_unknown,
+    })
+}
+
+/// Outcome of checking a review for a critical approver's LGTM.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CriticalApproval {
+    /// A listed approver's latest vote is a current LGTM.
+    Approved(String),
+    /// Approvers said LGTM, but the workspace changed afterwards.
+    Stale(Vec<StaleApproval>),
+    /// No listed approver has approved.
+    Missing,
+}
+
+/// Check whether any of `approvers` has a current LGTM on `review`.
+///
+/// Approvers don't run the reviewer loop, so their first LGTM is usually judged by
+/// the snapshot taken when a gate first sees it.
+pub fn check_approval(
+    store: &SnapshotStore,
+    workspace: &dyn WorkspaceProbe,
+    review: Option<&ReviewDetail>,
+    approvers: &[String],
+) -> CriticalApproval {
+    let Some(review) = review else {
+        return CriticalApproval::Missing;
+    };
+    let latest = review_gate::latest_votes(review);
+    let mut stale = Vec::new();
+    for approver in approvers {
+        let Some(vote) = latest.get(approver).filter(|v| v.is_lgtm()) else {
+            continue;
+        };
+        match vote_snapshots::stale_vote(store, workspace, &review.review_id, vote) {
+            None => return CriticalApproval::Approved(approver.clone()),
+            Some(s) => stale.push(s),
+        }
+    }
+    if stale.is_empty() {
+        CriticalApproval::Missing
+    } else {
+        CriticalApproval::Stale(stale)
+    }
+}
+
+/// An audit record of a critical gate override.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CriticalOverride {
+    pub command: String,
+    #[serde(default)]
+    pub bone_id: Option<String>,
+    pub workspace: String,
+    pub agent: String,
+    pub reason: String,
+    /// Why the change was critical (see [`CriticalChange::describe`]).
+    pub critical: String,
+    /// RFC 3339 timestamp
+    pub recorded_at: String,
+}
+
+impl CriticalOverride {
+    pub fn new(
+        command: &str,
+        bone_id: Option<&str>,
+        workspace: &str,
+        agent: &str,
+        reason: &str,
+        change: &CriticalChange,
+    ) -> Self {
+        Self {
+            command: command.to_string(),
+            bone_id: bone_id.map(str::to_string),
+            workspace: workspace.to_string(),
+            agent: agent.to_string(),
+            reason: reason.to_string(),
+            critical: change.describe(),
+            recorded_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        }
+    }
+}
+
+/// Append-only JSONL log of critical gate overrides.
+pub struct OverrideLog {
+    path: PathBuf,
+}
+
+impl OverrideLog {
+    pub fn new(project_root: &Path) -> Self {
+        Self::at(journal::cache_dir(project_root).join(OVERRIDES_FILE))
+    }
+
+    pub const fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record an override. Unlike vote snapshots a failure here is an error: an
+    /// override that can't be recorded must not be honored.
+    pub fn record(&self, entry: &CriticalOverride) -> anyhow::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub fn entries(&self) -> Vec<CriticalOverride> {
+        let Ok(content) = fs::read_to_string(&self.path) else {
+            return Vec::new();
+        };
+        content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+}
+
+/// Steps that make an override visible: a bone comment and a channel announcement.
+pub fn override_steps(
+    entry: &CriticalOverride,
+    project: &str,
+    approvers: &[String],
+) -> Vec<String> {
+    let summary = format!(
+        "Critical gate overridden by {} in {}: {} (critical: {})",
+        entry.agent, entry.workspace, entry.reason, entry.critical
+    );
+    let mut steps = Vec::new();
+    if let Some(bone_id) = &entry.bone_id {
+        steps.push(shell::bn_comment_cmd(bone_id, &summary));
+    }
+    let mentions: Vec<String> = approvers.iter().map(|a| format!("@{a}")).collect();
+    steps.push(shell::rite_send_cmd(
+        "agent",
+        project,
+        &format!("{summary} {}", mentions.join(" ")),
+        "critical-override",
+    ));
+    steps
+}
+
+/// Steps asking the approvers for a decision.
+pub fn approval_request_steps(
+    bone_id: Option<&str>,
+    review_id: Option<&str>,
+    project: &str,
+    change: &CriticalChange,
+    approvers: &[String],
+) -> Vec<String> {
+    let subject = match (bone_id, review_id) {
+        (Some(b), Some(r)) => format!("{b} (review {r})"),
+        (Some(b), None) => b.to_string(),
+        (None, Some(r)) => format!("review {r}"),
+        (None, None) => "this change".to_string(),
+    };
+    let mentions: Vec<String> = approvers.iter().map(|a| format!("@{a}")).collect();
+    vec![shell::rite_send_cmd(
+        "agent",
+        project,
+        &format!(
+            "Critical change {subject} needs human approval before merge ({}). {}",
+            change.describe(),
+            mentions.join(" ")
+        ),
+        "review-request",
+    )]
+}
+
+/// Apply the critical gate for `command` (finish/merge) to `guidance`.
+///
+/// Returns `true` when the command may proceed: a critical approver's LGTM is current,
+/// or a non-empty override reason was given and recorded. Otherwise marks `guidance`
+/// blocked with the reason and the steps to request approval.
+pub fn enforce(
+    guidance: &mut ProtocolGuidance,
+    command: &str,
+    project_root: &Path,
+    config: &Config,
+    change: &CriticalChange,
+    bone_id: Option<&str>,
+    workspace: &str,
+    review: Option<(&str, &ReviewDetail)>,
+    agent: &str,
+    project: &str,
+    override_reason: Option<&str>,
+) -> bool {
+    let approvers = approvers(config);
+    let approval = check_approval(
+        &SnapshotStore::new(project_root),
+        &MawWorkspace(workspace),
+        review.map(|(_, detail)| detail),
+        &approvers,
+    );
+    if let CriticalApproval::Approved(by) = &approval {
+        guidance.diagnostic(format!(
+            "Critical change ({}) approved by {by}.",
+            change.describe()
+        ));
+        return true;
+    }
+
+    if let Some(reason) = override_reason.map(str::trim).filter(|r| !r.is_empty()) {
+        let entry = CriticalOverride::new(command, bone_id, workspace, agent, reason, change);
+        let log = OverrideLog::new(project_root);
+        if let Err(e) = log.record(&entry) {
+            guidance.blocked(format!(
+                "could not record critical override in {}: {e}",
+                log.path().display()
+            ));
+            return false;
+        }
+        guidance.diagnostic(format!(
+            "WARNING: critical gate overridden ({}): {reason}. Recorded in {}.",
+            change.describe(),
+            log.path().display()
+        ));
+        guidance.steps(override_steps(&entry, project, &approvers));
+        return true;
+    }
+
+    guidance.blocked(format!(
+        "Critical change ({}) requires an LGTM from one of: {}. --force does not bypass this.",
+        change.describe(),
+        approvers.join(", ")
+    ));
+    if let CriticalApproval::Stale(stale) = &approval {
+        for s in stale {
+            guidance.diagnostic(vote_snapshots::describe(s));
+        }
+    }
+    if let Some((review_id, _)) = review {
+        guidance.step(shell::seal_show_cmd(workspace, review_id));
+    }
+    guidance.steps(approval_request_steps(
+        bone_id,
+        review.map(|(id, _)| id),
+        project,
+        change,
+        &approvers,
+    ));
+    guidance.advise(format!(
+        "Wait for a critical approver ({}) to LGTM, or pass --override-critical <reason> to proceed on their behalf.",
+        approvers.join(", ")
+    ));
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::protocol::adapters::ReviewVote;
+    use crate::commands::protocol::vote_snapshots::{FakeWorkspace, StaleReason, WorkspaceState};
+
+    fn config(extra: &str) -> Config {
+        Config::parse_toml(&format!(
+            "version = \"1\"\n[project]\nname = \"edict\"\ncritical_approvers = [\"@alice\", \"bob\"]\n{extra}"
+        ))
+        .unwrap()
+    }
+
+    fn paths(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn review(votes: &[(&str, &str)]) -> ReviewDetail {
+        ReviewDetail {
+            review_id: "cr-abc".into(),
+            title: None,
+            status: "open".into(),
+            change_id: None,
+            votes: votes
+                .iter()
+                .map(|(reviewer, vote)| ReviewVote {
+                    reviewer: (*reviewer).into(),
+                    vote: (*vote).into(),
+                    voted_at: Some("2026-02-16T10:00:00Z".into()),
+                    commit_id: None,
+                })
+                .collect(),
+            open_thread_count: 0,
+            threads: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn approvers_strip_mentions() {
+        assert_eq!(approvers(&config("")), vec!["alice", "bob"]);
+    }
+
+    #[test]
+    fn critical_label_is_critical() {
+        let change = assess(&config(""), Some(&paths(&["risk:critical"])), Some(&[])).unwrap();
+        assert_eq!(change.labels, vec!["risk:critical"]);
+        assert_eq!(change.describe(), "label risk:critical");
+        assert!(assess(&config(""), Some(&paths(&["risk:high"])), Some(&[])).is_none());
+    }
+
+    #[test]
+    fn unknown_bone_with_critical_labels_is_critical() {
+        let change = assess(&config(""), None, Some(&[])).unwrap();
+        assert!(change.labels_unknown);
+        assert_eq!(
+            change.describe(),
+            "bone labels unavailable, critical label assumed"
+        );
+        let no_labels = config("[review.critical]\nlabels = []\n");
+        assert!(assess(&no_labels, None, Some(&[])).is_none());
+    }
+
+    #[test]
+    fn critical_paths_match_touched_files() {
+        let config = config("[review.critical]\npaths = [\"/migrations/\", \"*.pem\"]\n");
+        let touched = paths(&["migrations/001.sql", "src/main.rs"]);
+        let change = assess(&config, Some(&[]), Some(&touched)).unwrap();
+        assert_eq!(change.paths, vec!["migrations/001.sql"]);
+        assert!(assess(&config, Some(&[]), Some(&paths(&["src/main.rs"]))).is_none());
+    }
+
+    #[test]
+    fn unknown_diff_with_critical_paths_is_critical() {
+        let config = config("[review.critical]\npaths = [\"/migrations/\"]\n");
+        assert!(assess(&config, Some(&[]), None).unwrap().diff_unknown);
+    }
+
+    #[test]
+    fn gate_inactive_without_approvers() {
+        let config = Config::parse_toml("version = \"1\"\n[project]\nname = \"edict\"\n").unwrap();
+        assert!(assess(&config, Some(&paths(&["risk:critical"])), None).is_none());
+    }
+
+    #[test]
+    fn agent_lgtm_is_not_critical_approval() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::at(dir.path().join("snapshots.jsonl"));
+        let approvers = paths(&["alice"]);
+        let review = review(&[("edict-security", "lgtm"), ("alice", "block")]);
+        assert_eq!(
+            check_approval(&store, &FakeWorkspace::default(), Some(&review), &approvers),
+            CriticalApproval::Missing
+        );
+        assert_eq!(
+            check_approval(&store, &FakeWorkspace::default(), None, &approvers),
+            CriticalApproval::Missing
+        );
+    }
+
+    #[test]
+    fn human_approver_lgtm_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::at(dir.path().join("snapshots.jsonl"));
+        let approvers = paths(&["alice"]);
+        let review = review(&[("edict-security", "lgtm"), ("alice", "lgtm")]);
+        let workspace = |commit: &str| FakeWorkspace {
+            state: WorkspaceState {
+                commit: commit.into(),
+                ..WorkspaceState::default()
+            },
+            changed: paths(&["migrations/002.sql"]),
+            committed: Vec::new(),
+        };
+
+        let approved = check_approval(&store, &workspace("aaaaaaa"), Some(&review), &approvers);
+        assert_eq!(approved, CriticalApproval::Approved("alice".into()));
+        let approved = check_approval(&store, &workspace("aaaaaaa"), Some(&review), &approvers);
+        assert_eq!(approved, CriticalApproval::Approved("alice".into()));
+
+        // The worker changes the workspace after alice approved.
+        let CriticalApproval::Stale(stale) =
+            check_approval(&store, &workspace("bbbbbbb"), Some(&review), &approvers)
+        else {
+            panic!("expected a stale approval");
+        };
+        assert_eq!(stale[0].reason, StaleReason::Changed);
+        assert_eq!(stale[0].delta, paths(&["migrations/002.sql"]));
+    }
+
+    #[test]
+    fn override_log_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = OverrideLog::at(dir.path().join(OVERRIDES_FILE));
+        let change = CriticalChange {
+            labels: paths(&["risk:critical"]),
+            paths: vec![],
+            diff_unknown: false,
+            labels_unknown: false,
+        };
+        let entry = CriticalOverride::new(
+            "merge",
+            Some("bd-1"),
+            "frost-castle",
+            "edict-lead",
+            "hotfix, alice approved on call",
+            &change,
+        );
+        log.record(&entry).unwrap();
+        assert_eq!(log.entries(), vec![entry.clone()]);
+
+        let steps = override_steps(&entry, "edict", &paths(&["alice"]));
+        assert_eq!(steps.len(), 2);
+        assert!(steps[0].contains("bn bone comment add bd-1"));
+        assert!(steps[1].contains("critical-override"));
+        assert!(steps[1].contains("@alice"));
+    }
+}
@@ -4,24 +4,34 @@
 //! gate status, and outputs the appropriate shell commands depending on whether
 //! the review is approved, blocked, or needs review.
 
+use std::path::Path;
+
 use super::context::ProtocolContext;
+use super::critical_gate;
 use super::executor;
+use super::last_guidance::Recorder;
 use super::render::{self, BoneRef, ProtocolGuidance, ProtocolStatus, ReviewRef};
-use super::review_gate::{self, ReviewGateStatus};
+use super::review_gate::{self, GatePolicy, ReviewGateStatus};
+use super::reviewer_rules;
+use super::secret_gate;
 use super::shell;
+use super::vote_snapshots::{self, SnapshotStore};
 use crate::commands::doctor::OutputFormat;
 use crate::config::Config;
 
 /// Execute the finish protocol command.
 pub fn execute(
+    project_root: &Path,
     bone_id: &str,
     no_merge: bool,
     force: bool,
+    override_critical: Option<&str>,
     execute: bool,
     agent: &str,
     project: &str,
     config: &Config,
     format: OutputFormat,
+    recorder: &Recorder,
 ) -> anyhow::Result<()> {
     // Collect state from rite and maw
     let ctx = match ProtocolContext::collect(project, agent) {
@@ -29,7 +39,7 @@ pub fn execute(
         Err(e) => {
             let mut guidance = ProtocolGuidance::new("finish");
             guidance.blocked(format!("failed to collect state: {}", e));
-            print_guidance(&guidance, format)?;
+            print_guidance(&guidance, format, recorder)?;
             return Ok(());
         }
     };
@@ -43,7 +53,7 @@ pub fn execute(
                 "bone {} not found. Check the ID with: maw exec default -- bn show {}",
                 bone_id, bone_id
             ));
-            print_guidance(&guidance, format)?;
+            print_guidance(&guidance, format, recorder)?;
             return Ok(());
         }
     };
@@ -58,7 +68,7 @@ pub fn execute(
     // Check bone is already closed
     if bone_info.state == "done" {
         guidance.blocked("bone is already done".to_string());
-        print_guidance(&guidance, format)?;
+        print_guidance(&guidance, format, recorder)?;
         return Ok(());
     }
 
@@ -72,7 +82,7 @@ pub fn execute(
              Check with: rite claims list --agent {} --format json",
             agent, bone_id, agent
         ));
-        print_guidance(&guidance, format)?;
+        print_guidance(&guidance, format, recorder)?;
         return Ok(());
     }
 
@@ -85,7 +95,7 @@ pub fn execute(
                  Cannot determine which workspace to merge.",
                 bone_id
             ));
-            print_guidance(&guidance, format)?;
+            print_guidance(&guidance, format, recorder)?;
             return Ok(());
         }
     };
@@ -94,23 +104,71 @@ pub fn execute(
         .find_workspace(&workspace)
         .and_then(|ws| ws.change_id.clone());
 
-    // Build required reviewers list from config: "{project}-{role}"
-    let required_reviewers: Vec<String> = config
-        .review
-        .reviewers
-        .iter()
-        .map(|role| format!("{}-{}", project, role))
-        .collect();
+    // Required reviewers ("{project}-{role}") for the files this workspace touches.
+    // If the diff is unavailable every active role is required.
+    let touched = ctx
+        .touched_paths(&workspace, config.project.trunk.as_deref())
+        .ok();
+    let required_reviewers =
+        reviewer_rules::required_reviewers(config, project, touched.as_deref());
 
     // Check review gate status
     let review_enabled = config.review.enabled && !required_reviewers.is_empty();
 
+    // Critical changes need a human approver's LGTM, whether or not agent review
+    // is enabled and regardless of --force
+    let critical = critical_gate::assess(config, Some(&bone_info.labels), touched.as_deref());
+    let review = if (review_enabled && !force) || critical.is_some() {
+        find_review_for_workspace(&ctx, &workspace)
+    } else {
+        None
+    };
+    if let Some(change) = &critical {
+        let proceed = critical_gate::enforce(
+            &mut guidance,
+            "finish",
+            project_root,
+            config,
+            change,
+            Some(bone_id),
+            &workspace,
+            review.as_ref().map(|(id, detail)| (id.as_str(), detail)),
+            agent,
+            project,
+            override_critical,
+        );
+        if !proceed {
+            print_guidance(&guidance, format, recorder)?;
+            return Ok(());
+        }
+    }
+
+    // Never emit merge steps for a diff that leaks secrets, --force or not
+    if !secret_gate::enforce(
+        &mut guidance,
+        &config.secrets,
+        &workspace,
+        config.project.trunk.as_deref(),
+    ) {
+        print_guidance(&guidance, format, recorder)?;
+        return Ok(());
+    }
+
     if review_enabled && !force {
-        // Try to find a review for this workspace
-        match find_review_for_workspace(&ctx, &workspace) {
+        // Use the review found for this workspace
+        match review {
             Some((review_id, review_detail)) => {
-                let decision =
-                    review_gate::evaluate_review_gate(&review_detail, &required_reviewers);
+                let mut decision = review_gate::evaluate_with_policy(
+                    &review_detail,
+                    &required_reviewers,
+                    &GatePolicy::from_config(&config.review, project),
+                );
+                let stale = vote_snapshots::apply_staleness(
+                    &SnapshotStore::new(project_root),
+                    &workspace,
+                    &review_detail,
+                    &mut decision,
+                );
                 if merge_target.is_none() {
                     merge_target = review_detail.change_id.clone();
                 }
@@ -118,6 +176,9 @@ pub fn execute(
                     review_id: review_id.clone(),
                     status: decision.status_str().to_string(),
                 });
+                for d in decision.diagnostics() {
+                    guidance.diagnostic(d);
+                }
 
                 match decision.status {
                     ReviewGateStatus::Approved => {
@@ -184,17 +245,24 @@ pub fn execute(
                             review_id
                         ));
                     }
+                    ReviewGateStatus::NeedsReview if !stale.is_empty() => {
+                        // Approved, but the workspace changed after the LGTM
+                        guidance.status = ProtocolStatus::NeedsReview;
+                        for s in &stale {
+                            guidance.diagnostic(vote_snapshots::describe(s));
+                        }
+                        guidance.steps(vote_snapshots::re_review_steps(
+                            &workspace, &review_id, project, "agent", &stale,
+                        ));
+                        guidance.advise(format!(
+                            "Review {} approval is stale: the workspace changed after approval, or the approved state is unknown. Re-request review of the new changes.",
+                            review_id
+                        ));
+                    }
                     ReviewGateStatus::NeedsReview => {
                         // Review exists but not all reviewers have voted
                         guidance.status = ProtocolStatus::NeedsReview;
 
-                        if !decision.missing_approvals.is_empty() {
-                            guidance.diagnostic(format!(
-                                "Awaiting votes from: {}",
-                                decision.missing_approvals.join(", ")
-                            ));
-                        }
-
                         let mut steps = Vec::new();
                         steps.push(shell::seal_show_cmd(&workspace, &review_id));
                         // Re-request from missing reviewers
@@ -291,7 +359,7 @@ pub fn execute(
         }
     }
 
-    print_guidance(&guidance, format)?;
+    print_guidance(&guidance, format, recorder)?;
     Ok(())
 }
 
@@ -411,11 +479,16 @@ fn execute_and_render(guidance: &ProtocolGuidance, format: OutputFormat) -> anyh
     Ok(())
 }
 
-/// Render and print guidance.
-fn print_guidance(guidance: &ProtocolGuidance, format: OutputFormat) -> anyhow::Result<()> {
+/// Render and print guidance, then record it as the agent's last guidance.
+fn print_guidance(
+    guidance: &ProtocolGuidance,
+    format: OutputFormat,
+    recorder: &Recorder,
+) -> anyhow::Result<()> {
     let output =
         render::render(guidance, format).map_err(|e| anyhow::anyhow!("render error: {}", e))?;
     println!("{}", output);
+    recorder.record(guidance);
     Ok(())
 }
 
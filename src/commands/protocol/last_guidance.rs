@@ -0,0 +1,165 @@
+//! The last protocol guidance each agent was given.
+//!
+//! Protocol commands record what they told the agent to do next, so the compaction
+//! hooks can repeat it after the conversation is compacted. Only the parts an agent acts
+//! on are kept; the store holds one entry per agent.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::render::{BoneRef, ProtocolGuidance, ProtocolStatus};
+use crate::commands::journal;
+
+/// Store file name in the project cache dir.
+pub const LAST_GUIDANCE_FILE: &str = "last-guidance.json";
+
+/// A rendered guidance, as recorded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedGuidance {
+    /// Protocol command: "start", "finish", "review", ...
+    pub command: String,
+    pub status: ProtocolStatus,
+    /// UTC ISO 8601 snapshot timestamp
+    pub snapshot_at: String,
+    #[serde(default)]
+    pub bone: Option<BoneRef>,
+    #[serde(default)]
+    pub workspace: Option<String>,
+    #[serde(default)]
+    pub steps: Vec<String>,
+    #[serde(default)]
+    pub diagnostics: Vec<String>,
+    #[serde(default)]
+    pub advice: Option<String>,
+}
+
+impl From<&ProtocolGuidance> for RecordedGuidance {
+    fn from(g: &ProtocolGuidance) -> Self {
+        Self {
+            command: g.command.to_string(),
+            status: g.status,
+            snapshot_at: g.snapshot_at.clone(),
+            bone: g.bone.clone(),
+            workspace: g.workspace.clone(),
+            steps: g.steps.clone(),
+            diagnostics: g.diagnostics.clone(),
+            advice: g.advice.clone(),
+        }
+    }
+}
+
+/// JSON store of the last guidance per agent.
+#[derive(Debug)]
+pub struct GuidanceStore {
+    path: PathBuf,
+}
+
+impl GuidanceStore {
+    /// The store of the project containing `dir`.
+    ///
+    /// Protocol commands and hooks may run from different directories of one project
+    /// (the repo root, a workspace, the config dir), so both resolve the root with
+    /// `hooks::project_root` to land on the same store.
+    pub fn new(dir: &Path) -> Self {
+        let root = crate::hooks::project_root(dir).unwrap_or_else(|| dir.to_path_buf());
+        Self::at(journal::cache_dir(&root).join(LAST_GUIDANCE_FILE))
+    }
+
+    pub const fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn load(&self) -> BTreeMap<String, RecordedGuidance> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn record(&self, agent: &str, guidance: &ProtocolGuidance) {
+        let mut entries = self.load();
+        entries.insert(agent.to_string(), guidance.into());
+        let result = (|| -> std::io::Result<()> {
+            if let Some(parent) = self.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let json = serde_json::to_string_pretty(&entries).map_err(std::io::Error::other)?;
+            fs::write(&self.path, json)
+        })();
+        if let Err(e) = result {
+            tracing::warn!(path = %self.path.display(), error = %e, "failed to record protocol guidance");
+        }
+    }
+
+    pub fn last(&self, agent: &str) -> Option<RecordedGuidance> {
+        self.load().remove(agent)
+    }
+}
+
+/// Records the guidance a protocol command printed as its agent's last guidance.
+#[derive(Debug)]
+pub struct Recorder {
+    store: GuidanceStore,
+    agent: String,
+}
+
+impl Recorder {
+    pub fn new(project_root: &Path, agent: &str) -> Self {
+        Self {
+            store: GuidanceStore::new(project_root),
+            agent: agent.to_string(),
+        }
+    }
+
+    pub fn record(&self, guidance: &ProtocolGuidance) {
+        self.store.record(&self.agent, guidance);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_guidance_per_agent() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = GuidanceStore::at(dir.path().join(LAST_GUIDANCE_FILE));
+        assert!(store.last("frost").is_none());
+
+        let mut start = ProtocolGuidance::new("start");
+        start.workspace = Some("frost-ws".into());
+        start.step("maw exec frost-ws -- cargo test".into());
+        store.record("frost", &start);
+
+        let mut finish = ProtocolGuidance::new("finish");
+        finish.blocked("review not approved".into());
+        store.record("amber", &finish);
+
+        let last = store.last("frost").unwrap();
+        assert_eq!(last.command, "start");
+        assert_eq!(last.workspace.as_deref(), Some("frost-ws"));
+        assert_eq!(last.steps, vec!["maw exec frost-ws -- cargo test"]);
+        assert_eq!(store.last("amber").unwrap().status, ProtocolStatus::Blocked);
+
+        store.record("frost", &finish);
+        assert_eq!(store.last("frost").unwrap().command, "finish");
+    }
+
+    #[test]
+    fn workspace_and_root_share_a_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join(".manifold")).unwrap();
+        fs::create_dir_all(root.join("ws/frost-ws/src")).unwrap();
+        fs::create_dir_all(root.join("ws/default")).unwrap();
+        fs::write(root.join("ws/default/.edict.toml"), "").unwrap();
+
+        let from_workspace = GuidanceStore::new(&root.join("ws/frost-ws/src"));
+        let from_config_dir = GuidanceStore::new(&root.join("ws/default"));
+        assert_eq!(from_workspace.path, GuidanceStore::new(root).path);
+        assert_eq!(from_config_dir.path, GuidanceStore::new(root).path);
+    }
+}
@@ -6,14 +6,20 @@
 //! recovery guidance.
 
 use std::io::IsTerminal;
+use std::path::Path;
 
 use anyhow::Context;
 use serde::Deserialize;
 
 use super::context::ProtocolContext;
+use super::critical_gate;
+use super::last_guidance::Recorder;
 use super::render::{self, ProtocolGuidance, ProtocolStatus};
-use super::review_gate::{self, ReviewGateStatus};
+use super::review_gate::{self, GatePolicy, ReviewGateStatus};
+use super::reviewer_rules;
+use super::secret_gate;
 use super::shell;
+use super::vote_snapshots::{self, SnapshotStore};
 use crate::commands::doctor::OutputFormat;
 use crate::config::Config;
 
@@ -132,14 +138,17 @@ impl MergeCheckResult {
 
 /// Execute the merge protocol command.
 pub fn execute(
+    project_root: &Path,
     workspace: &str,
     message: &str,
     force: bool,
+    override_critical: Option<&str>,
     execute: bool,
     agent: &str,
     project: &str,
     config: &Config,
     format: OutputFormat,
+    recorder: &Recorder,
 ) -> anyhow::Result<()> {
     // Reject merging default workspace
     if workspace == "default" {
@@ -149,7 +158,7 @@ pub fn execute(
              Default is the merge TARGET — other workspaces merge INTO it."
                 .to_string(),
         );
-        print_guidance(&guidance, format)?;
+        print_guidance(&guidance, format, recorder)?;
         return Ok(());
     }
 
@@ -159,7 +168,7 @@ pub fn execute(
         Err(e) => {
             let mut guidance = ProtocolGuidance::new("merge");
             guidance.blocked(format!("failed to collect state: {}", e));
-            print_guidance(&guidance, format)?;
+            print_guidance(&guidance, format, recorder)?;
             return Ok(());
         }
     };
@@ -178,12 +187,33 @@ pub fn execute(
             "workspace '{}' not found. Check with: maw ws list",
             workspace
         ));
-        print_guidance(&guidance, format)?;
+        print_guidance(&guidance, format, recorder)?;
         return Ok(());
     }
 
-    // Try to find the associated bone from workspace claims
-    let bone_id = find_bone_for_workspace(&ctx, workspace);
+    // Check review gate (if enabled), requiring the reviewers for the touched paths
+    let touched = ctx
+        .touched_paths(workspace, config.project.trunk.as_deref())
+        .ok();
+    let required_reviewers =
+        reviewer_rules::required_reviewers(config, project, touched.as_deref());
+    let review_enabled = config.review.enabled && !required_reviewers.is_empty();
+    let critical_gate_active = !critical_gate::approvers(config).is_empty();
+    let review = if review_enabled || critical_gate_active {
+        find_review_for_workspace(&ctx, workspace)
+    } else {
+        None
+    };
+
+    // Find the associated bone from workspace claims, else from the review title
+    let bone_id = find_bone_for_workspace(&ctx, workspace).or_else(|| {
+        review
+            .as_ref()
+            .and_then(|(_, detail)| detail.title.as_deref())
+            .and_then(bone_from_review_title)
+    });
+    // None until the bone's labels are known; the critical gate fails closed on None
+    let mut bone_labels: Option<Vec<String>> = None;
 
     if let Some(ref bone_id) = bone_id {
         guidance.bone = Some(render::BoneRef {
@@ -198,6 +228,7 @@ pub fn execute(
                     id: bone_id.clone(),
                     title: bone_info.title.clone(),
                 });
+                bone_labels = Some(bone_info.labels.clone());
 
                 if bone_info.state != "done" && !force {
                     guidance.status = ProtocolStatus::Blocked;
@@ -214,7 +245,7 @@ pub fn execute(
                     steps.push(format!("maw exec default -- bn show {}", bone_id));
                     guidance.steps(steps);
 
-                    print_guidance(&guidance, format)?;
+                    print_guidance(&guidance, format, recorder)?;
                     return Ok(());
                 }
             }
@@ -232,20 +263,53 @@ pub fn execute(
         );
     }
 
-    // Check review gate (if enabled)
-    let required_reviewers: Vec<String> = config
-        .review
-        .reviewers
-        .iter()
-        .map(|role| format!("{}-{}", project, role))
-        .collect();
-    let review_enabled = config.review.enabled && !required_reviewers.is_empty();
+    // Critical changes need a human approver's LGTM, regardless of --force
+    let critical = critical_gate::assess(config, bone_labels.as_deref(), touched.as_deref());
+    if let Some(change) = &critical {
+        let proceed = critical_gate::enforce(
+            &mut guidance,
+            "merge",
+            project_root,
+            config,
+            change,
+            bone_id.as_deref(),
+            workspace,
+            review.as_ref().map(|(id, detail)| (id.as_str(), detail)),
+            agent,
+            project,
+            override_critical,
+        );
+        if !proceed {
+            print_guidance(&guidance, format, recorder)?;
+            return Ok(());
+        }
+    }
+
+    // Never emit merge steps for a diff that leaks secrets, --force or not
+    if !secret_gate::enforce(
+        &mut guidance,
+        &config.secrets,
+        workspace,
+        config.project.trunk.as_deref(),
+    ) {
+        print_guidance(&guidance, format, recorder)?;
+        return Ok(());
+    }
 
     if review_enabled && !force {
-        match find_review_for_workspace(&ctx, workspace) {
+        match &review {
             Some((review_id, review_detail)) => {
-                let decision =
-                    review_gate::evaluate_review_gate(&review_detail, &required_reviewers);
+                let mut decision = review_gate::evaluate_with_policy(
+                    review_detail,
+                    &required_reviewers,
+                    &GatePolicy::from_config(&config.review, project),
+                );
+                let stale = vote_snapshots::apply_staleness(
+                    &SnapshotStore::new(project_root),
+                    workspace,
+                    review_detail,
+                    &mut decision,
+                );
                 if merge_target.is_none() {
                     merge_target = review_detail.change_id.clone();
                 }
@@ -253,6 +317,9 @@ pub fn execute(
                     review_id: review_id.clone(),
                     status: decision.status_str().to_string(),
                 });
+                for d in decision.diagnostics() {
+                    guidance.diagnostic(d);
+                }
 
                 match decision.status {
                     ReviewGateStatus::Approved => {
@@ -273,15 +340,30 @@ pub fn execute(
                         steps.push(shell::seal_show_cmd(workspace, &review_id));
                         guidance.steps(steps);
 
-                        print_guidance(&guidance, format)?;
+                        print_guidance(&guidance, format, recorder)?;
+                        return Ok(());
+                    }
+                    ReviewGateStatus::NeedsReview if !stale.is_empty() => {
+                        guidance.status = ProtocolStatus::NeedsReview;
+                        for s in &stale {
+                            guidance.diagnostic(vote_snapshots::describe(s));
+                        }
+                        guidance.advise(
+                            "Approval is stale: the workspace changed after approval, or the approved state is unknown. Re-request review of the new changes before merging."
+                                .to_string(),
+                        );
+                        guidance.steps(vote_snapshots::re_review_steps(
+                            workspace, &review_id, project, "agent", &stale,
+                        ));
+
+                        print_guidance(&guidance, format, recorder)?;
                         return Ok(());
                     }
                     ReviewGateStatus::NeedsReview => {
                         guidance.status = ProtocolStatus::NeedsReview;
                         guidance.diagnostic(format!(
-                            "Review {} still awaiting votes from: {}",
-                            review_id,
-                            decision.missing_approvals.join(", ")
+                            "Review {} is not yet approved under the review policy.",
+                            review_id
                         ));
                         guidance.advise(
                             "Wait for reviewers or re-request review before merging.".to_string(),
@@ -291,7 +373,7 @@ pub fn execute(
                         steps.push(shell::seal_show_cmd(workspace, &review_id));
                         guidance.steps(steps);
 
-                        print_guidance(&guidance, format)?;
+                        print_guidance(&guidance, format, recorder)?;
                         return Ok(());
                     }
                 }
@@ -320,7 +402,7 @@ pub fn execute(
                     ));
                     guidance.steps(steps);
 
-                    print_guidance(&guidance, format)?;
+                    print_guidance(&guidance, format, recorder)?;
                     return Ok(());
                 }
             }
@@ -353,7 +435,7 @@ pub fn execute(
                     merge_target.as_deref(),
                     message,
                 );
-                print_guidance(&guidance, format)?;
+                print_guidance(&guidance, format, recorder)?;
                 return Ok(());
             }
         }
@@ -369,7 +451,7 @@ pub fn execute(
     // All preconditions met — build merge steps
     guidance.status = ProtocolStatus::Ready;
     let review_id = if review_enabled {
-        find_review_for_workspace(&ctx, workspace).map(|(id, _)| id)
+        review.map(|(id, _)| id)
     } else {
         None
     };
@@ -387,7 +469,7 @@ pub fn execute(
 
     // Execute if --execute flag is set
     if execute {
-        return execute_and_render(&guidance, workspace, message, format);
+        return execute_and_render(&guidance, workspace, message, format, recorder);
     }
 
     if force {
@@ -403,7 +485,7 @@ pub fn execute(
         ));
     }
 
-    print_guidance(&guidance, format)?;
+    print_guidance(&guidance, format, recorder)?;
     Ok(())
 }
 
@@ -565,6 +647,14 @@ fn find_bone_for_workspace(ctx: &ProtocolContext, workspace: &str) -> Option<Str
     None
 }
 
+/// Bone ID from a review title created by `edict protocol review` (`<bone-id>: <title>`).
+fn bone_from_review_title(title: &str) -> Option<String> {
+    let (id, _) = title.split_once(':')?;
+    let id = id.trim();
+    shell::validate_bone_id(id).ok()?;
+    Some(id.to_string())
+}
+
 /// Try to find a review for a workspace.
 fn find_review_for_workspace(
     ctx: &ProtocolContext,
@@ -604,6 +694,7 @@ fn execute_and_render(
     workspace: &str,
     merge_msg: &str,
     format: OutputFormat,
+    recorder: &Recorder,
 ) -> anyhow::Result<()> {
     use super::executor;
 
@@ -626,9 +717,7 @@ fn execute_and_render(
         ));
         add_conflict_recovery_guidance(&mut conflict_guidance, workspace, None, merge_msg);
 
-        let output = render::render(&conflict_guidance, format)
-            .map_err(|e| anyhow::anyhow!("render error: {}", e))?;
-        println!("{}", output);
+        print_guidance(&conflict_guidance, format, recorder)?;
         std::process::exit(1);
     }
 
@@ -642,11 +731,16 @@ fn execute_and_render(
     Ok(())
 }
 
-/// Render and print guidance.
-fn print_guidance(guidance: &ProtocolGuidance, format: OutputFormat) -> anyhow::Result<()> {
+/// Render and print guidance, then record it as the agent's last guidance.
+fn print_guidance(
+    guidance: &ProtocolGuidance,
+    format: OutputFormat,
+    recorder: &Recorder,
+) -> anyhow::Result<()> {
     let output =
         render::render(guidance, format).map_err(|e| anyhow::anyhow!("render error: {}", e))?;
     println!("{}", output);
+    recorder.record(guidance);
     Ok(())
 }
 
@@ -764,6 +858,16 @@ mod tests {
         assert_eq!(result.conflict_labels()[0], "src/main.rs");
     }
 
+    #[test]
+    fn test_bone_from_review_title() {
+        assert_eq!(
+            bone_from_review_title("bn-3smm: Fix login"),
+            Some("bn-3smm".to_string())
+        );
+        assert_eq!(bone_from_review_title("Fix login"), None);
+        assert_eq!(bone_from_review_title("$(rm -rf /): x"), None);
+    }
+
     #[test]
     fn test_merge_check_result_parsing_stale() {
         let json = r#"{"status": "blocked", "has_conflicts": false, "conflicts": [], "stale": true, "message": "workspace is stale"}"#;
@@ -1,15 +1,20 @@
 pub mod adapters;
 pub mod cleanup;
 pub mod context;
+pub mod critical_gate;
 pub mod executor;
 pub mod exit_policy;
 pub mod finish;
+pub mod last_guidance;
 pub mod merge;
 pub mod render;
 pub mod resume;
 pub mod review;
 pub mod review_gate;
+pub mod reviewer_rules;
+pub mod secret_gate;
 pub mod shell;
+pub mod vote_snapshots;
 
 use std::io::IsTerminal;
 use std::path::PathBuf;
@@ -97,6 +102,10 @@ pub enum ProtocolCommand {
         /// Output finish commands even without review approval
         #[arg(long)]
         force: bool,
+        /// Proceed without a critical approver's LGTM. The reason is recorded and announced;
+        /// --force alone does not bypass the critical gate
+        #[arg(long, value_name = "REASON")]
+        override_critical: Option<String>,
         /// Execute finish commands directly instead of outputting them
         #[arg(long)]
         execute: bool,
@@ -143,6 +152,10 @@ pub enum ProtocolCommand {
         /// Merge even if bone is not closed or review is not approved
         #[arg(long)]
         force: bool,
+        /// Merge without a critical approver's LGTM. The reason is recorded and announced;
+        /// --force alone does not bypass the critical gate
+        #[arg(long, value_name = "REASON")]
+        override_critical: Option<String>,
         /// Execute merge commands directly instead of outputting them
         #[arg(long)]
         execute: bool,
@@ -164,6 +177,7 @@ impl ProtocolCommand {
                 bone_id,
                 no_merge,
                 force,
+                override_critical,
                 execute,
                 args,
             } => {
@@ -179,10 +193,21 @@ impl ProtocolCommand {
 
                 let project = args.resolve_project(&config);
                 let agent = args.resolve_agent(&config);
+                let recorder = last_guidance::Recorder::new(&project_root, &agent);
                 let format = args.resolve_format();
 
                 finish::execute(
-                    bone_id, *no_merge, *force, *execute, &agent, &project, &config, format,
+                    &project_root,
+                    bone_id,
+                    *no_merge,
+                    *force,
+                    override_critical.as_deref(),
+                    *execute,
+                    &agent,
+                    &project,
+                    &config,
+                    format,
+                    &recorder,
                 )
             }
             ProtocolCommand::Review {
@@ -203,10 +228,12 @@ impl ProtocolCommand {
                 let config = Config::load(&config_path)?;
 
                 let agent = args.resolve_agent(&config);
+                let recorder = last_guidance::Recorder::new(&project_root, &agent);
                 let project = args.resolve_project(&config);
                 let format = args.resolve_format();
 
                 review::execute(
+                    &project_root,
                     bone_id,
                     reviewers.as_deref(),
                     review_id.as_deref(),
@@ -215,6 +242,7 @@ impl ProtocolCommand {
                     &project,
                     &config,
                     format,
+                    &recorder,
                 )
             }
             ProtocolCommand::Cleanup { execute, args } => {
@@ -229,14 +257,16 @@ impl ProtocolCommand {
                 let config = crate::config::Config::load(&config_path)?;
 
                 let agent = args.resolve_agent(&config);
+                let recorder = last_guidance::Recorder::new(&project_root, &agent);
                 let project = args.resolve_project(&config);
                 let format = args.resolve_format();
-                cleanup::execute(*execute, &agent, &project, format)
+                cleanup::execute(*execute, &agent, &project, format, &recorder)
             }
             ProtocolCommand::Merge {
                 workspace,
                 message,
                 force,
+                override_critical,
                 execute,
                 args,
             } => {
@@ -252,19 +282,23 @@ impl ProtocolCommand {
 
                 let project = args.resolve_project(&config);
                 let agent = args.resolve_agent(&config);
+                let recorder = last_guidance::Recorder::new(&project_root, &agent);
                 let format = args.resolve_format();
 
                 let resolved_message = merge::resolve_message(message.as_deref())?;
 
                 merge::execute(
+                    &project_root,
                     workspace,
                     &resolved_message,
                     *force,
+                    override_critical.as_deref(),
                     *execute,
                     &agent,
                     &project,
                     &config,
                     format,
+                    &recorder,
                 )
             }
             ProtocolCommand::Resume { args } => {
@@ -279,9 +313,10 @@ impl ProtocolCommand {
                 let config = crate::config::Config::load(&config_path)?;
 
                 let agent = args.resolve_agent(&config);
+                let recorder = last_guidance::Recorder::new(&project_root, &agent);
                 let project = args.resolve_project(&config);
                 let format = args.resolve_format();
-                resume::execute(&agent, &project, &config, format)
+                resume::execute(&project_root, &agent, &project, &config, format, &recorder)
             }
         }
     }
@@ -323,6 +358,7 @@ impl ProtocolCommand {
 
         let project = args.resolve_project(&config);
         let agent = args.resolve_agent(&config);
+        let recorder = last_guidance::Recorder::new(&project_root, &agent);
         let format = args.resolve_format();
 
         // Collect state from rite and maw
@@ -337,7 +373,7 @@ impl ProtocolCommand {
                     "bone {} not found. Check the ID with: maw exec default -- bn show {}",
                     bone_id, bone_id
                 ));
-                return exit_policy::render_guidance(&guidance, format);
+                return print_guidance(&guidance, format, &recorder);
             }
         };
 
@@ -350,7 +386,7 @@ impl ProtocolCommand {
         // Status check: is bone done?
         if bone_info.state == "done" {
             guidance.blocked("bone is already done".to_string());
-            return exit_policy::render_guidance(&guidance, format);
+            return print_guidance(&guidance, format, &recorder);
         }
 
         // Check for claim conflicts
@@ -360,11 +396,11 @@ impl ProtocolCommand {
                 guidance.diagnostic(
                     "Check current claims with: rite claims list --format json".to_string(),
                 );
-                return exit_policy::render_guidance(&guidance, format);
+                return print_guidance(&guidance, format, &recorder);
             }
             Err(e) => {
                 guidance.blocked(format!("failed to check claim conflict: {}", e));
-                return exit_policy::render_guidance(&guidance, format);
+                return print_guidance(&guidance, format, &recorder);
             }
             Ok(None) => {
                 // No conflict, proceed
@@ -382,7 +418,7 @@ impl ProtocolCommand {
                 "Resume work in workspace {} with: edict protocol resume",
                 ws_name
             ));
-            return exit_policy::render_guidance(&guidance, format);
+            return print_guidance(&guidance, format, &recorder);
         }
 
         // READY: generate start commands
@@ -455,7 +491,18 @@ impl ProtocolCommand {
             Ok(())
         } else {
             // Otherwise, render guidance as usual
-            exit_policy::render_guidance(&guidance, format)
+            print_guidance(&guidance, format, &recorder)
         }
     }
 }
+
+/// Render and print guidance, then record it as the agent's last guidance.
+fn print_guidance(
+    guidance: &render::ProtocolGuidance,
+    format: OutputFormat,
+    recorder: &last_guidance::Recorder,
+) -> anyhow::Result<()> {
+    exit_policy::render_guidance(guidance, format)?;
+    recorder.record(guidance);
+    Ok(())
+}
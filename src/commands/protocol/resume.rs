@@ -4,10 +4,15 @@
 //! state to produce per-bone guidance: continue working, address review feedback,
 //! ready to finish, or start fresh.
 
+use std::path::Path;
+
 use super::context::ProtocolContext;
+use super::last_guidance::Recorder;
 use super::render::{self, BoneRef, ProtocolGuidance, ProtocolStatus, ReviewRef};
 use super::review_gate::{self, ReviewGateStatus};
+use super::reviewer_rules;
 use super::shell;
+use super::vote_snapshots::{self, SnapshotStore, StaleApproval};
 use crate::commands::doctor::OutputFormat;
 use crate::config::Config;
 
@@ -25,36 +30,48 @@ struct BoneResume {
 struct ReviewState {
     review_id: String,
     gate: ReviewGateStatus,
+    /// Gate status as reported by `ReviewGateDecision::status_str`
+    status: &'static str,
+    /// Approvals revoked because the workspace moved (or was never snapshotted)
+    stale: Vec<StaleApproval>,
     open_threads: usize,
 }
 
 /// Execute the resume protocol command.
 pub fn execute(
+    project_root: &Path,
     agent: &str,
     project: &str,
     config: &Config,
     format: OutputFormat,
+    recorder: &Recorder,
 ) -> anyhow::Result<()> {
     let ctx = ProtocolContext::collect(project, agent)?;
 
     let bone_claims = ctx.held_bone_claims();
 
     if bone_claims.is_empty() {
-        return render_fresh(agent, format);
+        return render_fresh(agent, format, recorder);
     }
 
     // Assess each held bone
+    let snapshots = SnapshotStore::new(project_root);
     let mut assessments = Vec::new();
     for (bone_id, _pattern) in &bone_claims {
-        let assessment = assess_bone(&ctx, bone_id, config);
+        let assessment = assess_bone(&ctx, &snapshots, bone_id, config);
         assessments.push(assessment);
     }
 
-    render_resume(&assessments, agent, project, format)
+    render_resume(&assessments, agent, project, format, recorder)
 }
 
 /// Assess a single held bone's state.
-fn assess_bone(ctx: &ProtocolContext, bone_id: &str, config: &Config) -> BoneResume {
+fn assess_bone(
+    ctx: &ProtocolContext,
+    snapshots: &SnapshotStore,
+    bone_id: &str,
+    config: &Config,
+) -> BoneResume {
     let (title, state) = match ctx.bone_status(bone_id) {
         Ok(bone) => (bone.title.clone(), bone.state.clone()),
         Err(_) => (String::new(), "unknown".to_string()),
@@ -68,18 +85,19 @@ fn assess_bone(ctx: &ProtocolContext, bone_id: &str, config: &Config) -> BoneRes
         let review_summary = reviews.into_iter().next()?;
         let detail = ctx.review_status(&review_summary.review_id, ws).ok()?;
 
-        let required_reviewers: Vec<String> = config
-            .review
-            .reviewers
-            .iter()
-            .map(|r| format!("{}-{}", config.project.name, r))
-            .collect();
+        let touched = ctx.touched_paths(ws, config.project.trunk.as_deref()).ok();
+        let required_reviewers =
+            reviewer_rules::required_reviewers(config, &config.project.name, touched.as_deref());
 
-        let gate = review_gate::evaluate_review_gate(&detail, &required_reviewers);
+        let policy = review_gate::GatePolicy::from_config(&config.review, &config.project.name);
+        let mut gate = review_gate::evaluate_with_policy(&detail, &required_reviewers, &policy);
+        let stale = vote_snapshots::apply_staleness(snapshots, ws, &detail, &mut gate);
 
         Some(ReviewState {
             review_id: review_summary.review_id,
             gate: gate.status,
+            status: gate.status_str(),
+            stale,
             open_threads: detail.open_thread_count,
         })
     });
@@ -94,7 +112,7 @@ fn assess_bone(ctx: &ProtocolContext, bone_id: &str, config: &Config) -> BoneRes
 }
 
 /// Render guidance when no held claims exist (fresh start).
-fn render_fresh(_agent: &str, format: OutputFormat) -> anyhow::Result<()> {
+fn render_fresh(_agent: &str, format: OutputFormat, recorder: &Recorder) -> anyhow::Result<()> {
     let mut guidance = ProtocolGuidance::new("resume");
     guidance.status = ProtocolStatus::Fresh;
     guidance.set_freshness(300, Some("edict protocol resume".to_string()));
@@ -109,6 +127,7 @@ fn render_fresh(_agent: &str, format: OutputFormat) -> anyhow::Result<()> {
     let output =
         render::render(&guidance, format).map_err(|e| anyhow::anyhow!("render error: {}", e))?;
     println!("{}", output);
+    recorder.record(&guidance);
     Ok(())
 }
 
@@ -118,6 +137,7 @@ fn render_resume(
     agent: &str,
     project: &str,
     format: OutputFormat,
+    recorder: &Recorder,
 ) -> anyhow::Result<()> {
     let mut guidance = ProtocolGuidance::new("resume");
     guidance.status = ProtocolStatus::Resumable;
@@ -136,11 +156,7 @@ fn render_resume(
         if let Some(ref review) = a.review {
             guidance.review = Some(ReviewRef {
                 review_id: review.review_id.clone(),
-                status: match review.gate {
-                    ReviewGateStatus::Approved => "approved".to_string(),
-                    ReviewGateStatus::Blocked => "blocked".to_string(),
-                    ReviewGateStatus::NeedsReview => "needs-review".to_string(),
-                },
+                status: review.status.to_string(),
             });
         }
     }
@@ -200,6 +216,7 @@ fn render_resume(
     let output =
         render::render(&guidance, format).map_err(|e| anyhow::anyhow!("render error: {}", e))?;
     println!("{}", output);
+    recorder.record(&guidance);
     Ok(())
 }
 
@@ -245,6 +262,24 @@ fn build_bone_guidance(
             ));
         }
 
+        // Approval went stale → re-request review of the current changes
+        (Some(review), Some(ws_name)) if !review.stale.is_empty() => {
+            guidance.step(format!(
+                "# {} — review {} approval is stale, re-request review",
+                bead_id, review.review_id
+            ));
+            for s in &review.stale {
+                guidance.diagnostic(vote_snapshots::describe(s));
+            }
+            guidance.steps(vote_snapshots::re_review_steps(
+                ws_name,
+                &review.review_id,
+                project,
+                "agent",
+                &review.stale,
+            ));
+        }
+
         // Review pending → wait or check
         (Some(review), Some(ws_name)) => {
             guidance.step(format!(
@@ -343,6 +378,8 @@ mod tests {
             review: Some(ReviewState {
                 review_id: "cr-xyz".to_string(),
                 gate: ReviewGateStatus::Approved,
+                status: "approved",
+                stale: Vec::new(),
                 open_threads: 0,
             }),
         };
@@ -364,6 +401,8 @@ mod tests {
             review: Some(ReviewState {
                 review_id: "cr-xyz".to_string(),
                 gate: ReviewGateStatus::Blocked,
+                status: "blocked",
+                stale: Vec::new(),
                 open_threads: 2,
             }),
         };
@@ -391,6 +430,8 @@ mod tests {
             review: Some(ReviewState {
                 review_id: "cr-xyz".to_string(),
                 gate: ReviewGateStatus::NeedsReview,
+                status: "needs-review",
+                stale: Vec::new(),
                 open_threads: 0,
             }),
         };
@@ -430,6 +471,8 @@ mod tests {
             review: Some(ReviewState {
                 review_id: "cr-xyz".to_string(),
                 gate: ReviewGateStatus::NeedsReview,
+                status: "needs-review",
+                stale: Vec::new(),
                 open_threads: 0,
             }),
         };
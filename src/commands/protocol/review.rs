@@ -3,16 +3,23 @@
 //! Resolves bone claim, workspace, existing review status, and reviewer list
 //! to produce guidance for creating or following up on a code review.
 
+use std::path::Path;
+
 use super::context::ProtocolContext;
 use super::executor;
+use super::last_guidance::Recorder;
 use super::render::{BoneRef, ProtocolGuidance, ProtocolStatus, ReviewRef};
-use super::review_gate::{self, ReviewGateStatus};
+use super::review_gate::{self, GatePolicy, ReviewGateStatus};
+use super::reviewer_rules;
 use super::shell;
+use super::vote_snapshots::{self, SnapshotStore};
 use crate::commands::doctor::OutputFormat;
 use crate::config::Config;
 
 /// Execute review protocol: check state and output review guidance.
+#[allow(clippy::too_many_arguments)]
 pub fn execute(
+    project_root: &Path,
     bone_id: &str,
     reviewers_override: Option<&str>,
     review_id_flag: Option<&str>,
@@ -21,6 +28,7 @@ pub fn execute(
     project: &str,
     config: &Config,
     format: OutputFormat,
+    recorder: &Recorder,
 ) -> anyhow::Result<()> {
     // Early input validation before any subprocess calls
     if let Err(e) = shell::validate_bone_id(bone_id) {
@@ -37,7 +45,7 @@ pub fn execute(
         Ok(bone) => bone,
         Err(e) => {
             guidance.blocked(format!("bone {bone_id} not found: {e}"));
-            print_guidance(&guidance, format)?;
+            print_guidance(&guidance, format, recorder)?;
             return Ok(());
         }
     };
@@ -56,7 +64,7 @@ pub fn execute(
              Stake a claim first with: {}",
             shell::claims_stake_cmd("agent", &format!("bone://{project}/{bone_id}"), bone_id,)
         ));
-        print_guidance(&guidance, format)?;
+        print_guidance(&guidance, format, recorder)?;
         return Ok(());
     }
 
@@ -68,7 +76,7 @@ pub fn execute(
                 "no workspace claim found for bone {bone_id}. \
                  Create workspace and stake claim first."
             ));
-            print_guidance(&guidance, format)?;
+            print_guidance(&guidance, format, recorder)?;
             return Ok(());
         }
     };
@@ -76,13 +84,44 @@ pub fn execute(
     // Validate workspace name before it flows into subprocess calls
     if let Err(e) = shell::validate_workspace_name(&workspace) {
         guidance.blocked(format!("invalid workspace name from claims: {e}"));
-        print_guidance(&guidance, format)?;
+        print_guidance(&guidance, format, recorder)?;
         return Ok(());
     }
     guidance.workspace = Some(workspace.clone());
 
+    // Path-scoped assignment needs the files this workspace touches (skipped for --reviewers)
+    let touched = if reviewers_override.is_none() {
+        match ctx.touched_paths(&workspace, config.project.trunk.as_deref()) {
+            Ok(paths) => Some(paths),
+            Err(e) => {
+                guidance.diagnostic(format!(
+                    "Could not diff workspace {workspace} ({e}); requesting all reviewers."
+                ));
+                None
+            }
+        }
+    } else {
+        None
+    };
+    if let Some(paths) = &touched {
+        guidance.diagnostic(format!(
+            "Workspace {workspace} touches {} file(s).",
+            paths.len()
+        ));
+    }
+
     // Resolve and validate reviewer names
-    let reviewer_names = resolve_reviewers(reviewers_override, config, project)?;
+    let reviewer_names =
+        resolve_reviewers(reviewers_override, config, project, touched.as_deref())?;
+    if reviewer_names.is_empty() {
+        guidance.status = ProtocolStatus::Ready;
+        guidance.advise(format!(
+            "No reviewers required for the files this change touches. Proceed to finish: edict protocol finish {bone_id}"
+        ));
+        print_guidance(&guidance, format, recorder)?;
+        return Ok(());
+    }
+    let policy = GatePolicy::from_config(&config.review, project);
 
     // If --review-id was provided, check that existing review
     if let Some(rid) = review_id_flag {
@@ -92,11 +131,14 @@ pub fn execute(
             rid,
             &workspace,
             &reviewer_names,
+            &policy,
+            &SnapshotStore::new(project_root),
             bone_id,
             project,
             agent,
             execute,
             format,
+            recorder,
         );
     }
 
@@ -111,11 +153,14 @@ pub fn execute(
                 &existing.review_id,
                 &workspace,
                 &reviewer_names,
+                &policy,
+                &SnapshotStore::new(project_root),
                 bone_id,
                 project,
                 agent,
                 execute,
                 format,
+                recorder,
             );
         }
         Ok(_) => {
@@ -170,7 +215,7 @@ pub fn execute(
         reviewer_names.join(", ")
     ));
 
-    print_guidance(&guidance, format)?;
+    print_guidance(&guidance, format, recorder)?;
     Ok(())
 }
 
@@ -182,17 +227,20 @@ fn handle_existing_review(
     review_id: &str,
     workspace: &str,
     reviewer_names: &[String],
+    policy: &GatePolicy,
+    snapshots: &SnapshotStore,
     bone_id: &str,
     project: &str,
     agent: &str,
     execute: bool,
     format: OutputFormat,
+    recorder: &Recorder,
 ) -> anyhow::Result<()> {
     let review_detail = match ctx.review_status(review_id, workspace) {
         Ok(r) => r,
         Err(e) => {
             guidance.blocked(format!("could not fetch review {review_id}: {e}"));
-            print_guidance(guidance, format)?;
+            print_guidance(guidance, format, recorder)?;
             return Ok(());
         }
     };
@@ -203,7 +251,15 @@ fn handle_existing_review(
     });
 
     // Evaluate review gate
-    let decision = review_gate::evaluate_review_gate(&review_detail, reviewer_names);
+    let mut decision = review_gate::evaluate_with_policy(&review_detail, reviewer_names, policy);
+    let stale =
+        vote_snapshots::apply_staleness(snapshots, workspace, &review_detail, &mut decision);
+    for d in decision.diagnostics() {
+        guidance.diagnostic(d);
+    }
+    for s in &stale {
+        guidance.diagnostic(vote_snapshots::describe(s));
+    }
 
     match decision.status {
         ReviewGateStatus::Approved => {
@@ -272,6 +328,22 @@ fn handle_existing_review(
                 "Read review feedback, address issues, then re-request review.".to_string(),
             );
         }
+        ReviewGateStatus::NeedsReview if !stale.is_empty() => {
+            // Approved, but the approvals no longer match the workspace
+            guidance.status = ProtocolStatus::NeedsReview;
+            let steps =
+                vote_snapshots::re_review_steps(workspace, review_id, project, agent, &stale);
+            if execute {
+                let report = executor::execute_steps(&steps)?;
+                guidance.executed = true;
+                guidance.execution_report = Some(report);
+            } else {
+                guidance.steps(steps);
+            }
+            guidance.advise(format!(
+                "Review {review_id} approval is stale or unverified. Re-request review of the current changes."
+            ));
+        }
         ReviewGateStatus::NeedsReview => {
             // Still waiting for reviews
             guidance.status = ProtocolStatus::NeedsReview;
@@ -323,7 +395,7 @@ fn handle_existing_review(
         }
     }
 
-    print_guidance(guidance, format)?;
+    print_guidance(guidance, format, recorder)?;
     Ok(())
 }
 
@@ -331,12 +403,14 @@ fn handle_existing_review(
 ///
 /// Reviewers in config are stored as role names (e.g., "security").
 /// These are mapped to `<project>-<role>` (e.g., "edict-security").
+/// Without an override, roles come from path-scoped assignment over `touched`.
 /// The --reviewers flag overrides with literal reviewer names.
 /// All reviewer names are validated against identifier rules.
 fn resolve_reviewers(
     reviewers_override: Option<&str>,
     config: &Config,
     project: &str,
+    touched: Option<&[String]>,
 ) -> anyhow::Result<Vec<String>> {
     let names: Vec<String> = if let Some(override_str) = reviewers_override {
         override_str
@@ -345,12 +419,7 @@ fn resolve_reviewers(
             .filter(|s| !s.is_empty())
             .collect()
     } else {
-        config
-            .review
-            .reviewers
-            .iter()
-            .map(|role| format!("{project}-{role}"))
-            .collect()
+        reviewer_rules::required_reviewers(config, project, touched)
     };
 
     // Validate all reviewer names
@@ -362,11 +431,16 @@ fn resolve_reviewers(
     Ok(names)
 }
 
-/// Render guidance to stdout.
-fn print_guidance(guidance: &ProtocolGuidance, format: OutputFormat) -> anyhow::Result<()> {
+/// Render guidance to stdout, then record it as the agent's last guidance.
+fn print_guidance(
+    guidance: &ProtocolGuidance,
+    format: OutputFormat,
+    recorder: &Recorder,
+) -> anyhow::Result<()> {
     let output = super::render::render(guidance, format)
         .map_err(|e| anyhow::anyhow!("render error: {e}"))?;
     println!("{}", output);
+    recorder.record(guidance);
     Ok(())
 }
 
@@ -387,15 +461,23 @@ mod tests {
                 release_instructions: None,
                 check_command: None,
                 critical_approvers: None,
+                trunk: None,
             },
             tools: Default::default(),
             review: crate::config::ReviewConfig {
                 enabled: true,
                 reviewers: reviewers.into_iter().map(|s| s.to_string()).collect(),
+                roles: Default::default(),
+                rules: Default::default(),
+                critical: Default::default(),
+                policy: Default::default(),
+                checks: Default::default(),
             },
             push_main: false,
             agents: Default::default(),
             models: Default::default(),
+            secrets: Default::default(),
+            hooks: Default::default(),
             env: Default::default(),
         }
     }
@@ -403,35 +485,52 @@ mod tests {
     #[test]
     fn resolve_reviewers_from_config() {
         let config = make_config(vec!["security", "perf"]);
-        let names = resolve_reviewers(None, &config, "edict").unwrap();
+        let names = resolve_reviewers(None, &config, "edict", None).unwrap();
         assert_eq!(names, vec!["edict-security", "edict-perf"]);
     }
 
     #[test]
     fn resolve_reviewers_override() {
         let config = make_config(vec!["security"]);
-        let names = resolve_reviewers(Some("custom-reviewer,another"), &config, "edict").unwrap();
+        let names =
+            resolve_reviewers(Some("custom-reviewer,another"), &config, "edict", None).unwrap();
         assert_eq!(names, vec!["custom-reviewer", "another"]);
     }
 
     #[test]
     fn resolve_reviewers_override_trims_whitespace() {
         let config = make_config(vec![]);
-        let names = resolve_reviewers(Some(" a , b , c "), &config, "proj").unwrap();
+        let names = resolve_reviewers(Some(" a , b , c "), &config, "proj", None).unwrap();
         assert_eq!(names, vec!["a", "b", "c"]);
     }
 
     #[test]
     fn resolve_reviewers_empty_config() {
         let config = make_config(vec![]);
-        let names = resolve_reviewers(None, &config, "edict").unwrap();
+        let names = resolve_reviewers(None, &config, "edict", None).unwrap();
         assert!(names.is_empty());
     }
 
+    #[test]
+    fn resolve_reviewers_path_scoped() {
+        let mut config = make_config(vec!["security"]);
+        config.review.rules.push(crate::config::ReviewRule {
+            paths: vec!["docs/".into()],
+            reviewers: vec!["docs".into()],
+        });
+        let touched = vec!["docs/guide.md".to_string()];
+        let names = resolve_reviewers(None, &config, "edict", Some(&touched)).unwrap();
+        assert_eq!(names, vec!["edict-docs"]);
+
+        let touched = vec!["src/lib.rs".to_string()];
+        let names = resolve_reviewers(None, &config, "edict", Some(&touched)).unwrap();
+        assert_eq!(names, vec!["edict-security"]);
+    }
+
     #[test]
     fn resolve_reviewers_rejects_invalid_names() {
         let config = make_config(vec![]);
-        let result = resolve_reviewers(Some("valid,bad name with spaces"), &config, "proj");
+        let result = resolve_reviewers(Some("valid,bad name with spaces"), &config, "proj", None);
         assert!(result.is_err());
     }
 }
@@ -5,6 +5,7 @@
 //! This prevents inconsistent policy logic across finish/review/resume/status commands.
 
 use super::adapters::{ReviewDetail, ReviewVote};
+use crate::config::{ReviewConfig, RoleGate};
 use std::collections::HashMap;
 
 /// Result of evaluating a review against a gate policy.
@@ -12,17 +13,28 @@ use std::collections::HashMap;
 pub struct ReviewGateDecision {
     /// Status: "approved", "blocked", or "needs-review"
     pub status: ReviewGateStatus,
-    /// Reviewers who haven't voted yet
+    /// Reviewers whose vote is still needed (required reviewers, plus quorum
+    /// members while the quorum is unmet)
     pub missing_approvals: Vec<String>,
     /// Reviewers who blocked after previously approving
     #[allow(dead_code)]
     pub newer_block_after_lgtm: Vec<String>,
     /// Total required reviewers
     pub total_required: usize,
-    /// Total voted lgtm
+    /// Total voted lgtm (veto-only reviewers excluded)
     pub approved_by: Vec<String>,
     /// Total voted block
     pub blocked_by: Vec<String>,
+    /// Reviewers whose LGTM predates newer workspace changes (approval stale)
+    pub stale_approvals: Vec<String>,
+    /// Blocks that no longer count because their threads were resolved
+    pub expired_blocks: Vec<String>,
+    /// Reviewers who must LGTM individually (gate = required)
+    pub required_approvers: Vec<String>,
+    /// LGTMs needed from non-veto reviewers
+    pub quorum: usize,
+    /// Policy clauses that failed, e.g. "quorum: 1 of 2 LGTMs"
+    pub failed_clauses: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -41,68 +53,177 @@ impl ReviewGateDecision {
         match self.status {
             ReviewGateStatus::Approved => "approved",
             ReviewGateStatus::Blocked => "blocked",
+            ReviewGateStatus::NeedsReview if !self.stale_approvals.is_empty() => "approval-stale",
             ReviewGateStatus::NeedsReview => "needs-review",
         }
     }
+
+    /// Invalidate a reviewer's LGTM because the workspace changed after they approved.
+    ///
+    /// The approval no longer counts. An approved review needs review again if the
+    /// reviewer was individually required or the quorum is no longer met.
+    pub fn mark_stale(&mut self, reviewer: &str) {
+        let Some(pos) = self.approved_by.iter().position(|r| r == reviewer) else {
+            return;
+        };
+        self.approved_by.remove(pos);
+        self.stale_approvals.push(reviewer.to_string());
+        let required = self.required_approvers.iter().any(|r| r == reviewer);
+        if required || self.approved_by.len() < self.quorum {
+            self.failed_clauses.push(format!(
+                "stale: {reviewer} approved an older workspace state"
+            ));
+            if self.status == ReviewGateStatus::Approved {
+                self.status = ReviewGateStatus::NeedsReview;
+            }
+        }
+    }
+
+    /// Diagnostics explaining the decision: failed policy clauses and expired blocks.
+    pub fn diagnostics(&self) -> Vec<String> {
+        let mut out: Vec<String> = self
+            .failed_clauses
+            .iter()
+            .map(|c| format!("Review policy: {c}"))
+            .collect();
+        for reviewer in &self.expired_blocks {
+            out.push(format!(
+                "Block by {reviewer} expired: all of its threads are resolved"
+            ));
+        }
+        out
+    }
+}
+
+/// How required reviewers' votes combine into a decision (`[review.policy]` and
+/// `[review.roles.<name>] gate`).
+#[derive(Debug, Clone, Default)]
+pub struct GatePolicy {
+    /// LGTMs needed from non-veto reviewers. `None`: every reviewer is required.
+    pub quorum: Option<usize>,
+    /// A BLOCK stops counting once every thread its reviewer opened is resolved.
+    pub block_expires_on_resolve: bool,
+    /// Explicit gates by reviewer agent name (`<prefix>-<role>`).
+    pub gates: HashMap<String, RoleGate>,
 }
 
-/// Evaluate a review against required reviewers.
+impl GatePolicy {
+    /// Build the policy for reviewers named `<prefix>-<role>`.
+    pub fn from_config(review: &ReviewConfig, prefix: &str) -> Self {
+        let gates = review
+            .roles
+            .iter()
+            .filter_map(|(role, cfg)| Some((format!("{prefix}-{role}"), cfg.gate?)))
+            .collect();
+        Self {
+            quorum: review.policy.quorum,
+            block_expires_on_resolve: review.policy.block_expires_on_resolve,
+            gates,
+        }
+    }
+
+    /// The gate for a reviewer: explicit, else required (or optional under a quorum).
+    pub fn gate(&self, reviewer: &str) -> RoleGate {
+        match self.gates.get(reviewer) {
+            Some(gate) => *gate,
+            None if self.quorum.is_some() => RoleGate::Optional,
+            None => RoleGate::Required,
+        }
+    }
+}
+
+/// Latest vote per reviewer (by voted_at timestamp).
+pub fn latest_votes(review: &ReviewDetail) -> HashMap<String, &ReviewVote> {
+    let mut latest_votes: HashMap<String, &ReviewVote> = HashMap::new();
+    for vote in &review.votes {
+        latest_votes
+            .entry(vote.reviewer.clone())
+            .and_modify(|existing| {
+                // Keep the vote with the later timestamp
+                // Lexicographic string comparison works correctly for ISO 8601/RFC3339 timestamps
+                if let (Some(existing_voted_at), Some(new_voted_at)) =
+                    (&existing.voted_at, &vote.voted_at)
+                {
+                    if new_voted_at > existing_voted_at {
+                        *existing = vote;
+                    }
+                }
+            })
+            .or_insert(vote);
+    }
+    latest_votes
+}
+
+/// Whether every thread `reviewer` opened is resolved (false if they opened none).
+fn threads_resolved(review: &ReviewDetail, reviewer: &str) -> bool {
+    let mut opened = review
+        .threads
+        .iter()
+        .filter(|t| t.comments.first().is_some_and(|c| c.author == reviewer))
+        .peekable();
+    opened.peek().is_some() && opened.all(|t| t.resolved)
+}
+
+/// Evaluate a review against required reviewers under a gate policy.
 ///
 /// Returns the canonical gate decision and diagnostics.
 ///
 /// Logic:
-/// 1. If no votes and required reviewers not empty → NeedsReview
-/// 2. For each required reviewer:
-///    - Track their latest vote (by voted_at timestamp)
-///    - If no vote → add to missing_approvals
-/// 3. If any required reviewer's latest vote is "block" → Blocked, add to newer_block_after_lgtm
-/// 4. If all required reviewers have voted lgtm and no blocks → Approved
-/// 5. Otherwise → NeedsReview
-pub fn evaluate_review_gate(
+/// 1. Track each reviewer's latest vote (by voted_at timestamp)
+/// 2. If a required-gate reviewer hasn't voted (or nobody has) → NeedsReview
+/// 3. If any reviewer's latest vote is "block" (and hasn't expired) → Blocked;
+///    blocks after an earlier LGTM are also listed in newer_block_after_lgtm
+/// 4. If a required-gate reviewer's latest vote isn't lgtm → NeedsReview
+/// 5. If fewer than `quorum` non-veto reviewers voted lgtm → NeedsReview
+/// 6. Otherwise → Approved
+pub fn evaluate_with_policy(
     review: &ReviewDetail,
     required_reviewers: &[String],
+    policy: &GatePolicy,
 ) -> ReviewGateDecision {
     let mut approved_by = Vec::new();
     let mut blocked_by = Vec::new();
-    let mut latest_votes: HashMap<String, &ReviewVote> = HashMap::new();
+    let mut expired_blocks = Vec::new();
 
     // Build a map of latest vote per reviewer
     // Track both latest vote and whether they previously LGTM'd
+    let latest_votes = latest_votes(review);
     let mut previous_lgtm: HashMap<String, bool> = HashMap::new();
 
     for vote in &review.votes {
-        let reviewer_key = vote.reviewer.clone();
-
         // Track if this reviewer LGTM'd at some point
         if vote.is_lgtm() {
-            previous_lgtm.insert(reviewer_key.clone(), true);
+            previous_lgtm.insert(vote.reviewer.clone(), true);
         }
-
-        latest_votes
-            .entry(reviewer_key)
-            .and_modify(|existing| {
-                // Keep the vote with the later timestamp
-                // Lexicographic string comparison works correctly for ISO 8601/RFC3339 timestamps
-                if let (Some(existing_voted_at), Some(new_voted_at)) =
-                    (&existing.voted_at, &vote.voted_at)
-                {
-                    if new_voted_at > existing_voted_at {
-                        *existing = vote;
-                    }
-                }
-            })
-            .or_insert(vote);
     }
 
-    let mut missing_approvals = Vec::new();
+    let mut missing_required = Vec::new();
+    let mut missing_optional = Vec::new();
     let mut newer_block_after_lgtm = Vec::new();
+    let mut required_approvers = Vec::new();
+    let mut voters = 0;
 
     for required in required_reviewers {
+        let gate = policy.gate(required);
+        match gate {
+            RoleGate::Required => {
+                required_approvers.push(required.clone());
+                voters += 1;
+            }
+            RoleGate::Optional => voters += 1,
+            RoleGate::Veto => {}
+        }
         match latest_votes.get(required) {
             Some(vote) => {
                 if vote.is_lgtm() {
-                    approved_by.push(required.clone());
+                    if gate != RoleGate::Veto {
+                        approved_by.push(required.clone());
+                    }
                 } else if vote.is_block() {
+                    if policy.block_expires_on_resolve && threads_resolved(review, required) {
+                        expired_blocks.push(required.clone());
+                        continue;
+                    }
                     blocked_by.push(required.clone());
                     // Only add to newer_block_after_lgtm if they previously LGTM'd
                     if previous_lgtm.get(required).copied().unwrap_or(false) {
@@ -110,13 +231,66 @@ pub fn evaluate_review_gate(
                     }
                 }
             }
-            None => {
-                missing_approvals.push(required.clone());
-            }
+            None => match gate {
+                RoleGate::Required => missing_required.push(required.clone()),
+                RoleGate::Optional => missing_optional.push(required.clone()),
+                RoleGate::Veto => {}
+            },
         }
     }
 
-    let status = if !missing_approvals.is_empty()
+    // Reviewers were requested, so at least one LGTM is needed, even when every
+    // role is veto-only
+    let quorum = if required_reviewers.is_empty() {
+        0
+    } else {
+        policy
+            .quorum
+            .map_or(required_approvers.len(), |q| q.min(voters))
+            .max(1)
+    };
+    let unapproved: Vec<&String> = required_approvers
+        .iter()
+        .filter(|r| !approved_by.contains(r) && !missing_required.contains(r))
+        .collect();
+    let quorum_met = approved_by.len() >= quorum;
+
+    let mut failed_clauses = Vec::new();
+    if required_reviewers.is_empty() && review.votes.is_empty() {
+        failed_clauses.push("no reviewers assigned and no votes cast".to_string());
+    }
+    if !missing_required.is_empty() {
+        failed_clauses.push(format!(
+            "required: awaiting votes from {}",
+            missing_required.join(", ")
+        ));
+    }
+    for reviewer in &blocked_by {
+        let kind = match policy.gate(reviewer) {
+            RoleGate::Veto => "veto",
+            _ => "block",
+        };
+        failed_clauses.push(format!("{kind}: {reviewer} blocked"));
+    }
+    if !unapproved.is_empty() {
+        let names: Vec<&str> = unapproved.iter().map(|s| s.as_str()).collect();
+        failed_clauses.push(format!(
+            "required: {} must LGTM (latest vote is not an approval)",
+            names.join(", ")
+        ));
+    }
+    if !quorum_met {
+        let mut clause = format!("quorum: {} of {quorum} LGTMs", approved_by.len());
+        if !missing_optional.is_empty() {
+            clause.push_str(&format!(
+                " (not yet voted: {})",
+                missing_optional.join(", ")
+            ));
+        }
+        failed_clauses.push(clause);
+    }
+
+    let status = if !missing_required.is_empty()
         || (required_reviewers.is_empty() && review.votes.is_empty())
     {
         ReviewGateStatus::NeedsReview
@@ -124,12 +298,17 @@ pub fn evaluate_review_gate(
         ReviewGateStatus::Blocked
     } else if !blocked_by.is_empty() {
         ReviewGateStatus::Blocked
-    } else if approved_by.len() == required_reviewers.len() {
+    } else if unapproved.is_empty() && quorum_met {
         ReviewGateStatus::Approved
     } else {
         ReviewGateStatus::NeedsReview
     };
 
+    let mut missing_approvals = missing_required;
+    if !quorum_met {
+        missing_approvals.extend(missing_optional);
+    }
+
     ReviewGateDecision {
         status,
         missing_approvals,
@@ -137,18 +316,30 @@ pub fn evaluate_review_gate(
         total_required: required_reviewers.len(),
         approved_by,
         blocked_by,
+        stale_approvals: Vec::new(),
+        expired_blocks,
+        required_approvers,
+        quorum,
+        failed_clauses,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::protocol::adapters::{ReviewComment, ReviewThread};
+
+    /// Default policy: every required reviewer must LGTM.
+    fn evaluate_review_gate(review: &ReviewDetail, required: &[String]) -> ReviewGateDecision {
+        evaluate_with_policy(review, required, &GatePolicy::default())
+    }
 
     fn make_vote(reviewer: &str, vote: &str, voted_at: &str) -> ReviewVote {
         ReviewVote {
             reviewer: reviewer.to_string(),
             vote: vote.to_string(),
             voted_at: Some(voted_at.to_string()),
+            commit_id: None,
         }
     }
 
@@ -160,6 +351,7 @@ mod tests {
             change_id: None,
             votes,
             open_thread_count: 0,
+            threads: Vec::new(),
         }
     }
 
@@ -305,4 +497,229 @@ mod tests {
         assert_eq!(decision.approved_by, vec!["edict-security"]);
         assert_eq!(decision.blocked_by.len(), 0);
     }
+
+    #[test]
+    fn test_mark_stale_revokes_approval() {
+        let review = make_review(vec![
+            make_vote("edict-security", "lgtm", "2026-02-16T10:00:00Z"),
+            make_vote("edict-perf", "lgtm", "2026-02-16T10:05:00Z"),
+        ]);
+        let required = vec!["edict-security".to_string(), "edict-perf".to_string()];
+
+        let mut decision = evaluate_review_gate(&review, &required);
+        assert_eq!(decision.status, ReviewGateStatus::Approved);
+
+        decision.mark_stale("edict-security");
+        assert_eq!(decision.status, ReviewGateStatus::NeedsReview);
+        assert_eq!(decision.status_str(), "approval-stale");
+        assert_eq!(decision.approved_by, vec!["edict-perf"]);
+        assert_eq!(decision.stale_approvals, vec!["edict-security"]);
+
+        // Reviewers who didn't approve can't go stale
+        decision.mark_stale("edict-other");
+        assert_eq!(decision.stale_approvals, vec!["edict-security"]);
+    }
+
+    fn names(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn thread(author: &str, resolved: bool) -> ReviewThread {
+        ReviewThread {
+            thread_id: format!("th-{author}"),
+            resolved,
+            comments: vec![ReviewComment {
+                author: author.to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_quorum_ignores_slow_reviewer() {
+        let review = make_review(vec![
+            make_vote("edict-security", "lgtm", "2026-02-16T10:00:00Z"),
+            make_vote("edict-perf", "lgtm", "2026-02-16T10:05:00Z"),
+        ]);
+        let required = names(&["edict-security", "edict-perf", "edict-docs"]);
+        let policy = GatePolicy {
+            quorum: Some(2),
+            ..Default::default()
+        };
+
+        let decision = evaluate_with_policy(&review, &required, &policy);
+        assert_eq!(decision.status, ReviewGateStatus::Approved);
+        assert!(decision.failed_clauses.is_empty());
+
+        let one = make_review(vec![make_vote(
+            "edict-security",
+            "lgtm",
+            "2026-02-16T10:00:00Z",
+        )]);
+        let decision = evaluate_with_policy(&one, &required, &policy);
+        assert_eq!(decision.status, ReviewGateStatus::NeedsReview);
+        assert_eq!(
+            decision.missing_approvals,
+            names(&["edict-perf", "edict-docs"])
+        );
+        assert_eq!(
+            decision.failed_clauses,
+            vec!["quorum: 1 of 2 LGTMs (not yet voted: edict-perf, edict-docs)"]
+        );
+    }
+
+    #[test]
+    fn test_required_role_under_quorum() {
+        let review = make_review(vec![
+            make_vote("edict-perf", "lgtm", "2026-02-16T10:00:00Z"),
+            make_vote("edict-docs", "lgtm", "2026-02-16T10:05:00Z"),
+        ]);
+        let required = names(&["edict-security", "edict-perf", "edict-docs"]);
+        let mut policy = GatePolicy {
+            quorum: Some(2),
+            ..Default::default()
+        };
+        policy
+            .gates
+            .insert("edict-security".to_string(), RoleGate::Required);
+
+        let decision = evaluate_with_policy(&review, &required, &policy);
+        assert_eq!(decision.status, ReviewGateStatus::NeedsReview);
+        assert_eq!(decision.missing_approvals, vec!["edict-security"]);
+        assert_eq!(
+            decision.diagnostics(),
+            vec!["Review policy: required: awaiting votes from edict-security"]
+        );
+    }
+
+    #[test]
+    fn test_veto_role_blocks_but_cannot_approve() {
+        let required = names(&["edict-security", "edict-perf"]);
+        let mut policy = GatePolicy::default();
+        policy
+            .gates
+            .insert("edict-security".to_string(), RoleGate::Veto);
+
+        // Veto reviewer hasn't voted: doesn't hold the gate
+        let review = make_review(vec![make_vote(
+            "edict-perf",
+            "lgtm",
+            "2026-02-16T10:00:00Z",
+        )]);
+        let decision = evaluate_with_policy(&review, &required, &policy);
+        assert_eq!(decision.status, ReviewGateStatus::Approved);
+
+        let review = make_review(vec![
+            make_vote("edict-perf", "lgtm", "2026-02-16T10:00:00Z"),
+            make_vote("edict-security", "block", "2026-02-16T10:05:00Z"),
+        ]);
+        let decision = evaluate_with_policy(&review, &required, &policy);
+        assert_eq!(decision.status, ReviewGateStatus::Blocked);
+        assert_eq!(
+            decision.failed_clauses,
+            vec!["veto: edict-security blocked"]
+        );
+
+        // A veto LGTM is not an approval
+        let review = make_review(vec![make_vote(
+            "edict-security",
+            "lgtm",
+            "2026-02-16T10:00:00Z",
+        )]);
+        let decision = evaluate_with_policy(&review, &required, &policy);
+        assert_eq!(decision.status, ReviewGateStatus::NeedsReview);
+        assert!(decision.approved_by.is_empty());
+    }
+
+    #[test]
+    fn test_all_veto_roles_still_need_an_approval() {
+        let required = names(&["edict-security"]);
+        let mut policy = GatePolicy {
+            quorum: Some(1),
+            ..Default::default()
+        };
+        policy
+            .gates
+            .insert("edict-security".to_string(), RoleGate::Veto);
+
+        let review = make_review(vec![make_vote(
+            "edict-security",
+            "lgtm",
+            "2026-02-16T10:00:00Z",
+        )]);
+        let decision = evaluate_with_policy(&review, &required, &policy);
+        assert_eq!(decision.status, ReviewGateStatus::NeedsReview);
+        assert_eq!(decision.quorum, 1);
+        assert_eq!(decision.failed_clauses, vec!["quorum: 0 of 1 LGTMs"]);
+    }
+
+    #[test]
+    fn test_block_expires_when_threads_resolved() {
+        let mut review = make_review(vec![
+            make_vote("edict-security", "lgtm", "2026-02-16T10:00:00Z"),
+            make_vote("edict-docs", "block", "2026-02-16T10:05:00Z"),
+        ]);
+        review.threads = vec![thread("edict-docs", true), thread("edict-security", false)];
+        let required = names(&["edict-security", "edict-docs"]);
+        let mut policy = GatePolicy {
+            block_expires_on_resolve: true,
+            ..Default::default()
+        };
+        policy
+            .gates
+            .insert("edict-docs".to_string(), RoleGate::Optional);
+
+        let decision = evaluate_with_policy(&review, &required, &policy);
+        assert_eq!(decision.status, ReviewGateStatus::Approved);
+        assert_eq!(decision.expired_blocks, vec!["edict-docs"]);
+
+        // An unresolved thread keeps the block
+        review.threads.push(thread("edict-docs", false));
+        let decision = evaluate_with_policy(&review, &required, &policy);
+        assert_eq!(decision.status, ReviewGateStatus::Blocked);
+
+        // Without the policy the block always counts
+        review.threads.pop();
+        let decision = evaluate_with_policy(&review, &required, &GatePolicy::default());
+        assert_eq!(decision.status, ReviewGateStatus::Blocked);
+    }
+
+    #[test]
+    fn test_stale_approval_under_quorum() {
+        let review = make_review(vec![
+            make_vote("edict-security", "lgtm", "2026-02-16T10:00:00Z"),
+            make_vote("edict-perf", "lgtm", "2026-02-16T10:05:00Z"),
+            make_vote("edict-docs", "lgtm", "2026-02-16T10:10:00Z"),
+        ]);
+        let required = names(&["edict-security", "edict-perf", "edict-docs"]);
+        let policy = GatePolicy {
+            quorum: Some(2),
+            ..Default::default()
+        };
+        let mut decision = evaluate_with_policy(&review, &required, &policy);
+
+        // Quorum still met after one stale approval
+        decision.mark_stale("edict-docs");
+        assert_eq!(decision.status, ReviewGateStatus::Approved);
+
+        decision.mark_stale("edict-perf");
+        assert_eq!(decision.status, ReviewGateStatus::NeedsReview);
+    }
+
+    #[test]
+    fn test_policy_from_config() {
+        let config = crate::config::Config::parse_toml(
+            "version = \"1\"\n[project]\nname = \"edict\"\n[review]\nenabled = true\nreviewers = [\"security\"]\n[review.policy]\nquorum = 1\n[review.roles.docs]\ngate = \"veto\"\n",
+        )
+        .unwrap();
+        let policy = GatePolicy::from_config(&config.review, "edict");
+        assert_eq!(policy.quorum, Some(1));
+        assert_eq!(policy.gate("edict-docs"), RoleGate::Veto);
+        assert_eq!(policy.gate("edict-security"), RoleGate::Optional);
+        assert_eq!(
+            GatePolicy::default().gate("edict-security"),
+            RoleGate::Required
+        );
+    }
 }
@@ -0,0 +1,263 @@
+//! Path-scoped reviewer assignment.
+//!
+//! Maps the files a workspace touches to the reviewer roles that must approve it,
+//! using `[[review.rules]]` (CODEOWNERS-style, last match wins) and the `paths`
+//! scope of `[review.roles.<name>]`. Shared by review/finish/merge/resume so the
+//! reviewers requested and the reviewers the gate requires are always the same set.
+
+use globset::{GlobBuilder, GlobMatcher};
+
+use crate::config::{Config, ReviewConfig};
+
+/// A compiled CODEOWNERS-style pattern.
+struct PathPattern {
+    matcher: GlobMatcher,
+}
+
+impl PathPattern {
+    /// Compile a pattern. `/` anchors to the repo root, a trailing `/` matches
+    /// everything under a directory, and a pattern without `/` matches at any depth.
+    fn compile(pattern: &str) -> Option<Self> {
+        let trimmed = pattern.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+        let anchored = trimmed.starts_with('/');
+        let mut glob = trimmed.trim_start_matches('/').to_string();
+        if glob.ends_with('/') {
+            glob.push_str("**");
+        }
+        if !anchored && !glob.contains('/') {
+            glob = format!("**/{glob}");
+        }
+        match GlobBuilder::new(&glob).literal_separator(true).build() {
+            Ok(g) => Some(Self {
+                matcher: g.compile_matcher(),
+            }),
+            Err(e) => {
+                tracing::warn!(pattern, error = %e, "ignoring invalid review path pattern");
+                None
+            }
+        }
+    }
+
+    /// Match a file, or any directory containing it (so `docs` covers `docs/a.md`).
+    fn matches(&self, path: &str) -> bool {
+        let path = path.trim_start_matches("./");
+        if self.matcher.is_match(path) {
+            return true;
+        }
+        path.match_indices('/')
+            .any(|(i, _)| self.matcher.is_match(&path[..i]))
+    }
+}
+
+fn compile_all(patterns: &[String]) -> Vec<PathPattern> {
+    patterns
+        .iter()
+        .filter_map(|p| PathPattern::compile(p))
+        .collect()
+}
+
+fn push_unique(roles: &mut Vec<String>, role: &str) {
+    if !roles.iter().any(|r| r == role) {
+        roles.push(role.to_string());
+    }
+}
+
+/// Reviewer roles required for a change touching `touched` paths.
+///
+/// - `None` (diff unavailable) or an empty diff: every active role — the conservative choice.
+/// - Otherwise each file takes the reviewers of the last `[[review.rules]]` entry it
+///   matches, or the default set (`reviewers` plus unscoped roles) when none match.
+///   Scoped roles (`[review.roles.<name>] paths = [...]`) are added when any file
+///   falls in their scope.
+pub fn required_roles(review: &ReviewConfig, touched: Option<&[String]>) -> Vec<String> {
+    let touched = match touched {
+        Some(paths) if !paths.is_empty() => paths,
+        _ => return review.all_roles(),
+    };
+
+    let mut defaults = review.reviewers.clone();
+    for (name, role) in &review.roles {
+        if role.paths.is_empty() {
+            push_unique(&mut defaults, name);
+        }
+    }
+
+    let rules: Vec<(Vec<PathPattern>, &[String])> = review
+        .rules
+        .iter()
+        .map(|rule| (compile_all(&rule.paths), rule.reviewers.as_slice()))
+        .collect();
+
+    let mut roles = Vec::new();
+    for file in touched {
+        let owners = rules
+            .iter()
+            .rev()
+            .find(|(patterns, _)| patterns.iter().any(|p| p.matches(file)))
+            .map(|(_, reviewers)| *reviewers)
+            .unwrap_or(&defaults);
+        for role in owners {
+            push_unique(&mut roles, role);
+        }
+    }
+
+    for (name, role) in &review.roles {
+        if role.paths.is_empty() {
+            continue;
+        }
+        let scope = compile_all(&role.paths);
+        if touched
+            .iter()
+            .any(|file| scope.iter().any(|p| p.matches(file)))
+        {
+            push_unique(&mut roles, name);
+        }
+    }
+
+    roles
+}
+
+/// The files in `touched` matched by any of the CODEOWNERS-style `patterns`.
+pub fn matching_paths(patterns: &[String], touched: &[String]) -> Vec<String> {
+    let compiled = compile_all(patterns);
+    touched
+        .iter()
+        .filter(|file| compiled.iter().any(|p| p.matches(file)))
+        .cloned()
+        .collect()
+}
+
+/// Required reviewer agent names (`<prefix>-<role>`) for a change touching `touched` paths.
+pub fn required_reviewers(
+    config: &Config,
+    prefix: &str,
+    touched: Option<&[String]>,
+) -> Vec<String> {
+    required_roles(&config.review, touched)
+        .iter()
+        .map(|role| format!("{prefix}-{role}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ReviewRoleConfig, ReviewRule};
+
+    fn paths(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn review_config() -> ReviewConfig {
+        let mut review = ReviewConfig {
+            enabled: true,
+            reviewers: paths(&["security"]),
+            ..Default::default()
+        };
+        review.rules = vec![
+            ReviewRule {
+                paths: paths(&["*.md", "docs/"]),
+                reviewers: paths(&["docs"]),
+            },
+            ReviewRule {
+                paths: paths(&["/migrations/**"]),
+                reviewers: paths(&["security", "db"]),
+            },
+            ReviewRule {
+                paths: paths(&["Cargo.lock"]),
+                reviewers: vec![],
+            },
+        ];
+        review.roles.insert(
+            "perf".to_string(),
+            ReviewRoleConfig {
+                paths: paths(&["src/engine/"]),
+                ..Default::default()
+            },
+        );
+        review
+    }
+
+    #[test]
+    fn unknown_or_empty_diff_requires_all_roles() {
+        let review = review_config();
+        let all = vec!["security", "perf", "docs", "db"];
+        assert_eq!(required_roles(&review, None), all);
+        assert_eq!(required_roles(&review, Some(&[])), all);
+        assert_eq!(review.all_roles(), all);
+    }
+
+    #[test]
+    fn docs_only_change_gets_docs_reviewer() {
+        let review = review_config();
+        let touched = paths(&["README.md", "docs/guide/intro.txt"]);
+        assert_eq!(required_roles(&review, Some(&touched)), vec!["docs"]);
+    }
+
+    #[test]
+    fn unmatched_files_get_default_reviewers() {
+        let review = review_config();
+        let touched = paths(&["src/main.rs", "README.md"]);
+        assert_eq!(
+            required_roles(&review, Some(&touched)),
+            vec!["security", "docs"]
+        );
+    }
+
+    #[test]
+    fn anchored_rule_only_matches_root() {
+        let review = review_config();
+        let touched = paths(&["migrations/001_init.sql"]);
+        assert_eq!(
+            required_roles(&review, Some(&touched)),
+            vec!["security", "db"]
+        );
+
+        let nested = paths(&["tools/migrations/x.sql"]);
+        assert_eq!(required_roles(&review, Some(&nested)), vec!["security"]);
+    }
+
+    #[test]
+    fn rule_with_no_reviewers_needs_no_review() {
+        let review = review_config();
+        let touched = paths(&["Cargo.lock"]);
+        assert!(required_roles(&review, Some(&touched)).is_empty());
+    }
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let mut review = review_config();
+        review.rules.push(ReviewRule {
+            paths: paths(&["docs/api/**"]),
+            reviewers: paths(&["api-compat"]),
+        });
+        let touched = paths(&["docs/api/v1.md"]);
+        assert_eq!(required_roles(&review, Some(&touched)), vec!["api-compat"]);
+    }
+
+    #[test]
+    fn scoped_role_added_when_scope_touched() {
+        let review = review_config();
+        let touched = paths(&["src/engine/core.rs"]);
+        assert_eq!(
+            required_roles(&review, Some(&touched)),
+            vec!["security", "perf"]
+        );
+    }
+
+    #[test]
+    fn required_reviewers_prefixes_project() {
+        let mut config =
+            crate::config::Config::parse_toml("version = \"1\"\n[project]\nname = \"edict\"\n")
+                .unwrap();
+        config.review = review_config();
+        let touched = paths(&["docs/x.md"]);
+        assert_eq!(
+            required_reviewers(&config, "edict", Some(&touched)),
+            vec!["edict-docs"]
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Secret gate for finish and merge.
+//!
+//! Scans the workspace diff against the trunk (`project.trunk`, else `origin/HEAD`,
+//! else main or master; see [`workspace_diff::trunk_branch`]) with the built-in
+//! secret detector before any merge steps are emitted. The gate is on by default.
+//! Findings or an invalid `[secrets]` allowlist block: a leaked key is far more
+//! expensive than a retry. A diff that cannot be read only warns unless
+//! `[secrets] gate = true` is set explicitly, so a project whose diff can't be
+//! computed isn't stuck after upgrading. `--force` does not bypass the gate; false
+//! positives go in the `[secrets]` allowlist or get an inline `edict:allow-secret`
+//! marker. `[secrets] gate = false` turns the gate off.
+
+use super::render::ProtocolGuidance;
+use crate::commands::secret_scan::{self, Scanner, SecretFinding};
+use crate::commands::workspace_diff;
+use crate::config::SecretsConfig;
+
+/// Findings listed individually before summarizing the rest.
+const MAX_DIAGNOSTICS: usize = 20;
+
+/// Run the gate. Returns whether the command may proceed.
+pub fn enforce(
+    guidance: &mut ProtocolGuidance,
+    config: &SecretsConfig,
+    workspace: &str,
+    trunk: Option<&str>,
+) -> bool {
+    if !config.gate_enabled() {
+        return true;
+    }
+    let scanner = match Scanner::new(config) {
+        Ok(scanner) => scanner,
+        Err(e) => return apply(guidance, workspace, Err(e)),
+    };
+    match workspace_diff::workspace_diff(workspace, trunk) {
+        Ok(files) => apply(guidance, workspace, Ok(scanner.scan(&files))),
+        Err(e) if config.gate.is_none() => {
+            warn_unscanned(guidance, workspace, &e);
+            true
+        }
+        Err(e) => apply(guidance, workspace, Err(e)),
+    }
+}
+
+/// A diff error with the gate left at its default: proceed, but say the scan didn't run.
+fn warn_unscanned(guidance: &mut ProtocolGuidance, workspace: &str, error: &anyhow::Error) {
+    guidance.diagnostic(format!(
+        "WARNING: could not scan workspace {workspace} for secrets, so the secret gate was \
+         skipped: {error}. Set `[secrets] gate = true` in .edict.toml to block instead."
+    ));
+}
+
+fn apply(
+    guidance: &mut ProtocolGuidance,
+    workspace: &str,
+    findings: anyhow::Result<Vec<SecretFinding>>,
+) -> bool {
+    let findings = match findings {
+        Ok(findings) if findings.is_empty() => return true,
+        Ok(findings) => findings,
+        Err(e) => {
+            guidance.blocked(format!(
+                "could not scan workspace {workspace} for secrets: {e}"
+            ));
+            guidance.advise(
+                "Fix the error above, or set `[secrets] gate = false` in .edict.toml to skip the secret scan."
+                    .to_string(),
+            );
+            return false;
+        }
+    };
+
+    guidance.blocked(format!(
+        "{} possible secret(s) in workspace {workspace}. --force does not bypass this.",
+        findings.len()
+    ));
+    for finding in findings.iter().take(MAX_DIAGNOSTICS) {
+        guidance.diagnostic(finding.describe());
+    }
+    if findings.len() > MAX_DIAGNOSTICS {
+        guidance.diagnostic(format!("... and {} more", findings.len() - MAX_DIAGNOSTICS));
+    }
+    guidance.advise(format!(
+        "Remove the secrets from the workspace (and rotate any that are real), then re-run. \
+         For false positives, add the path to `[secrets] allow_paths` or a regex to \
+         `[secrets] allow_patterns` in .edict.toml, or append `{}` to the line.",
+        secret_scan::ALLOW_MARKER
+    ));
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::protocol::render::ProtocolStatus;
+
+    #[test]
+    fn clean_diff_proceeds() {
+        let mut guidance = ProtocolGuidance::new("merge");
+        assert!(apply(&mut guidance, "frost-castle", Ok(Vec::new())));
+        assert!(guidance.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn findings_block_with_locations() {
+        let mut guidance = ProtocolGuidance::new("merge");
+        let findings = vec![
+            SecretFinding {
+                path: "src/aws.rs".to_string(),
+                line: Some(12),
+                kind: "AWS access key ID",
+            },
+            SecretFinding {
+                path: ".env".to_string(),
+                line: None,
+                kind: "dotenv file",
+            },
+        ];
+        assert!(!apply(&mut guidance, "frost-castle", Ok(findings)));
+        assert_eq!(guidance.status, ProtocolStatus::Blocked);
+        assert_eq!(
+            guidance.diagnostics[1..],
+            [
+                "src/aws.rs:12: possible AWS access key ID",
+                ".env: possible dotenv file"
+            ]
+        );
+        assert!(guidance.steps.is_empty());
+    }
+
+    #[test]
+    fn scan_errors_fail_closed() {
+        let mut guidance = ProtocolGuidance::new("finish");
+        assert!(!apply(
+            &mut guidance,
+            "frost-castle",
+            Err(anyhow::anyhow!("merge-base failed"))
+        ));
+        assert_eq!(guidance.status, ProtocolStatus::Blocked);
+    }
+
+    #[test]
+    fn unreadable_diff_warns_unless_gate_is_explicit() {
+        let mut guidance = ProtocolGuidance::new("finish");
+        assert!(enforce(
+            &mut guidance,
+            &SecretsConfig::default(),
+            "no-such-workspace",
+            None
+        ));
+        assert_eq!(guidance.status, ProtocolStatus::Ready);
+        assert!(guidance.diagnostics[0].starts_with("WARNING: could not scan workspace"));
+
+        let explicit = SecretsConfig {
+            gate: Some(true),
+            ..Default::default()
+        };
+        let mut guidance = ProtocolGuidance::new("finish");
+        assert!(!enforce(
+            &mut guidance,
+            &explicit,
+            "no-such-workspace",
+            None
+        ));
+        assert_eq!(guidance.status, ProtocolStatus::Blocked);
+    }
+
+    #[test]
+    fn disabled_gate_skips_scan() {
+        let mut guidance = ProtocolGuidance::new("merge");
+        let config = SecretsConfig {
+            gate: Some(false),
+            ..Default::default()
+        };
+        assert!(enforce(&mut guidance, &config, "no-such-workspace", None));
+    }
+}
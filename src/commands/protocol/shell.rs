@@ -332,7 +332,6 @@ pub fn bn_do_cmd(bone_id: &str) -> String {
 }
 
 /// Build: `maw exec default -- bn bone comment add <id> '<message>'`
-#[allow(dead_code)]
 pub fn bn_comment_cmd(bone_id: &str, message: &str) -> String {
     // Validate bone_id before use
     let bone_id_safe = if validate_bone_id(bone_id).is_ok() {
@@ -0,0 +1,771 @@
+//! Review vote snapshots: which workspace state each vote was cast against.
+//!
+//! Seal records when a reviewer voted but not what they saw. Without that, an LGTM
+//! stays valid forever and a worker can push new code after approval. The reviewer
+//! loop records a snapshot of the workspace before each review. Votes cast outside
+//! the loop (human approvers, reviewers named with `--reviewers`) get a snapshot the
+//! first time a gate sees them, unless something was committed after the vote. The
+//! gate compares the workspace against the snapshot and reports the approval stale
+//! when it moved.
+//!
+//! A snapshot is `git stash create` (HEAD plus uncommitted changes, without touching
+//! the working tree), or HEAD when the workspace is clean, plus the blob ID of every
+//! untracked file, since neither of those commits contains untracked files.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::adapters::{self, ReviewDetail, ReviewVote};
+use super::review_gate::{self, ReviewGateDecision};
+use crate::commands::{journal, workspace_diff};
+use crate::subprocess::Tool;
+
+/// Snapshot log file name in the project cache dir.
+pub const SNAPSHOTS_FILE: &str = "review-snapshots.jsonl";
+
+/// Where a snapshot came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotSource {
+    /// Recorded by the reviewer loop before the reviewer read the diff.
+    Reviewer,
+    /// Recorded by a gate the first time it saw a vote with no reviewer snapshot.
+    Observed,
+}
+
+/// A workspace's content: a commit for tracked files plus untracked file blobs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceState {
+    pub commit: String,
+    /// Untracked file path → blob ID.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub untracked: BTreeMap<String, String>,
+}
+
+/// The workspace state a reviewer saw for a review.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoteSnapshot {
+    pub review_id: String,
+    pub reviewer: String,
+    #[serde(flatten)]
+    pub state: WorkspaceState,
+    /// RFC 3339 timestamp
+    pub recorded_at: String,
+    pub source: SnapshotSource,
+}
+
+impl VoteSnapshot {
+    fn recorded(&self) -> Option<DateTime<Utc>> {
+        parse_time(&self.recorded_at)
+    }
+}
+
+fn parse_time(t: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(t)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Why an LGTM no longer counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaleReason {
+    /// Files changed since the approved snapshot.
+    Changed,
+    /// The approved snapshot can no longer be compared with the workspace.
+    Uncomparable,
+    /// No snapshot exists for the vote and none could be taken, so what was
+    /// approved is unknown.
+    NoSnapshot,
+}
+
+/// An LGTM invalidated by workspace changes made after it was cast, or one that
+/// can't be tied to a workspace state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleApproval {
+    pub reviewer: String,
+    /// The approved commit, if one is known.
+    pub commit: Option<String>,
+    /// Files changed since the approval (empty unless `reason` is `Changed`).
+    pub delta: Vec<String>,
+    pub reason: StaleReason,
+}
+
+/// Append-only JSONL store of vote snapshots.
+pub struct SnapshotStore {
+    path: PathBuf,
+}
+
+impl SnapshotStore {
+    pub fn new(project_root: &Path) -> Self {
+        Self::at(journal::cache_dir(project_root).join(SNAPSHOTS_FILE))
+    }
+
+    pub const fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn record(&self, snapshot: &VoteSnapshot) {
+        let result = (|| -> std::io::Result<()> {
+            if let Some(parent) = self.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            let line = serde_json::to_string(snapshot).map_err(std::io::Error::other)?;
+            writeln!(file, "{line}")
+        })();
+        if let Err(e) = result {
+            tracing::warn!(path = %self.path.display(), error = %e, "failed to record vote snapshot");
+        }
+    }
+
+    pub fn snapshots(&self) -> Vec<VoteSnapshot> {
+        let Ok(content) = fs::read_to_string(&self.path) else {
+            return Vec::new();
+        };
+        content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+
+    /// The workspace state a vote was cast against.
+    ///
+    /// Prefers the latest reviewer snapshot taken at or before the vote; otherwise the
+    /// earliest observed snapshot taken at or after it.
+    pub fn state_for(
+        &self,
+        review_id: &str,
+        reviewer: &str,
+        voted_at: Option<&str>,
+    ) -> Option<WorkspaceState> {
+        let voted_at = voted_at.and_then(parse_time);
+        let snapshots: Vec<VoteSnapshot> = self
+            .snapshots()
+            .into_iter()
+            .filter(|s| s.review_id == review_id && s.reviewer == reviewer)
+            .collect();
+
+        let reviewed = snapshots
+            .iter()
+            .filter(|s| s.source == SnapshotSource::Reviewer)
+            .filter(|s| voted_at.is_none_or(|v| s.recorded().is_some_and(|r| r <= v)))
+            .max_by_key(|s| s.recorded());
+        if let Some(s) = reviewed {
+            return Some(s.state.clone());
+        }
+
+        snapshots
+            .iter()
+            .filter(|s| s.source == SnapshotSource::Observed)
+            .filter(|s| voted_at.is_none_or(|v| s.recorded().is_some_and(|r| r >= v)))
+            .min_by_key(|s| s.recorded())
+            .map(|s| s.state.clone())
+    }
+
+    /// Untracked files recorded alongside `commit` by any snapshot of the review.
+    fn untracked_for_commit(&self, review_id: &str, commit: &str) -> BTreeMap<String, String> {
+        self.snapshots()
+            .into_iter()
+            .filter(|s| s.review_id == review_id && s.state.commit == commit)
+            .max_by_key(VoteSnapshot::recorded)
+            .map(|s| s.state.untracked)
+            .unwrap_or_default()
+    }
+}
+
+fn is_commit_id(s: &str) -> bool {
+    (7..=64).contains(&s.len()) && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Read access to a workspace's content, for checking votes against it.
+pub trait WorkspaceProbe {
+    /// The workspace's current state.
+    fn snapshot(&self) -> anyhow::Result<WorkspaceState>;
+    /// Files that differ between `state` and the workspace now.
+    fn changed_since(&self, state: &WorkspaceState) -> anyhow::Result<Vec<String>>;
+    /// Files touched by commits made after `time`.
+    fn committed_since(&self, time: DateTime<Utc>) -> anyhow::Result<Vec<String>>;
+}
+
+/// A maw workspace, read through `maw exec`.
+pub struct MawWorkspace<'a>(pub &'a str);
+
+impl WorkspaceProbe for MawWorkspace<'_> {
+    fn snapshot(&self) -> anyhow::Result<WorkspaceState> {
+        workspace_snapshot(self.0)
+    }
+
+    fn changed_since(&self, state: &WorkspaceState) -> anyhow::Result<Vec<String>> {
+        changed_since(self.0, state)
+    }
+
+    fn committed_since(&self, time: DateTime<Utc>) -> anyhow::Result<Vec<String>> {
+        let since = format!(
+            "--since={}",
+            time.to_rfc3339_opts(SecondsFormat::Secs, true)
+        );
+        let out = Tool::new("git")
+            .args(&["log", &since, "--name-only", "--format=", "HEAD"])
+            .in_workspace(self.0)?
+            .run_ok()?;
+        let mut files = adapters::parse_name_only(&out.stdout);
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+}
+
+/// A workspace with fixed content, for tests.
+#[cfg(test)]
+#[derive(Default)]
+pub struct FakeWorkspace {
+    pub state: WorkspaceState,
+    /// Files reported changed against any state other than `state`.
+    pub changed: Vec<String>,
+    /// Files reported committed since any time.
+    pub committed: Vec<String>,
+}
+
+#[cfg(test)]
+impl WorkspaceProbe for FakeWorkspace {
+    fn snapshot(&self) -> anyhow::Result<WorkspaceState> {
+        Ok(self.state.clone())
+    }
+
+    fn changed_since(&self, state: &WorkspaceState) -> anyhow::Result<Vec<String>> {
+        Ok(if *state == self.state {
+            Vec::new()
+        } else {
+            self.changed.clone()
+        })
+    }
+
+    fn committed_since(&self, _time: DateTime<Utc>) -> anyhow::Result<Vec<String>> {
+        Ok(self.committed.clone())
+    }
+}
+
+/// Snapshot the workspace state (HEAD plus uncommitted changes, plus untracked files).
+pub fn workspace_snapshot(workspace: &str) -> anyhow::Result<WorkspaceState> {
+    Ok(WorkspaceState {
+        commit: tracked_snapshot(workspace)?,
+        untracked: untracked_blobs(workspace)?,
+    })
+}
+
+/// HEAD plus uncommitted changes to tracked files, as a commit ID.
+fn tracked_snapshot(workspace: &str) -> anyhow::Result<String> {
+    let stash = Tool::new("git")
+        .args(&["stash", "create"])
+        .in_workspace(workspace)?
+        .run_ok()?;
+    let stash = stash.stdout.trim();
+    if is_commit_id(stash) {
+        return Ok(stash.to_string());
+    }
+    let head = Tool::new("git")
+        .args(&["rev-parse", "HEAD"])
+        .in_workspace(workspace)?
+        .run_ok()?;
+    let head = head.stdout.trim();
+    if !is_commit_id(head) {
+        anyhow::bail!("unexpected rev-parse output in {workspace}: {head:?}");
+    }
+    Ok(head.to_string())
+}
+
+/// Blob IDs of the workspace's untracked, non-ignored files.
+fn untracked_blobs(workspace: &str) -> anyhow::Result<BTreeMap<String, String>> {
+    let paths = workspace_diff::untracked_paths(workspace)?;
+    hash_files(workspace, &paths)
+}
+
+/// `git hash-object` each of `paths`, keyed by path.
+fn hash_files(workspace: &str, paths: &[String]) -> anyhow::Result<BTreeMap<String, String>> {
+    if paths.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    let mut args = vec!["hash-object", "--"];
+    args.extend(paths.iter().map(String::as_str));
+    let out = Tool::new("git")
+        .args(&args)
+        .in_workspace(workspace)?
+        .run_ok()?;
+    let blobs: Vec<&str> = out.stdout.lines().map(str::trim).collect();
+    if blobs.len() != paths.len() || !blobs.iter().all(|b| is_commit_id(b)) {
+        anyhow::bail!("unexpected hash-object output in {workspace}");
+    }
+    Ok(paths
+        .iter()
+        .cloned()
+        .zip(blobs.into_iter().map(str::to_string))
+        .collect())
+}
+
+/// Files that differ between `state` and the workspace's current working tree,
+/// untracked files included.
+pub fn changed_since(workspace: &str, state: &WorkspaceState) -> anyhow::Result<Vec<String>> {
+    if !is_commit_id(&state.commit) {
+        anyhow::bail!("invalid commit id {:?}", state.commit);
+    }
+    let diff = Tool::new("git")
+        .args(&["diff", "--name-only", &state.commit])
+        .in_workspace(workspace)?
+        .run_ok()?;
+    let tracked = adapters::parse_name_only(&diff.stdout);
+    let now = untracked_blobs(workspace)?;
+    // Files untracked at snapshot time show up in the tracked diff once they're
+    // added; compare their content rather than counting the add as a change.
+    let adopted: Vec<String> = tracked
+        .iter()
+        .filter(|p| state.untracked.contains_key(*p))
+        .cloned()
+        .collect();
+    let adopted = hash_files(workspace, &adopted)?;
+    Ok(merge_delta(tracked, &state.untracked, &now, &adopted))
+}
+
+/// Combine the tracked diff with untracked-file changes into one sorted delta.
+fn merge_delta(
+    tracked: Vec<String>,
+    then: &BTreeMap<String, String>,
+    now: &BTreeMap<String, String>,
+    adopted: &BTreeMap<String, String>,
+) -> Vec<String> {
+    let mut delta = BTreeSet::new();
+    for path in &tracked {
+        if then
+            .get(path)
+            .is_none_or(|blob| adopted.get(path) != Some(blob))
+        {
+            delta.insert(path.clone());
+        }
+    }
+    for (path, blob) in now {
+        if then.get(path) != Some(blob) {
+            delta.insert(path.clone());
+        }
+    }
+    for path in then.keys() {
+        if !now.contains_key(path) && !tracked.contains(path) {
+            delta.insert(path.clone());
+        }
+    }
+    delta.into_iter().collect()
+}
+
+/// Record a reviewer snapshot for a review the reviewer is about to look at.
+pub fn record_reviewer_snapshot(
+    store: &SnapshotStore,
+    review_id: &str,
+    reviewer: &str,
+    workspace: &str,
+) {
+    match workspace_snapshot(workspace) {
+        Ok(state) => store.record(&VoteSnapshot {
+            review_id: review_id.to_string(),
+            reviewer: reviewer.to_string(),
+            state,
+            recorded_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            source: SnapshotSource::Reviewer,
+        }),
+        Err(e) => tracing::warn!(review_id, workspace, error = %e, "could not snapshot workspace"),
+    }
+}
+
+/// Find approvals cast against an older workspace state and revoke them in
+/// `decision`.
+pub fn apply_staleness(
+    store: &SnapshotStore,
+    workspace: &str,
+    review: &ReviewDetail,
+    decision: &mut ReviewGateDecision,
+) -> Vec<StaleApproval> {
+    let latest = review_gate::latest_votes(review);
+    let mut stale = Vec::new();
+
+    for reviewer in decision.approved_by.clone() {
+        let Some(vote) = latest.get(&reviewer) else {
+            continue;
+        };
+        if let Some(s) = stale_vote(store, &MawWorkspace(workspace), &review.review_id, vote) {
+            decision.mark_stale(&reviewer);
+            stale.push(s);
+        }
+    }
+    stale
+}
+
+/// Check a single approval against the workspace.
+///
+/// A vote with no snapshot gets one now (see [`observe_vote`]). Only when that fails
+/// is it reported as `NoSnapshot`: without knowing what was approved, the approval
+/// can't be trusted.
+pub fn stale_vote(
+    store: &SnapshotStore,
+    workspace: &dyn WorkspaceProbe,
+    review_id: &str,
+    vote: &ReviewVote,
+) -> Option<StaleApproval> {
+    let reviewer = &vote.reviewer;
+    let known = match vote.commit_id.as_deref().filter(|c| is_commit_id(c)) {
+        Some(commit) => Some(WorkspaceState {
+            commit: commit.to_string(),
+            untracked: store.untracked_for_commit(review_id, commit),
+        }),
+        None => store.state_for(review_id, reviewer, vote.voted_at.as_deref()),
+    };
+    let Some(state) = known else {
+        return observe_vote(store, workspace, review_id, vote);
+    };
+
+    // A diff failure (e.g. the snapshot was garbage-collected) counts as stale:
+    // re-review is cheaper than merging unreviewed code.
+    let (reason, delta) = match workspace.changed_since(&state) {
+        Ok(delta) if delta.is_empty() => return None,
+        Ok(delta) => (StaleReason::Changed, delta),
+        Err(e) => {
+            tracing::warn!(%reviewer, commit = %state.commit, error = %e, "could not diff against approval");
+            (StaleReason::Uncomparable, Vec::new())
+        }
+    };
+    Some(StaleApproval {
+        reviewer: reviewer.clone(),
+        commit: Some(state.commit),
+        delta,
+        reason,
+    })
+}
+
+/// Snapshot the workspace for a vote seen for the first time, tying the vote to the
+/// workspace as it is now.
+///
+/// Commits made after the vote mean the reviewer saw an older state, so the vote is
+/// stale and nothing is recorded. Uncommitted edits made between the vote and this
+/// first sighting can't be told apart from what was approved.
+fn observe_vote(
+    store: &SnapshotStore,
+    workspace: &dyn WorkspaceProbe,
+    review_id: &str,
+    vote: &ReviewVote,
+) -> Option<StaleApproval> {
+    let reviewer = &vote.reviewer;
+    let unverified = || StaleApproval {
+        reviewer: reviewer.clone(),
+        commit: None,
+        delta: Vec::new(),
+        reason: StaleReason::NoSnapshot,
+    };
+    if let Some(voted_at) = vote.voted_at.as_deref().and_then(parse_time) {
+        match workspace.committed_since(voted_at) {
+            Ok(delta) if delta.is_empty() => {}
+            Ok(delta) => {
+                return Some(StaleApproval {
+                    reviewer: reviewer.clone(),
+                    commit: None,
+                    delta,
+                    reason: StaleReason::Changed,
+                });
+            }
+            Err(e) => {
+                tracing::warn!(%reviewer, error = %e, "could not list commits since vote");
+                return Some(unverified());
+            }
+        }
+    }
+    match workspace.snapshot() {
+        Ok(state) => {
+            store.record(&VoteSnapshot {
+                review_id: review_id.to_string(),
+                reviewer: reviewer.clone(),
+                state,
+                recorded_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+                source: SnapshotSource::Observed,
+            });
+            None
+        }
+        Err(e) => {
+            tracing::warn!(%reviewer, error = %e, "could not snapshot workspace for vote");
+            Some(unverified())
+        }
+    }
+}
+
+/// Human-readable summary of a stale approval for diagnostics.
+pub fn describe(stale: &StaleApproval) -> String {
+    let short = stale.commit.as_deref().map_or("", |c| &c[..c.len().min(8)]);
+    match stale.reason {
+        StaleReason::NoSnapshot => format!(
+            "approval unverified: the workspace could not be snapshotted for {}'s LGTM, so what they approved is unknown",
+            stale.reviewer
+        ),
+        StaleReason::Uncomparable => format!(
+            "approval stale: {} approved {short}, which can no longer be compared with the workspace",
+            stale.reviewer
+        ),
+        StaleReason::Changed if stale.commit.is_none() => format!(
+            "approval stale: {} file(s) committed after {}'s LGTM: {}",
+            stale.delta.len(),
+            stale.reviewer,
+            stale.delta.join(", ")
+        ),
+        StaleReason::Changed => format!(
+            "approval stale: {} approved {short}, {} file(s) changed since: {}",
+            stale.reviewer,
+            stale.delta.len(),
+            stale.delta.join(", ")
+        ),
+    }
+}
+
+/// Commands to re-request review of the delta from reviewers whose approval went stale.
+pub fn re_review_steps(
+    workspace: &str,
+    review_id: &str,
+    project: &str,
+    agent: &str,
+    stale: &[StaleApproval],
+) -> Vec<String> {
+    let reviewers: Vec<&str> = stale.iter().map(|s| s.reviewer.as_str()).collect();
+    let mut delta: Vec<&str> = stale
+        .iter()
+        .flat_map(|s| s.delta.iter().map(String::as_str))
+        .collect();
+    delta.sort_unstable();
+    delta.dedup();
+    let changed = if delta.is_empty() {
+        String::new()
+    } else {
+        format!(" Changed since approval: {}.", delta.join(", "))
+    };
+    let mentions: Vec<String> = reviewers.iter().map(|r| format!("@{r}")).collect();
+    vec![
+        super::shell::seal_request_cmd(workspace, review_id, &reviewers.join(","), agent),
+        super::shell::rite_send_cmd(
+            agent,
+            project,
+            &format!(
+                "Review {review_id}: approval stale, please re-review the new changes.{changed} {}",
+                mentions.join(" ")
+            ),
+            "review-request",
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> (tempfile::TempDir, SnapshotStore) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::at(dir.path().join(SNAPSHOTS_FILE));
+        (dir, store)
+    }
+
+    fn snapshot(commit: &str, at: &str) -> VoteSnapshot {
+        VoteSnapshot {
+            review_id: "cr-abc".into(),
+            reviewer: "edict-security".into(),
+            state: WorkspaceState {
+                commit: commit.into(),
+                untracked: BTreeMap::new(),
+            },
+            recorded_at: at.into(),
+            source: SnapshotSource::Reviewer,
+        }
+    }
+
+    fn blobs(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(p, b)| (p.to_string(), b.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn state_for_prefers_latest_snapshot_before_vote() {
+        let (_dir, store) = store();
+        store.record(&snapshot("aaaaaaa", "2026-02-16T09:00:00Z"));
+        store.record(&snapshot("bbbbbbb", "2026-02-16T09:30:00Z"));
+        store.record(&snapshot("ccccccc", "2026-02-16T11:00:00Z"));
+
+        let state = store.state_for("cr-abc", "edict-security", Some("2026-02-16T10:00:00Z"));
+        assert_eq!(state.map(|s| s.commit), Some("bbbbbbb".to_string()));
+        assert_eq!(store.state_for("cr-abc", "edict-perf", None), None);
+        assert_eq!(store.state_for("cr-other", "edict-security", None), None);
+    }
+
+    #[test]
+    fn state_for_ignores_unknown_sources() {
+        let (dir, store) = store();
+        let imported = r#"{"review_id":"cr-abc","reviewer":"edict-security","commit":"ddddddd","recorded_at":"2026-02-16T10:30:00Z","source":"imported"}"#;
+        fs::write(dir.path().join(SNAPSHOTS_FILE), format!("{imported}\n")).unwrap();
+        assert_eq!(store.state_for("cr-abc", "edict-security", None), None);
+    }
+
+    #[test]
+    fn state_for_falls_back_to_first_observed_snapshot_after_vote() {
+        let (_dir, store) = store();
+        let observed = |commit: &str, at: &str| VoteSnapshot {
+            source: SnapshotSource::Observed,
+            ..snapshot(commit, at)
+        };
+        store.record(&observed("aaaaaaa", "2026-02-16T09:00:00Z"));
+        store.record(&observed("bbbbbbb", "2026-02-16T10:30:00Z"));
+        store.record(&observed("ccccccc", "2026-02-16T11:00:00Z"));
+        let at = Some("2026-02-16T10:00:00Z");
+        let state = store.state_for("cr-abc", "edict-security", at);
+        assert_eq!(state.map(|s| s.commit), Some("bbbbbbb".to_string()));
+
+        store.record(&snapshot("ddddddd", "2026-02-16T09:30:00Z"));
+        let state = store.state_for("cr-abc", "edict-security", at);
+        assert_eq!(state.map(|s| s.commit), Some("ddddddd".to_string()));
+    }
+
+    fn lgtm(reviewer: &str) -> ReviewVote {
+        ReviewVote {
+            reviewer: reviewer.into(),
+            vote: "lgtm".into(),
+            voted_at: Some("2026-02-16T10:00:00Z".into()),
+            commit_id: None,
+        }
+    }
+
+    fn workspace(commit: &str) -> FakeWorkspace {
+        FakeWorkspace {
+            state: WorkspaceState {
+                commit: commit.into(),
+                untracked: BTreeMap::new(),
+            },
+            changed: vec!["src/lib.rs".into()],
+            committed: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn vote_without_snapshot_is_observed_then_checked() {
+        let (_dir, store) = store();
+        let vote = lgtm("alice");
+        assert_eq!(
+            stale_vote(&store, &workspace("aaaaaaa"), "cr-abc", &vote),
+            None
+        );
+        let recorded = store.snapshots();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].source, SnapshotSource::Observed);
+        assert_eq!(recorded[0].reviewer, "alice");
+
+        // Seen again: judged against the observed snapshot, not re-observed.
+        assert_eq!(
+            stale_vote(&store, &workspace("aaaaaaa"), "cr-abc", &vote),
+            None
+        );
+        let stale = stale_vote(&store, &workspace("bbbbbbb"), "cr-abc", &vote).unwrap();
+        assert_eq!(stale.reason, StaleReason::Changed);
+        assert_eq!(stale.delta, vec!["src/lib.rs"]);
+        assert_eq!(store.snapshots().len(), 1);
+    }
+
+    #[test]
+    fn vote_followed_by_commits_is_stale_when_first_seen() {
+        let (_dir, store) = store();
+        let ws = FakeWorkspace {
+            committed: vec!["src/main.rs".into()],
+            ..workspace("aaaaaaa")
+        };
+        let stale = stale_vote(&store, &ws, "cr-abc", &lgtm("alice")).unwrap();
+        assert_eq!(stale.reason, StaleReason::Changed);
+        assert_eq!(stale.delta, vec!["src/main.rs"]);
+        assert!(store.snapshots().is_empty());
+        assert_eq!(
+            describe(&stale),
+            "approval stale: 1 file(s) committed after alice's LGTM: src/main.rs"
+        );
+    }
+
+    #[test]
+    fn snapshot_roundtrips_untracked_files() {
+        let (_dir, store) = store();
+        let mut snap = snapshot("aaaaaaa", "2026-02-16T09:00:00Z");
+        snap.state.untracked = blobs(&[("new.rs", "1111111")]);
+        store.record(&snap);
+        assert_eq!(store.snapshots(), vec![snap]);
+        assert_eq!(
+            store.untracked_for_commit("cr-abc", "aaaaaaa"),
+            blobs(&[("new.rs", "1111111")])
+        );
+    }
+
+    #[test]
+    fn merge_delta_counts_new_changed_and_removed_untracked_files() {
+        let then = blobs(&[
+            ("kept.rs", "1111111"),
+            ("edited.rs", "2222222"),
+            ("gone.rs", "3333333"),
+        ]);
+        let now = blobs(&[
+            ("kept.rs", "1111111"),
+            ("edited.rs", "9999999"),
+            ("added.rs", "4444444"),
+        ]);
+        assert_eq!(
+            merge_delta(vec!["src/lib.rs".into()], &then, &now, &BTreeMap::new()),
+            vec!["added.rs", "edited.rs", "gone.rs", "src/lib.rs"]
+        );
+    }
+
+    #[test]
+    fn merge_delta_ignores_untracked_file_added_unchanged() {
+        let then = blobs(&[("new.rs", "1111111")]);
+        let tracked = vec!["new.rs".to_string()];
+        assert!(merge_delta(tracked.clone(), &then, &BTreeMap::new(), &then).is_empty());
+        let edited = blobs(&[("new.rs", "2222222")]);
+        assert_eq!(
+            merge_delta(tracked, &then, &BTreeMap::new(), &edited),
+            vec!["new.rs"]
+        );
+    }
+
+    #[test]
+    fn describe_lists_delta() {
+        let stale = StaleApproval {
+            reviewer: "edict-security".into(),
+            commit: Some("0123456789abcdef".into()),
+            delta: vec!["src/a.rs".into(), "src/b.rs".into()],
+            reason: StaleReason::Changed,
+        };
+        assert_eq!(
+            describe(&stale),
+            "approval stale: edict-security approved 01234567, 2 file(s) changed since: src/a.rs, src/b.rs"
+        );
+    }
+
+    #[test]
+    fn describe_reports_missing_snapshot() {
+        let stale = StaleApproval {
+            reviewer: "edict-security".into(),
+            commit: None,
+            delta: Vec::new(),
+            reason: StaleReason::NoSnapshot,
+        };
+        assert!(describe(&stale).starts_with("approval unverified: the workspace could not"));
+    }
+
+    #[test]
+    fn changed_since_rejects_non_commit() {
+        let state = WorkspaceState {
+            commit: "HEAD; rm -rf /".into(),
+            untracked: BTreeMap::new(),
+        };
+        assert!(changed_since("frost", &state).is_err());
+    }
+}
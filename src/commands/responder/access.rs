@@ -0,0 +1,157 @@
+//! Sender authorization, per-sender rate limits and the concurrency cap.
+//!
+//! Configured in `[agents.responder.access]`. Allowlists name agents directly or
+//! reference `@role`s. Rate limits count requests that run an LLM or spawn agents,
+//! per sender, in a JSON counter store in the project cache dir so they hold across
+//! responder processes (each message gets a fresh responder). The store is updated
+//! under a file lock, so concurrent responders don't lose counts.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::state_file;
+use crate::commands::journal;
+
+/// Counter store file name in the project cache dir.
+pub const RATE_FILE: &str = "responder-rate.json";
+
+/// Whether `sender` matches an allowlist. Empty allows everyone.
+pub fn allowed(entries: &[String], sender: &str, roles: &BTreeMap<String, Vec<String>>) -> bool {
+    entries.is_empty()
+        || entries.iter().any(|entry| match entry.strip_prefix('@') {
+            Some(role) => roles
+                .get(role)
+                .is_some_and(|members| members.iter().any(|m| m == sender)),
+            None => entry == sender,
+        })
+}
+
+/// Outcome of a rate limit check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateDecision {
+    Allowed,
+    /// Over the limit; the oldest counted request ages out after this many seconds.
+    Limited {
+        retry_after: u64,
+    },
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RateState {
+    /// Request times (unix seconds) per sender, oldest first.
+    #[serde(default)]
+    senders: BTreeMap<String, Vec<u64>>,
+}
+
+/// Persistent per-sender request counters.
+pub struct RateStore {
+    path: PathBuf,
+}
+
+impl RateStore {
+    pub fn new(project_root: &Path) -> Self {
+        Self::at(journal::cache_dir(project_root).join(RATE_FILE))
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Count a request from `sender` at `now` unless it would exceed `limit` within
+    /// `window` seconds. Rejected requests are not counted.
+    pub fn check_and_record(
+        &self,
+        sender: &str,
+        now: u64,
+        limit: u32,
+        window: u64,
+    ) -> anyhow::Result<RateDecision> {
+        state_file::update(&self.path, |state: &mut RateState| {
+            let cutoff = now.saturating_sub(window);
+            state.senders.retain(|_, times| {
+                times.retain(|t| *t > cutoff);
+                !times.is_empty()
+            });
+
+            let times = state.senders.entry(sender.to_string()).or_default();
+            if times.len() >= limit as usize {
+                let oldest = times.first().copied().unwrap_or(now);
+                return RateDecision::Limited {
+                    retry_after: (oldest + window).saturating_sub(now).max(1),
+                };
+            }
+            times.push(now);
+            RateDecision::Allowed
+        })
+    }
+}
+
+/// `42m`, `3h`, `20s` for rejection replies.
+pub fn human_duration(secs: u64) -> String {
+    match secs {
+        s if s >= 3600 => format!("{}h", s.div_ceil(3600)),
+        s if s >= 60 => format!("{}m", s.div_ceil(60)),
+        s => format!("{s}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roles() -> BTreeMap<String, Vec<String>> {
+        BTreeMap::from([(
+            "maintainers".to_string(),
+            vec!["bob".to_string(), "alice".to_string()],
+        )])
+    }
+
+    #[test]
+    fn allowlist_names_and_roles() {
+        let entries = vec!["@maintainers".to_string(), "carol".to_string()];
+        assert!(allowed(&entries, "bob", &roles()));
+        assert!(allowed(&entries, "carol", &roles()));
+        assert!(!allowed(&entries, "mallory", &roles()));
+        assert!(!allowed(&["@nobody".to_string()], "bob", &roles()));
+        assert!(allowed(&[], "mallory", &roles()));
+    }
+
+    #[test]
+    fn rate_limit_persists_and_expires() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = RateStore::at(tmp.path().join("cache").join(RATE_FILE));
+
+        assert_eq!(
+            store.check_and_record("bob", 1000, 2, 60).unwrap(),
+            RateDecision::Allowed
+        );
+        assert_eq!(
+            store.check_and_record("bob", 1010, 2, 60).unwrap(),
+            RateDecision::Allowed
+        );
+        // A fresh store on the same file sees the earlier requests
+        let store = RateStore::at(tmp.path().join("cache").join(RATE_FILE));
+        assert_eq!(
+            store.check_and_record("bob", 1020, 2, 60).unwrap(),
+            RateDecision::Limited { retry_after: 40 }
+        );
+        assert_eq!(
+            store.check_and_record("alice", 1020, 2, 60).unwrap(),
+            RateDecision::Allowed
+        );
+        // The first request ages out of the window
+        assert_eq!(
+            store.check_and_record("bob", 1061, 2, 60).unwrap(),
+            RateDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn human_durations() {
+        assert_eq!(human_duration(20), "20s");
+        assert_eq!(human_duration(61), "2m");
+        assert_eq!(human_duration(3600), "1h");
+    }
+}
@@ -0,0 +1,354 @@
+//! Built-in control commands: `!status`, `!agents`, `!cancel`, `!pause`, `!resume`.
+//!
+//! These operate on the running system instead of spawning an LLM, so humans on the
+//! channel can see and stop what the swarm is doing without shelling in. Pausing
+//! writes a flag file to the project cache dir; while it exists the responder refuses
+//! to spawn leads or project-defined spawn commands (questions are still answered).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{SecondsFormat, Utc};
+
+use crate::commands::journal;
+use crate::commands::protocol::adapters::Claim;
+use crate::commands::status::StatusReport;
+
+/// Pause flag file name in the project cache dir.
+pub const PAUSE_FILE: &str = "responder-paused";
+
+/// Advice entries included in the condensed status.
+const MAX_ADVICE: usize = 3;
+
+/// A running agent from `vessel list`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunningAgent {
+    pub name: String,
+    pub status: String,
+}
+
+/// Parse `vessel list --format json`.
+pub fn parse_vessel_agents(json: &str) -> Vec<RunningAgent> {
+    let parsed: serde_json::Value = serde_json::from_str(json).unwrap_or_default();
+    parsed["agents"]
+        .as_array()
+        .map(|agents| {
+            agents
+                .iter()
+                .filter_map(|a| {
+                    let name = a["id"].as_str().or(a["name"].as_str())?;
+                    Some(RunningAgent {
+                        name: name.to_string(),
+                        status: a["status"].as_str().unwrap_or("running").to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether an agent belongs to this project: the responder's own hierarchy
+/// (`{agent}/...`) or any `{project}-*` agent.
+pub fn is_project_agent(name: &str, agent: &str, project: &str) -> bool {
+    name.strip_prefix(agent)
+        .is_some_and(|rest| rest.starts_with('/'))
+        || name.starts_with(&format!("{project}-"))
+}
+
+/// One line per project agent with the bones and workspaces it has claimed.
+pub fn format_agents(
+    agents: &[RunningAgent],
+    claims: &[Claim],
+    agent: &str,
+    project: &str,
+) -> String {
+    let mine: Vec<&RunningAgent> = agents
+        .iter()
+        .filter(|a| is_project_agent(&a.name, agent, project))
+        .collect();
+    if mine.is_empty() {
+        return "No agents running.".to_string();
+    }
+    let mut lines = vec![format!("{} agent(s) running:", mine.len())];
+    for a in mine {
+        let held: Vec<&Claim> = claims.iter().filter(|c| c.agent == a.name).collect();
+        let bones: Vec<&str> = held.iter().flat_map(|c| c.bone_ids()).collect();
+        let workspaces: Vec<&str> = held.iter().flat_map(|c| c.workspace_names()).collect();
+        let mut line = format!("- {} ({})", a.name, a.status);
+        if !bones.is_empty() {
+            line.push_str(&format!(" bones: {}", bones.join(", ")));
+        }
+        if !workspaces.is_empty() {
+            line.push_str(&format!(" ws: {}", workspaces.join(", ")));
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// A condensed `edict status` for the channel.
+pub fn condense_status(report: &StatusReport, paused: Option<&str>) -> String {
+    let mut lines = vec![format!(
+        "{} ready bone(s), {} workspace(s) ({} stale), {} agent(s) running, {} claim(s), {} unread.",
+        report.ready_bones.count,
+        report.workspaces.total,
+        report.workspaces.stale,
+        report.agents.running,
+        report.claims.active,
+        report.inbox.unread,
+    )];
+    if let Some(paused) = paused {
+        lines.push(format!("Dispatch is PAUSED ({paused})."));
+    }
+    for advice in report.advice.iter().take(MAX_ADVICE) {
+        lines.push(format!("[{}] {}", advice.severity, advice.message));
+    }
+    if report.advice.len() > MAX_ADVICE {
+        lines.push(format!(
+            "... and {} more (edict status)",
+            report.advice.len() - MAX_ADVICE
+        ));
+    }
+    lines.join("\n")
+}
+
+/// What `!cancel` targets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CancelTarget {
+    Bone(String),
+    Agent(String),
+}
+
+impl CancelTarget {
+    pub fn parse(body: &str) -> Option<Self> {
+        let target = body.split_whitespace().next()?;
+        if target.starts_with("bn-") {
+            Some(Self::Bone(target.to_string()))
+        } else {
+            Some(Self::Agent(target.to_string()))
+        }
+    }
+}
+
+/// Agents holding a claim on `bone://{project}/{bone_id}`.
+pub fn agents_for_bone(claims: &[Claim], project: &str, bone_id: &str) -> Vec<String> {
+    let uri = format!("bone://{project}/{bone_id}");
+    let mut agents: Vec<String> = claims
+        .iter()
+        .filter(|c| c.patterns.iter().any(|p| *p == uri))
+        .map(|c| c.agent.clone())
+        .collect();
+    agents.sort();
+    agents.dedup();
+    agents
+}
+
+/// An agent and its running descendants (`name/...`), children first so a lead
+/// can't respawn a worker between kills.
+pub fn kill_order(name: &str, agents: &[RunningAgent]) -> Vec<String> {
+    let prefix = format!("{name}/");
+    let mut order: Vec<String> = agents
+        .iter()
+        .filter(|a| a.name.starts_with(&prefix))
+        .map(|a| a.name.clone())
+        .collect();
+    order.sort_by_key(|n| std::cmp::Reverse(n.matches('/').count()));
+    order.push(name.to_string());
+    order
+}
+
+/// The dispatch pause flag.
+pub struct PauseFlag {
+    path: PathBuf,
+}
+
+impl PauseFlag {
+    pub fn new(project_root: &Path) -> Self {
+        Self::at(journal::cache_dir(project_root).join(PAUSE_FILE))
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// `by <agent> since <time>` when paused.
+    pub fn paused(&self) -> Option<String> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .map(|s| s.trim().to_string())
+    }
+
+    pub fn pause(&self, by: &str) -> anyhow::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let since = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        fs::write(&self.path, format!("by {by} since {since}\n"))?;
+        Ok(())
+    }
+
+    /// Returns whether dispatch was paused.
+    pub fn resume(&self) -> anyhow::Result<bool> {
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::status::{
+        Advice, AgentsSummary, ClaimsSummary, InboxSummary, ReadyBones, WorkspaceSummary,
+    };
+
+    fn claim(agent: &str, patterns: &[&str]) -> Claim {
+        Claim {
+            agent: agent.to_string(),
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            active: true,
+            memo: None,
+            expires_at: None,
+        }
+    }
+
+    fn running(names: &[&str]) -> Vec<RunningAgent> {
+        names
+            .iter()
+            .map(|n| RunningAgent {
+                name: n.to_string(),
+                status: "running".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_vessel_list() {
+        let json = r#"{"agents":[{"id":"edict-dev/0","status":"running"},{"name":"other"}]}"#;
+        assert_eq!(
+            parse_vessel_agents(json),
+            vec![
+                RunningAgent {
+                    name: "edict-dev/0".into(),
+                    status: "running".into()
+                },
+                RunningAgent {
+                    name: "other".into(),
+                    status: "running".into()
+                },
+            ]
+        );
+        assert!(parse_vessel_agents("not json").is_empty());
+    }
+
+    #[test]
+    fn lists_project_agents_with_claims() {
+        let agents = running(&[
+            "edict-dev/0",
+            "edict-dev/0/amber-reef",
+            "other-dev",
+            "edictx-dev",
+        ]);
+        let claims = vec![
+            claim(
+                "edict-dev/0/amber-reef",
+                &["bone://edict/bn-1a2", "workspace://edict/amber-reef"],
+            ),
+            claim("other-dev", &["bone://other/bn-9"]),
+        ];
+        let text = format_agents(&agents, &claims, "edict-dev", "edict");
+        assert_eq!(
+            text,
+            "2 agent(s) running:\n\
+             - edict-dev/0 (running)\n\
+             - edict-dev/0/amber-reef (running) bones: bn-1a2 ws: amber-reef"
+        );
+        assert_eq!(
+            format_agents(&[], &claims, "edict-dev", "edict"),
+            "No agents running."
+        );
+    }
+
+    #[test]
+    fn cancel_targets_and_kill_order() {
+        assert_eq!(
+            CancelTarget::parse("bn-3kf please"),
+            Some(CancelTarget::Bone("bn-3kf".into()))
+        );
+        assert_eq!(
+            CancelTarget::parse("edict-dev/1"),
+            Some(CancelTarget::Agent("edict-dev/1".into()))
+        );
+        assert_eq!(CancelTarget::parse("  "), None);
+
+        let claims = vec![
+            claim("edict-dev/0/amber-reef", &["bone://edict/bn-3kf"]),
+            claim("edict-dev/0", &["agent://edict-dev/0"]),
+        ];
+        assert_eq!(
+            agents_for_bone(&claims, "edict", "bn-3kf"),
+            vec!["edict-dev/0/amber-reef"]
+        );
+
+        let agents = running(&[
+            "edict-dev/0",
+            "edict-dev/0/a",
+            "edict-dev/0/a/b",
+            "edict-dev/1",
+        ]);
+        assert_eq!(
+            kill_order("edict-dev/0", &agents),
+            vec!["edict-dev/0/a/b", "edict-dev/0/a", "edict-dev/0"]
+        );
+    }
+
+    #[test]
+    fn condensed_status_includes_pause_and_top_advice() {
+        let report = StatusReport {
+            ready_bones: ReadyBones {
+                count: 2,
+                items: Vec::new(),
+            },
+            workspaces: WorkspaceSummary {
+                total: 3,
+                active: 2,
+                stale: 1,
+            },
+            inbox: InboxSummary { unread: 0 },
+            agents: AgentsSummary { running: 4 },
+            claims: ClaimsSummary { active: 5 },
+            advice: (0..5)
+                .map(|i| Advice {
+                    severity: "HIGH".into(),
+                    message: format!("advice {i}"),
+                    command: None,
+                })
+                .collect(),
+        };
+        let text = condense_status(&report, Some("by bob since 2026-01-01T00:00:00Z"));
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[0],
+            "2 ready bone(s), 3 workspace(s) (1 stale), 4 agent(s) running, 5 claim(s), 0 unread."
+        );
+        assert_eq!(
+            lines[1],
+            "Dispatch is PAUSED (by bob since 2026-01-01T00:00:00Z)."
+        );
+        assert_eq!(lines[4], "[HIGH] advice 2");
+        assert_eq!(lines[5], "... and 2 more (edict status)");
+    }
+
+    #[test]
+    fn pause_flag_round_trip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let flag = PauseFlag::at(tmp.path().join("cache").join(PAUSE_FILE));
+        assert_eq!(flag.paused(), None);
+        flag.pause("bob").unwrap();
+        assert!(flag.paused().unwrap().starts_with("by bob since "));
+        assert!(flag.resume().unwrap());
+        assert!(!flag.resume().unwrap());
+    }
+}
@@ -0,0 +1,173 @@
+//! Persistent responder conversations.
+//!
+//! Each message gets a fresh responder process, and the in-memory transcript ends
+//! with it. Transcripts are saved to a JSON store in the project cache dir, keyed by
+//! channel and sender, so a follow-up minutes later resumes the conversation instead
+//! of starting cold. Conversations expire `conversation_ttl` seconds after their last
+//! message; `!forget` drops one immediately.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Transcript, TranscriptEntry};
+use crate::commands::journal;
+
+/// Conversation store file name in the project cache dir.
+pub const CONVERSATIONS_FILE: &str = "responder-conversations.json";
+
+/// Store key for a sender's conversation in a channel (rite has no sub-threads, so
+/// the channel is the thread).
+pub fn key(channel: &str, sender: &str) -> String {
+    format!("{channel}/{sender}")
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredConversation {
+    /// Unix seconds of the last save.
+    updated_at: u64,
+    entries: Vec<TranscriptEntry>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ConversationState {
+    #[serde(default)]
+    conversations: BTreeMap<String, StoredConversation>,
+}
+
+/// Saved transcripts.
+pub struct ConversationStore {
+    path: PathBuf,
+    ttl: u64,
+}
+
+impl ConversationStore {
+    pub fn new(project_root: &Path, ttl: u64) -> Self {
+        Self::at(
+            journal::cache_dir(project_root).join(CONVERSATIONS_FILE),
+            ttl,
+        )
+    }
+
+    pub fn at(path: PathBuf, ttl: u64) -> Self {
+        Self { path, ttl }
+    }
+
+    fn enabled(&self) -> bool {
+        self.ttl > 0
+    }
+
+    /// Read the store, dropping conversations idle for longer than the TTL.
+    fn load_state(&self, now: u64) -> ConversationState {
+        let mut state: ConversationState = fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        state
+            .conversations
+            .retain(|_, c| c.updated_at.saturating_add(self.ttl) >= now);
+        state
+    }
+
+    fn save_state(&self, state: &ConversationState) -> anyhow::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string(state)?)?;
+        Ok(())
+    }
+
+    /// The unexpired transcript for `key`, or an empty one.
+    pub fn load(&self, key: &str, now: u64) -> Transcript {
+        if !self.enabled() {
+            return Transcript::new();
+        }
+        let mut transcript = Transcript::new();
+        if let Some(conversation) = self.load_state(now).conversations.remove(key) {
+            transcript.entries = conversation.entries;
+        }
+        transcript
+    }
+
+    /// Save the transcript for `key`. Empty transcripts are not stored.
+    pub fn save(&self, key: &str, transcript: &Transcript, now: u64) -> anyhow::Result<()> {
+        if !self.enabled() || transcript.entries.is_empty() {
+            return Ok(());
+        }
+        let mut state = self.load_state(now);
+        state.conversations.insert(
+            key.to_string(),
+            StoredConversation {
+                updated_at: now,
+                entries: transcript.entries.clone(),
+            },
+        );
+        self.save_state(&state)
+    }
+
+    /// Drop the conversation for `key`. Returns whether there was one.
+    pub fn forget(&self, key: &str, now: u64) -> anyhow::Result<bool> {
+        let mut state = self.load_state(now);
+        let existed = state.conversations.remove(key).is_some();
+        self.save_state(&state)?;
+        Ok(existed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript(body: &str) -> Transcript {
+        let mut t = Transcript::new();
+        t.add("user", "bob", body);
+        t
+    }
+
+    #[test]
+    fn conversations_persist_per_sender() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("cache").join(CONVERSATIONS_FILE);
+        let store = ConversationStore::at(path.clone(), 600);
+        store
+            .save(
+                &key("edict", "bob"),
+                &transcript("how do reviews work?"),
+                1000,
+            )
+            .unwrap();
+
+        let store = ConversationStore::at(path, 600);
+        let resumed = store.load(&key("edict", "bob"), 1300);
+        assert_eq!(resumed.entries.len(), 1);
+        assert_eq!(resumed.entries[0].body, "how do reviews work?");
+        assert!(store.load(&key("edict", "alice"), 1300).entries.is_empty());
+        assert!(store.load(&key("other", "bob"), 1300).entries.is_empty());
+    }
+
+    #[test]
+    fn conversations_expire_and_forget() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = ConversationStore::at(tmp.path().join(CONVERSATIONS_FILE), 600);
+        let bob = key("edict", "bob");
+        store.save(&bob, &transcript("hi"), 1000).unwrap();
+        assert!(store.load(&bob, 1601).entries.is_empty());
+
+        store.save(&bob, &transcript("hi again"), 2000).unwrap();
+        assert!(store.forget(&bob, 2010).unwrap());
+        assert!(!store.forget(&bob, 2010).unwrap());
+        assert!(store.load(&bob, 2020).entries.is_empty());
+    }
+
+    #[test]
+    fn zero_ttl_disables_persistence() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(CONVERSATIONS_FILE);
+        let store = ConversationStore::at(path.clone(), 0);
+        store.save("edict/bob", &transcript("hi"), 1000).unwrap();
+        assert!(!path.exists());
+        assert!(store.load("edict/bob", 1000).entries.is_empty());
+    }
+}
@@ -0,0 +1,178 @@
+//! Project-defined responder commands (`[agents.responder.commands.<name>]`).
+//!
+//! Each command maps `!<name> [body]` to an action: spawn a detached agent via vessel,
+//! run a fixed edict subcommand, or answer inline. Built-in command names are
+//! reserved so a config typo can't shadow `!dev` or `!q`.
+
+use std::collections::BTreeMap;
+
+use minijinja::Environment;
+use serde::Serialize;
+
+use super::strip_prefix_ci;
+use crate::config::{ResponderAction, ResponderCommandConfig};
+
+/// Commands handled by the router itself.
+pub const BUILTIN_COMMANDS: &[&str] = &[
+    "oneshot", "mission", "leads", "dev", "bone", "bead", "q", "qq", "bigq", "status", "agents",
+    "cancel", "pause", "resume", "forget",
+];
+
+/// Max characters of edict subcommand output posted back to the channel.
+const MAX_OUTPUT_CHARS: usize = 3000;
+
+/// Variables available to prompt templates.
+#[derive(Debug, Serialize)]
+pub struct PromptVars<'a> {
+    pub body: &'a str,
+    pub sender: &'a str,
+    pub project: &'a str,
+    pub channel: &'a str,
+    pub agent: &'a str,
+    pub command: &'a str,
+}
+
+fn normalize(name: &str) -> &str {
+    name.trim().trim_start_matches('!')
+}
+
+fn is_reserved(name: &str) -> bool {
+    BUILTIN_COMMANDS
+        .iter()
+        .any(|b| b.eq_ignore_ascii_case(normalize(name)))
+}
+
+/// Match `!<name> [body]` against the configured commands. Returns the command name
+/// (as configured) and the trimmed body.
+pub fn match_command<'a>(
+    body: &str,
+    commands: &'a BTreeMap<String, ResponderCommandConfig>,
+) -> Option<(&'a str, String)> {
+    let trimmed = body.trim();
+    commands.keys().find_map(|name| {
+        let bare = normalize(name);
+        if bare.is_empty() || is_reserved(bare) {
+            return None;
+        }
+        strip_prefix_ci(trimmed, &format!("!{bare}")).map(|rest| (name.as_str(), rest))
+    })
+}
+
+/// Configured command names that collide with built-ins (ignored by the router).
+pub fn reserved_names(commands: &BTreeMap<String, ResponderCommandConfig>) -> Vec<&str> {
+    commands
+        .keys()
+        .filter(|name| is_reserved(name))
+        .map(String::as_str)
+        .collect()
+}
+
+/// Check that a command has what its action needs.
+pub fn validate(command: &ResponderCommandConfig) -> Result<(), String> {
+    match command.action {
+        ResponderAction::Spawn | ResponderAction::Answer
+            if command
+                .prompt
+                .as_deref()
+                .is_none_or(|p| p.trim().is_empty()) =>
+        {
+            Err("`prompt` is required for spawn and answer commands".to_string())
+        }
+        ResponderAction::Edict if command.args.is_empty() => {
+            Err("`args` is required for edict commands".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Render a prompt template.
+pub fn render_prompt(template: &str, vars: &PromptVars) -> anyhow::Result<String> {
+    let env = Environment::new();
+    Ok(env.render_str(template, vars)?)
+}
+
+/// Trim subcommand output to something postable.
+pub fn truncate_output(output: &str) -> String {
+    let trimmed = output.trim();
+    if trimmed.len() <= MAX_OUTPUT_CHARS {
+        return trimmed.to_string();
+    }
+    format!(
+        "{}\n... [truncated]",
+        &trimmed[..trimmed.floor_char_boundary(MAX_OUTPUT_CHARS)]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(action: ResponderAction) -> ResponderCommandConfig {
+        ResponderCommandConfig {
+            description: None,
+            action,
+            prompt: Some("Do {{ command }} for {{ sender }}: {{ body }}".into()),
+            model: None,
+            allowed_senders: Vec::new(),
+            args: Vec::new(),
+            timeout: None,
+        }
+    }
+
+    fn commands() -> BTreeMap<String, ResponderCommandConfig> {
+        let mut commands = BTreeMap::new();
+        commands.insert("release".to_string(), command(ResponderAction::Spawn));
+        commands.insert("!groom".to_string(), command(ResponderAction::Answer));
+        commands.insert("dev".to_string(), command(ResponderAction::Answer));
+        commands
+    }
+
+    #[test]
+    fn matches_configured_commands() {
+        let commands = commands();
+        assert_eq!(
+            match_command("!release v1.2 now", &commands),
+            Some(("release", "v1.2 now".to_string()))
+        );
+        assert_eq!(
+            match_command("!GROOM", &commands),
+            Some(("!groom", String::new()))
+        );
+        assert_eq!(match_command("!releases", &commands), None);
+        assert_eq!(match_command("release", &commands), None);
+    }
+
+    #[test]
+    fn builtin_names_are_reserved() {
+        let commands = commands();
+        assert_eq!(match_command("!dev 2", &commands), None);
+        assert_eq!(reserved_names(&commands), vec!["dev"]);
+    }
+
+    #[test]
+    fn validates_action_requirements() {
+        assert!(validate(&command(ResponderAction::Answer)).is_ok());
+        let mut spawn = command(ResponderAction::Spawn);
+        spawn.prompt = None;
+        assert!(validate(&spawn).is_err());
+        let mut edict = command(ResponderAction::Edict);
+        assert!(validate(&edict).is_err());
+        edict.args = vec!["status".into()];
+        assert!(validate(&edict).is_ok());
+    }
+
+    #[test]
+    fn renders_prompt_variables() {
+        let vars = PromptVars {
+            body: "v1.2",
+            sender: "bob",
+            project: "edict",
+            channel: "edict",
+            agent: "edict-dev",
+            command: "release",
+        };
+        let prompt = render_prompt("Do {{ command }} for {{ sender }}: {{ body }}", &vars).unwrap();
+        assert_eq!(prompt, "Do release for bob: v1.2");
+        assert!(render_prompt("{{ unclosed", &vars).is_err());
+    }
+}
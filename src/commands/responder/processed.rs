@@ -0,0 +1,513 @@
+//! Durable record of messages the responder has processed.
+//!
+//! Message claims (`message://{project}/{id}`) expire after ten minutes, so on their
+//! own they don't stop a respawned responder from re-handling an old `!dev` or
+//! `!bone`. Every message the responder routes (or deliberately skips) is recorded
+//! here with its outcome, the reason it didn't act, and what it spawned. The
+//! responder claims a message in the store (as `in_progress`) before dispatching it,
+//! so a crash mid-handler or a second responder racing for the same message doesn't
+//! re-handle it. `edict journal messages` shows the store so humans can see why a
+//! message did or didn't trigger anything.
+
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::state_file;
+use crate::commands::doctor::OutputFormat;
+use crate::commands::journal;
+
+/// Store file name in the project cache dir.
+pub const PROCESSED_FILE: &str = "responder-processed.json";
+
+/// Records older than this are dropped on write.
+const RETENTION_MAX_AGE_DAYS: i64 = 30;
+/// At most this many records are kept (newest win).
+const RETENTION_MAX_ENTRIES: usize = 1000;
+/// Characters of the message body kept for display.
+const MAX_BODY_CHARS: usize = 120;
+
+/// What happened to a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageOutcome {
+    /// Claimed for dispatch; the handler hasn't finished (or the responder died).
+    InProgress,
+    /// Routed and acted on.
+    Handled,
+    /// Routed, but the responder chose not to act (see `reason`).
+    Declined,
+    /// Not routed: internal coordination or project-agent traffic.
+    Skipped,
+    /// The handler failed.
+    Failed,
+}
+
+impl MessageOutcome {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::InProgress => "in_progress",
+            Self::Handled => "handled",
+            Self::Declined => "declined",
+            Self::Skipped => "skipped",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// What handling a message did, collected while the handlers run.
+#[derive(Debug, Default)]
+pub struct Trace {
+    pub bones: Vec<String>,
+    pub agents: Vec<String>,
+    /// Why the responder didn't act, e.g. a rate limit or a paused dispatch.
+    pub reason: Option<String>,
+}
+
+impl Trace {
+    /// Note why nothing was done. The first reason wins.
+    pub fn decline(&mut self, reason: impl Into<String>) {
+        self.reason.get_or_insert_with(|| reason.into());
+    }
+}
+
+/// One processed message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessedMessage {
+    pub id: String,
+    pub sender: String,
+    /// Start of the message body.
+    pub body: String,
+    /// Command the message routed to (`dev`, `q`, `triage`, ...).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub route: Option<String>,
+    pub outcome: MessageOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bones: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agents: Vec<String>,
+    /// RFC 3339 UTC timestamp.
+    pub processed_at: String,
+}
+
+impl ProcessedMessage {
+    /// A routed message. Errors make it `failed`; a decline reason with nothing
+    /// spawned makes it `declined`.
+    pub fn routed(
+        id: &str,
+        sender: &str,
+        body: &str,
+        route: &str,
+        trace: Trace,
+        error: Option<String>,
+    ) -> Self {
+        let spawned = !trace.bones.is_empty() || !trace.agents.is_empty();
+        let (outcome, reason) = match (error, trace.reason) {
+            (Some(e), _) => (MessageOutcome::Failed, Some(e)),
+            (None, Some(reason)) if !spawned => (MessageOutcome::Declined, Some(reason)),
+            (None, reason) => (MessageOutcome::Handled, reason),
+        };
+        Self {
+            id: id.to_string(),
+            sender: sender.to_string(),
+            body: excerpt(body),
+            route: Some(route.to_string()),
+            outcome,
+            reason,
+            bones: trace.bones,
+            agents: trace.agents,
+            processed_at: now_rfc3339(),
+        }
+    }
+
+    /// A message claimed for dispatch, before its handler runs.
+    pub fn in_progress(id: &str, sender: &str, body: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            sender: sender.to_string(),
+            body: excerpt(body),
+            route: None,
+            outcome: MessageOutcome::InProgress,
+            reason: None,
+            bones: Vec::new(),
+            agents: Vec::new(),
+            processed_at: now_rfc3339(),
+        }
+    }
+
+    /// A message the responder skipped before routing.
+    pub fn skipped(id: &str, sender: &str, body: &str, reason: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            sender: sender.to_string(),
+            body: excerpt(body),
+            route: None,
+            outcome: MessageOutcome::Skipped,
+            reason: Some(reason.to_string()),
+            bones: Vec::new(),
+            agents: Vec::new(),
+            processed_at: now_rfc3339(),
+        }
+    }
+
+    fn matches(&self, needle: &str) -> bool {
+        let needle = needle.to_lowercase();
+        self.body.to_lowercase().contains(&needle)
+            || self
+                .reason
+                .as_deref()
+                .is_some_and(|r| r.to_lowercase().contains(&needle))
+            || self.bones.iter().any(|b| b.contains(&needle))
+            || self
+                .agents
+                .iter()
+                .any(|a| a.to_lowercase().contains(&needle))
+    }
+}
+
+fn excerpt(body: &str) -> String {
+    let body = body.trim();
+    match body.char_indices().nth(MAX_BODY_CHARS) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body.to_string(),
+    }
+}
+
+fn now_rfc3339() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProcessedState {
+    #[serde(default)]
+    messages: BTreeMap<String, ProcessedMessage>,
+}
+
+/// The processed-message store.
+pub struct ProcessedStore {
+    path: PathBuf,
+}
+
+impl ProcessedStore {
+    pub fn new(project_root: &Path) -> Self {
+        Self::at(journal::cache_dir(project_root).join(PROCESSED_FILE))
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn load(&self) -> anyhow::Result<ProcessedState> {
+        state_file::read(&self.path)
+    }
+
+    /// The record for a message ID, if it was processed.
+    pub fn get(&self, id: &str) -> anyhow::Result<Option<ProcessedMessage>> {
+        Ok(self.load()?.messages.remove(id))
+    }
+
+    /// All records, oldest first.
+    pub fn entries(&self) -> anyhow::Result<Vec<ProcessedMessage>> {
+        let mut entries: Vec<ProcessedMessage> = self.load()?.messages.into_values().collect();
+        entries.sort_by(|a, b| a.processed_at.cmp(&b.processed_at));
+        Ok(entries)
+    }
+
+    /// Record `message` unless its ID is already in the store, atomically.
+    ///
+    /// Returns the existing record when there is one (the message must not be handled
+    /// again), or `None` when `message` was recorded and the caller now owns it.
+    pub fn claim(&self, message: ProcessedMessage) -> anyhow::Result<Option<ProcessedMessage>> {
+        state_file::update(&self.path, |state: &mut ProcessedState| {
+            if let Some(prev) = state.messages.get(&message.id) {
+                return Some(prev.clone());
+            }
+            state.messages.insert(message.id.clone(), message);
+            prune(state, Utc::now());
+            None
+        })
+    }
+
+    /// Record a message (replacing any earlier record), then apply the retention policy.
+    pub fn record(&self, message: ProcessedMessage) -> anyhow::Result<()> {
+        state_file::update(&self.path, |state: &mut ProcessedState| {
+            state.messages.insert(message.id.clone(), message);
+            prune(state, Utc::now());
+        })
+    }
+}
+
+fn prune(state: &mut ProcessedState, now: DateTime<Utc>) {
+    let cutoff = now - chrono::Duration::days(RETENTION_MAX_AGE_DAYS);
+    state.messages.retain(|_, m| {
+        DateTime::parse_from_rfc3339(&m.processed_at).is_ok_and(|t| t.with_timezone(&Utc) >= cutoff)
+    });
+    if state.messages.len() > RETENTION_MAX_ENTRIES {
+        let mut times: Vec<String> = state
+            .messages
+            .values()
+            .map(|m| m.processed_at.clone())
+            .collect();
+        times.sort();
+        let oldest_kept = times[times.len() - RETENTION_MAX_ENTRIES].clone();
+        state.messages.retain(|_, m| m.processed_at >= oldest_kept);
+    }
+}
+
+/// `edict journal messages`: list processed messages, newest last.
+pub fn show(
+    project_root: Option<&Path>,
+    id: Option<&str>,
+    sender: Option<&str>,
+    query: Option<&str>,
+    limit: usize,
+    format: Option<OutputFormat>,
+) -> anyhow::Result<()> {
+    let root = match project_root {
+        Some(p) => p.to_path_buf(),
+        None => std::env::current_dir().context("getting current directory")?,
+    };
+    let format = format.unwrap_or_else(|| {
+        if std::io::stdout().is_terminal() {
+            OutputFormat::Pretty
+        } else {
+            OutputFormat::Text
+        }
+    });
+
+    let store = ProcessedStore::new(&root);
+    let entries = match id {
+        Some(id) => store.get(id)?.into_iter().collect(),
+        None => store.entries()?,
+    };
+    let messages = select(&entries, id, sender, query, limit);
+
+    match format {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({ "messages": messages }))?
+            );
+        }
+        OutputFormat::Pretty | OutputFormat::Text => {
+            if messages.is_empty() {
+                println!("No processed messages found.");
+            }
+            for m in &messages {
+                let mut line = format!(
+                    "{}  {}  {}  {}",
+                    m.processed_at,
+                    m.id,
+                    m.sender,
+                    m.outcome.as_str()
+                );
+                if let Some(route) = &m.route {
+                    line.push_str(&format!("  !{route}"));
+                }
+                println!("{line}");
+                println!("  message: {}", m.body);
+                if let Some(reason) = &m.reason {
+                    println!("  reason:  {reason}");
+                }
+                if !m.bones.is_empty() {
+                    println!("  bones:   {}", m.bones.join(", "));
+                }
+                if !m.agents.is_empty() {
+                    println!("  agents:  {}", m.agents.join(", "));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn select(
+    entries: &[ProcessedMessage],
+    id: Option<&str>,
+    sender: Option<&str>,
+    query: Option<&str>,
+    limit: usize,
+) -> Vec<ProcessedMessage> {
+    let matched: Vec<&ProcessedMessage> = entries
+        .iter()
+        .filter(|m| id.is_none_or(|id| m.id == id))
+        .filter(|m| sender.is_none_or(|s| m.sender == s))
+        .filter(|m| query.is_none_or(|q| m.matches(q)))
+        .collect();
+    let skip = matched.len().saturating_sub(limit);
+    matched.into_iter().skip(skip).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trace(bones: &[&str], reason: Option<&str>) -> Trace {
+        Trace {
+            bones: bones.iter().map(|b| b.to_string()).collect(),
+            agents: Vec::new(),
+            reason: reason.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn outcome_from_trace() {
+        let handled = ProcessedMessage::routed(
+            "m1",
+            "bob",
+            "!bone fix it",
+            "bone",
+            trace(&["bn-1a2"], None),
+            None,
+        );
+        assert_eq!(handled.outcome, MessageOutcome::Handled);
+        assert_eq!(handled.bones, vec!["bn-1a2"]);
+
+        let declined = ProcessedMessage::routed(
+            "m2",
+            "bob",
+            "!dev",
+            "dev",
+            trace(&[], Some("dispatch paused")),
+            None,
+        );
+        assert_eq!(declined.outcome, MessageOutcome::Declined);
+        assert_eq!(declined.reason.as_deref(), Some("dispatch paused"));
+
+        let failed = ProcessedMessage::routed(
+            "m3",
+            "bob",
+            "!dev",
+            "dev",
+            trace(&[], None),
+            Some("vessel not found".to_string()),
+        );
+        assert_eq!(failed.outcome, MessageOutcome::Failed);
+
+        let mut first = Trace::default();
+        first.decline("rate limited");
+        first.decline("paused");
+        assert_eq!(first.reason.as_deref(), Some("rate limited"));
+    }
+
+    #[test]
+    fn store_round_trip_and_select() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("cache").join(PROCESSED_FILE);
+        let store = ProcessedStore::at(path.clone());
+        assert!(store.get("m1").unwrap().is_none());
+
+        store
+            .record(ProcessedMessage::routed(
+                "m1",
+                "bob",
+                "!bone flaky test",
+                "bone",
+                trace(&["bn-1a2"], None),
+                None,
+            ))
+            .unwrap();
+        store
+            .record(ProcessedMessage::skipped(
+                "m2",
+                "edict-dev/0",
+                "done",
+                "label task-done",
+            ))
+            .unwrap();
+
+        let store = ProcessedStore::at(path);
+        assert_eq!(store.get("m1").unwrap().unwrap().bones, vec!["bn-1a2"]);
+        let entries = store.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            select(&entries, Some("m2"), None, None, 10)[0].outcome,
+            MessageOutcome::Skipped
+        );
+        assert_eq!(select(&entries, None, Some("bob"), None, 10).len(), 1);
+        assert_eq!(select(&entries, None, None, Some("BN-1A2"), 10).len(), 1);
+        assert_eq!(select(&entries, None, None, Some("flaky"), 10)[0].id, "m1");
+    }
+
+    #[test]
+    fn claim_records_in_progress_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = ProcessedStore::at(tmp.path().join(PROCESSED_FILE));
+
+        let claimed = store
+            .claim(ProcessedMessage::in_progress("m1", "bob", "!dev"))
+            .unwrap();
+        assert!(claimed.is_none());
+        let again = store
+            .claim(ProcessedMessage::in_progress("m1", "bob", "!dev"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(again.outcome, MessageOutcome::InProgress);
+
+        // The handler's outcome replaces the in-progress record
+        store
+            .record(ProcessedMessage::routed(
+                "m1",
+                "bob",
+                "!dev",
+                "dev",
+                trace(&[], None),
+                None,
+            ))
+            .unwrap();
+        assert_eq!(
+            store.get("m1").unwrap().unwrap().outcome,
+            MessageOutcome::Handled
+        );
+    }
+
+    #[test]
+    fn corrupt_store_is_an_error_not_empty() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(PROCESSED_FILE);
+        std::fs::write(&path, "{\"messages\": {").unwrap();
+        let store = ProcessedStore::at(path);
+        assert!(store.get("m1").is_err());
+        assert!(
+            store
+                .claim(ProcessedMessage::in_progress("m1", "bob", "!dev"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn retention_drops_old_and_excess_records() {
+        let now = Utc::now();
+        let mut state = ProcessedState::default();
+        let at = |days: i64, secs: i64| {
+            (now - chrono::Duration::days(days) + chrono::Duration::seconds(secs))
+                .to_rfc3339_opts(SecondsFormat::Secs, true)
+        };
+        let mut old = ProcessedMessage::skipped("old", "bob", "hi", "x");
+        old.processed_at = at(RETENTION_MAX_AGE_DAYS + 1, 0);
+        state.messages.insert(old.id.clone(), old);
+        for i in 0..RETENTION_MAX_ENTRIES + 5 {
+            let mut m = ProcessedMessage::skipped(&format!("m{i}"), "bob", "hi", "x");
+            m.processed_at = at(1, i as i64);
+            state.messages.insert(m.id.clone(), m);
+        }
+        prune(&mut state, now);
+        assert_eq!(state.messages.len(), RETENTION_MAX_ENTRIES);
+        assert!(!state.messages.contains_key("old"));
+        assert!(!state.messages.contains_key("m4"));
+        assert!(state.messages.contains_key("m5"));
+    }
+
+    #[test]
+    fn long_bodies_are_excerpted() {
+        let body = "x".repeat(MAX_BODY_CHARS + 10);
+        assert_eq!(excerpt(&body).len(), MAX_BODY_CHARS + 3);
+        assert_eq!(excerpt("  short  "), "short");
+    }
+}
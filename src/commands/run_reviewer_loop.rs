@@ -7,21 +7,30 @@ use std::{env, fs};
 use anyhow::{Context, Result};
 use serde::Deserialize;
 
-use crate::config::{Config, ReviewerAgentConfig};
+use crate::commands::journal::{self, Journal, JournalEntry, Outcome};
+use crate::commands::kb::{self, KnowledgeBase};
+use crate::commands::protocol::vote_snapshots::{self, SnapshotStore};
+use crate::commands::review_checks;
+use crate::config::{Config, ReviewConfig, ReviewRoleConfig, ReviewerAgentConfig};
 use crate::subprocess::Tool;
 
-/// Known reviewer roles that can be derived from agent names
+/// Built-in reviewer roles that can be derived from agent names
 const KNOWN_ROLES: &[&str] = &["security"];
 
 /// Derive the reviewer role from an agent name.
 /// e.g., "myproject-security" -> Some("security"), "myproject-dev" -> None
-pub fn derive_role_from_agent_name(agent_name: &str) -> Option<String> {
-    for role in KNOWN_ROLES {
-        if agent_name.ends_with(&format!("-{}", role)) {
-            return Some(role.to_string());
-        }
-    }
-    None
+///
+/// Roles listed in `review.reviewers` or defined under `[review.roles.<name>]` are
+/// recognized alongside the built-in ones. The longest matching suffix wins, so
+/// "myproject-api-compat" resolves to "api-compat" even if "compat" is also a role.
+pub fn derive_role_from_agent_name(agent_name: &str, review: &ReviewConfig) -> Option<String> {
+    let configured = review.all_roles();
+    KNOWN_ROLES
+        .iter()
+        .map(|r| r.to_string())
+        .chain(configured)
+        .filter(|role| agent_name.ends_with(&format!("-{}", role)))
+        .max_by_key(|role| role.len())
 }
 
 /// Get the prompt name for a reviewer based on role.
@@ -55,22 +64,57 @@ pub fn load_prompt(
     prompts_dir: &Path,
     workspace: Option<&str>,
 ) -> Result<String> {
-    // Validate inputs to prevent template injection
-    validate_name(agent, "agent")?;
-    validate_name(project, "project")?;
-    if let Some(ws) = workspace {
-        validate_name(ws, "workspace")?;
-    }
-
     // Prevent path traversal in prompt name
     if prompt_name.contains('/') || prompt_name.contains('\\') || prompt_name.contains("..") {
         anyhow::bail!("invalid prompt name {prompt_name:?}");
     }
 
     let file_path = prompts_dir.join(format!("{}.md", prompt_name));
+    load_prompt_file(&file_path, agent, project, workspace)
+}
 
-    let template =
-        fs::read_to_string(&file_path).with_context(|| "reading prompt template".to_string())?;
+/// Load a role's configured prompt file (relative to the project root).
+///
+/// Absolute paths and `..` components are rejected so a config entry can't pull
+/// in files from outside the project.
+pub fn load_role_prompt(
+    prompt_path: &str,
+    agent: &str,
+    project: &str,
+    project_root: &Path,
+    workspace: Option<&str>,
+) -> Result<String> {
+    let relative = Path::new(prompt_path);
+    if relative.is_absolute()
+        || relative.components().any(|c| {
+            !matches!(
+                c,
+                std::path::Component::Normal(_) | std::path::Component::CurDir
+            )
+        })
+    {
+        anyhow::bail!(
+            "invalid role prompt path {prompt_path:?}: must be relative to the project root"
+        );
+    }
+    load_prompt_file(&project_root.join(relative), agent, project, workspace)
+}
+
+fn load_prompt_file(
+    file_path: &Path,
+    agent: &str,
+    project: &str,
+    workspace: Option<&str>,
+) -> Result<String> {
+    // Validate inputs to prevent template injection
+    validate_name(agent, "agent")?;
+    validate_name(project, "project")?;
+    if let Some(ws) = workspace {
+        validate_name(ws, "workspace")?;
+    }
+
+    let template = fs::read_to_string(file_path)
+        .with_context(|| format!("reading prompt template {}", file_path.display()))?;
 
     // Simple variable substitution (support both spaced and unspaced forms)
     let mut result = template;
@@ -87,54 +131,50 @@ pub fn load_prompt(
     Ok(result)
 }
 
-/// Get XDG-compliant cache directory for this project.
-fn get_cache_dir() -> Result<PathBuf> {
-    let base = if let Ok(xdg) = env::var("XDG_CACHE_HOME") {
-        PathBuf::from(xdg)
-    } else if cfg!(target_os = "macos") {
-        dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("could not determine home directory"))?
-            .join("Library")
-            .join("Caches")
+/// Describe a configured role's file scope and severity policy for the prompt.
+///
+/// These are instructions to the reviewer, not checks: nothing downgrades a BLOCK
+/// that ignores them.
+fn role_policy_section(role: &str, role_config: &ReviewRoleConfig) -> String {
+    let mut section = format!("\n\n## REVIEW ROLE: {role}\n\n");
+    if role_config.paths.is_empty() {
+        section.push_str("Scope: all files in the diff.\n");
     } else {
-        dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("could not determine home directory"))?
-            .join(".cache")
-    };
-
-    // Canonicalize current dir to prevent path traversal via symlinks
-    let current_dir = env::current_dir()?
-        .canonicalize()
-        .unwrap_or_else(|_| env::current_dir().unwrap_or_default());
-
-    // Use a safe slug: replace path separators, strip leading dashes, limit length
-    let slug = current_dir
-        .to_string_lossy()
-        .replace(['/', '\\'], "-")
-        .trim_start_matches('-')
-        .to_string();
-
-    // Verify slug doesn't contain path traversal
-    if slug.contains("..") {
-        anyhow::bail!("invalid project directory: path traversal detected");
+        section.push_str(&format!(
+            "Scope: only review files matching {}. If the diff touches none of them, LGTM without comments.\n",
+            role_config
+                .paths
+                .iter()
+                .map(|p| format!("`{p}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
     }
-
-    let cache_path = base.join("edict").join("projects").join(&slug);
-
-    // Verify the result is within the expected cache directory
-    if !cache_path.starts_with(base.join("edict").join("projects")) {
-        anyhow::bail!("cache directory escaped expected boundaries");
+    if role_config.advisory {
+        section.push_str(
+            "Severity policy: advisory. Comment with severities as usual, but never BLOCK — always LGTM.\n",
+        );
+    } else {
+        section.push_str(&format!(
+            "Severity policy: BLOCK if any {} issues exist; otherwise LGTM. This overrides the default CRITICAL/HIGH rule above.\n",
+            role_config.block_on.at_or_above().join("/")
+        ));
     }
-
-    Ok(cache_path)
+    if role_config.gate == Some(crate::config::RoleGate::Veto) {
+        section.push_str(
+            "Gate: veto-only. Your LGTM does not count toward approval; only a BLOCK affects the merge.\n",
+        );
+    }
+    section
 }
 
 /// Get the journal path for a specific agent.
-fn get_journal_path(agent_name: &str) -> Result<PathBuf> {
-    let role = derive_role_from_agent_name(agent_name);
+///
+/// Uses the same JSONL format as the dev-loop journal so `edict journal` can read both.
+fn get_journal_path(project_root: &Path, agent_name: &str, review: &ReviewConfig) -> PathBuf {
+    let role = derive_role_from_agent_name(agent_name, review);
     let role_suffix = role.as_deref().unwrap_or("reviewer");
-    let cache_dir = get_cache_dir()?;
-    Ok(cache_dir.join(format!("review-loop-{}.txt", role_suffix)))
+    journal::cache_dir(project_root).join(journal::review_loop_journal(role_suffix))
 }
 
 /// Workspace information from maw ws list.
@@ -252,43 +292,48 @@ fn find_work(agent: &str) -> Result<Vec<WorkItem>> {
 }
 
 /// Build the reviewer prompt with workspace context and last iteration.
+///
+/// `config_dir` is the directory holding `.edict.toml` (`ws/default` in the maw v2
+/// layout); prompt paths resolve against it.
 fn build_prompt(
     agent: &str,
     project: &str,
+    config_dir: &Path,
+    review: &ReviewConfig,
     work_items: &[WorkItem],
+    check_evidence: &[String],
     last_iteration: Option<(&str, &str)>, // (content, age)
 ) -> Result<String> {
-    let role = derive_role_from_agent_name(agent);
+    let role = derive_role_from_agent_name(agent, review);
+    let role_config = role.as_deref().and_then(|r| review.role(r));
     let prompt_name = get_reviewer_prompt_name(role.as_deref());
 
-    // Find prompts directory (handle maw v2 bare repo layout)
-    let mut prompts_dir = PathBuf::from(".agents/edict/prompts");
-    if !prompts_dir.exists() {
-        prompts_dir = PathBuf::from("ws/default/.agents/edict/prompts");
-    }
+    let prompts_dir = config_dir.join(".agents/edict/prompts");
 
     // Determine target workspace from first work item
     let target_workspace = work_items.first().map(|w| w.workspace.as_str());
 
     // Try to load specialized prompt, fall back to base reviewer if not found
-    let mut base_prompt = match load_prompt(
-        &prompt_name,
-        agent,
-        project,
-        &prompts_dir,
-        target_workspace,
-    ) {
+    let specialized = match role_config.and_then(|rc| rc.prompt.as_deref()) {
+        Some(path) => load_role_prompt(path, agent, project, config_dir, target_workspace),
+        None => load_prompt(&prompt_name, agent, project, &prompts_dir, target_workspace),
+    };
+    let mut base_prompt = match specialized {
         Ok(p) => p,
         Err(_) if role.is_some() => {
             eprintln!(
-                "Warning: {}.md not found, using base reviewer prompt",
-                prompt_name
+                "Warning: prompt for role {} not found, using base reviewer prompt",
+                role.as_deref().unwrap_or(&prompt_name)
             );
             load_prompt("reviewer", agent, project, &prompts_dir, target_workspace)?
         }
         Err(e) => return Err(e),
     };
 
+    if let (Some(role), Some(role_config)) = (role.as_deref(), role_config) {
+        base_prompt.push_str(&role_policy_section(role, role_config));
+    }
+
     // Prepend workspace preamble so the agent sees it before any steps
     if let Some(ws) = target_workspace {
         let preamble = format!(
@@ -344,6 +389,15 @@ fn build_prompt(
         }
     }
 
+    // Append deterministic check results as evidence
+    if !check_evidence.is_empty() {
+        base_prompt.push_str("\n\n## PRE-REVIEW CHECKS (deterministic, run by reviewer-loop)\n\n");
+        base_prompt.push_str("These ran on each review's diff before you. Treat FAIL/WARN results as evidence: verify them and cite them in your comments. Don't re-run them.\n\n");
+        for section in check_evidence {
+            base_prompt.push_str(section);
+        }
+    }
+
     // Append previous iteration context if available
     if let Some((content, age)) = last_iteration {
         base_prompt.push_str(&format!(
@@ -355,29 +409,47 @@ fn build_prompt(
     Ok(base_prompt)
 }
 
-/// Read the last iteration from the journal.
-fn read_last_iteration(journal_path: &Path) -> Option<(String, String)> {
-    if !journal_path.exists() {
-        return None;
-    }
-
-    let content = fs::read_to_string(journal_path).ok()?;
-    let metadata = fs::metadata(journal_path).ok()?;
-    let modified = metadata.modified().ok()?;
-    let age_secs = std::time::SystemTime::now()
-        .duration_since(modified)
-        .ok()?
-        .as_secs();
-
-    let age_minutes = age_secs / 60;
-    let age_hours = age_minutes / 60;
-    let age_str = if age_hours > 0 {
-        format!("{}h ago", age_hours)
-    } else {
-        format!("{}m ago", age_minutes)
-    };
+/// Summarize the work items handled in one iteration for the journal.
+fn summarize_work_items(work_items: &[WorkItem]) -> String {
+    work_items
+        .iter()
+        .map(|item| match &item.thread_id {
+            Some(thread_id) if item.is_thread => format!(
+                "Thread {} on review {} in workspace {}",
+                thread_id, item.review_id, item.workspace
+            ),
+            _ => format!(
+                "Review {} in workspace {}: {}",
+                item.review_id,
+                item.workspace,
+                item.title.as_deref().unwrap_or("(no title)")
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
 
-    Some((content.trim().to_string(), age_str))
+/// Record the threads of each reviewed item into the knowledge base.
+fn record_review_findings(knowledge: &KnowledgeBase, work_items: &[WorkItem]) {
+    let mut seen = std::collections::HashSet::new();
+    let mut docs = Vec::new();
+    for item in work_items {
+        if item.review_id.is_empty() || !seen.insert(item.review_id.as_str()) {
+            continue;
+        }
+        let Ok(tool) = Tool::new("seal").in_workspace(&item.workspace) else {
+            continue;
+        };
+        if let Ok(output) = tool
+            .args(&["review", &item.review_id, "--format", "json"])
+            .run()
+            && output.success()
+            && let Some(doc) = kb::review_document(&output.stdout, &item.workspace)
+        {
+            docs.push(doc);
+        }
+    }
+    knowledge.record(&docs);
 }
 
 /// Cleanup handler - release claims, clear status, send sign-off.
@@ -431,14 +503,15 @@ pub fn run_reviewer_loop(
     model_override: Option<String>,
 ) -> Result<()> {
     // Change to project root if specified
-    if let Some(root) = project_root {
-        env::set_current_dir(&root)
+    if let Some(root) = &project_root {
+        env::set_current_dir(root)
             .with_context(|| format!("changing to project root {}", root.display()))?;
     }
+    // Resolved the same way as protocol commands, so both share a cache dir
+    let project_root = env::current_dir().context("could not determine current directory")?;
 
     // Load config
-    let cwd = Path::new(".");
-    let (config_path, _) = crate::config::find_config_in_project(cwd)?;
+    let (config_path, config_dir) = crate::config::find_config_in_project(&project_root)?;
 
     let config = Config::load(&config_path)?;
 
@@ -477,13 +550,21 @@ pub fn run_reviewer_loop(
             memory_limit: None,
         });
 
-    let model_raw = model_override.unwrap_or(reviewer_config.model);
+    // Role-specific model tier from [review.roles.<name>] beats the reviewer default
+    let role_model = derive_role_from_agent_name(&agent, &config.review)
+        .and_then(|r| config.review.role(&r).and_then(|rc| rc.model.clone()));
+    let model_raw = model_override
+        .or(role_model)
+        .unwrap_or(reviewer_config.model);
     let model = config.resolve_model(&model_raw);
     let max_loops = reviewer_config.max_loops;
     let pause_secs = reviewer_config.pause;
     let timeout = reviewer_config.timeout;
 
-    let journal_path = get_journal_path(&agent)?;
+    let journal_path = get_journal_path(&project_root, &agent, &config.review);
+    let journal = Journal::at(journal_path.clone());
+    let knowledge = KnowledgeBase::new(&project_root);
+    let snapshots = SnapshotStore::new(&project_root);
 
     eprintln!("Reviewer:  {}", agent);
     eprintln!("Project:   {}", project);
@@ -556,11 +637,6 @@ pub fn run_reviewer_loop(
         ])
         .run();
 
-    // Truncate journal at start
-    if journal_path.exists() {
-        fs::write(&journal_path, "")?;
-    }
-
     // Install signal handler for cleanup
     let cleanup_agent = agent.clone();
     let cleanup_project = project.clone();
@@ -574,6 +650,7 @@ pub fn run_reviewer_loop(
     // Main loop
     for i in 1..=max_loops {
         eprintln!("\n--- Review loop {}/{} ---", i, max_loops);
+        let started_at = chrono::Utc::now();
         crate::telemetry::metrics::counter(
             "edict.reviewer.iterations_total",
             1,
@@ -613,12 +690,89 @@ pub fn run_reviewer_loop(
         );
 
         // Build prompt
-        let last_iteration = read_last_iteration(&journal_path);
+        let last_iteration = journal.read_last();
         let last_iter_ref = last_iteration
             .as_ref()
-            .map(|(content, age)| (content.as_str(), age.as_str()));
+            .map(|li| (li.content.as_str(), li.age.as_str()));
+
+        // Record what the reviewer is about to see, so an LGTM goes stale if the
+        // workspace changes afterwards
+        for item in work_items.iter().filter(|w| !w.review_id.is_empty()) {
+            vote_snapshots::record_reviewer_snapshot(
+                &snapshots,
+                &item.review_id,
+                &agent,
+                &item.workspace,
+            );
+        }
 
-        let prompt = build_prompt(&agent, &project, &work_items, last_iter_ref)?;
+        // Deterministic pre-review checks: hard failures BLOCK without an LLM pass,
+        // everything else becomes evidence in the prompt
+        let mut check_evidence = Vec::new();
+        let mut auto_blocked = Vec::new();
+        let mut llm_items = Vec::new();
+        for item in work_items {
+            if item.is_thread || item.review_id.is_empty() {
+                llm_items.push(item);
+                continue;
+            }
+            let report = review_checks::run(
+                &config.review.checks,
+                &config.secrets,
+                &config.project,
+                &item.workspace,
+            );
+            if report.is_blocking() {
+                match review_checks::auto_block(&agent, &item.workspace, &item.review_id, &report) {
+                    Ok(()) => {
+                        eprintln!(
+                            "  Blocked review {} on pre-review checks: {}",
+                            item.review_id,
+                            report.block_reason()
+                        );
+                        auto_blocked.push(format!(
+                            "Review {} in workspace {}: auto-blocked. {}",
+                            item.review_id,
+                            item.workspace,
+                            report.block_reason()
+                        ));
+                        continue;
+                    }
+                    Err(e) => eprintln!(
+                        "  Could not auto-block review {} ({e}); leaving it to the reviewer",
+                        item.review_id
+                    ),
+                }
+            }
+            check_evidence.push(report.evidence(&item.review_id, &item.workspace));
+            llm_items.push(item);
+        }
+        let work_items = llm_items;
+
+        if work_items.is_empty() {
+            journal.append(&JournalEntry::finish(
+                i,
+                &agent,
+                started_at,
+                Outcome::Complete,
+                &auto_blocked.join("\n"),
+                "",
+            ));
+            if i < max_loops {
+                std::thread::sleep(Duration::from_secs(pause_secs.into()));
+            }
+            continue;
+        }
+
+        let prompt = build_prompt(
+            &agent,
+            &project,
+            &config_dir,
+            &config.review,
+            &work_items,
+            &check_evidence,
+            last_iter_ref,
+        )?;
 
         // Run agent via Pi (default runtime)
         let reviewer_start = crate::telemetry::metrics::time_start();
@@ -636,12 +790,34 @@ pub fn run_reviewer_loop(
             &[("agent", &agent)],
         );
 
+        let mut summary = summarize_work_items(&work_items);
+        for line in &auto_blocked {
+            summary.push('\n');
+            summary.push_str(line);
+        }
+        record_review_findings(&knowledge, &work_items);
         match run_agent_result {
             Ok(_) => {
                 eprintln!("✓ Review iteration complete");
+                journal.append(&JournalEntry::finish(
+                    i,
+                    &agent,
+                    started_at,
+                    Outcome::Complete,
+                    &summary,
+                    "",
+                ));
             }
             Err(e) => {
                 eprintln!("Error running Claude: {}", e);
+                journal.append(&JournalEntry::finish(
+                    i,
+                    &agent,
+                    started_at,
+                    Outcome::Error,
+                    &format!("{summary}\nError: {e}"),
+                    "",
+                ));
                 // Continue to next iteration on error
             }
         }
@@ -663,21 +839,125 @@ mod tests {
 
     #[test]
     fn test_derive_role_security() {
+        let review = ReviewConfig::default();
         assert_eq!(
-            derive_role_from_agent_name("myproject-security"),
+            derive_role_from_agent_name("myproject-security", &review),
             Some("security".to_string())
         );
         assert_eq!(
-            derive_role_from_agent_name("foo-bar-security"),
+            derive_role_from_agent_name("foo-bar-security", &review),
             Some("security".to_string())
         );
     }
 
     #[test]
     fn test_derive_role_no_match() {
-        assert_eq!(derive_role_from_agent_name("myproject-dev"), None);
-        assert_eq!(derive_role_from_agent_name("security"), None);
-        assert_eq!(derive_role_from_agent_name("project-sec"), None);
+        let review = ReviewConfig::default();
+        assert_eq!(derive_role_from_agent_name("myproject-dev", &review), None);
+        assert_eq!(derive_role_from_agent_name("security", &review), None);
+        assert_eq!(derive_role_from_agent_name("project-sec", &review), None);
+    }
+
+    #[test]
+    fn test_derive_role_configured() {
+        let mut review = ReviewConfig::default();
+        review.reviewers.push("perf".to_string());
+        review
+            .roles
+            .insert("compat".to_string(), ReviewRoleConfig::default());
+        review
+            .roles
+            .insert("api-compat".to_string(), ReviewRoleConfig::default());
+        review.rules.push(crate::config::ReviewRule {
+            paths: vec!["migrations/".to_string()],
+            reviewers: vec!["db".to_string()],
+        });
+
+        assert_eq!(
+            derive_role_from_agent_name("myproject-db", &review),
+            Some("db".to_string())
+        );
+        assert_eq!(
+            derive_role_from_agent_name("myproject-perf", &review),
+            Some("perf".to_string())
+        );
+        assert_eq!(
+            derive_role_from_agent_name("myproject-api-compat", &review),
+            Some("api-compat".to_string())
+        );
+        assert_eq!(
+            derive_role_from_agent_name("myproject-compat", &review),
+            Some("compat".to_string())
+        );
+    }
+
+    #[test]
+    fn test_load_role_prompt_rejects_escape() {
+        let dir = std::env::temp_dir();
+        for path in ["/etc/passwd", "../secret.md", "prompts/../../x.md"] {
+            assert!(load_role_prompt(path, "proj-perf", "proj", &dir, None).is_err());
+        }
+    }
+
+    #[test]
+    fn test_load_role_prompt_substitutes() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        fs::create_dir_all(dir.join("prompts")).unwrap();
+        fs::write(
+            dir.join("prompts/perf.md"),
+            "agent={{ AGENT }} ws={{WORKSPACE}}",
+        )
+        .unwrap();
+
+        let prompt =
+            load_role_prompt("prompts/perf.md", "proj-perf", "proj", dir, Some("frost")).unwrap();
+        assert_eq!(prompt, "agent=proj-perf ws=frost");
+    }
+
+    #[test]
+    fn test_build_prompt_resolves_role_prompt_in_config_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_dir = dir.path().join("ws/default");
+        fs::create_dir_all(config_dir.join("prompts")).unwrap();
+        fs::write(
+            config_dir.join("prompts/perf.md"),
+            "perf prompt for {{ AGENT }}",
+        )
+        .unwrap();
+        let mut review = ReviewConfig::default();
+        review.roles.insert(
+            "perf".to_string(),
+            ReviewRoleConfig {
+                prompt: Some("prompts/perf.md".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let prompt =
+            build_prompt("proj-perf", "proj", &config_dir, &review, &[], &[], None).unwrap();
+        assert!(prompt.starts_with("perf prompt for proj-perf"), "{prompt}");
+        assert!(prompt.contains("## REVIEW ROLE: perf"));
+    }
+
+    #[test]
+    fn test_role_policy_section() {
+        let role = ReviewRoleConfig {
+            paths: vec!["docs/**".to_string()],
+            block_on: crate::config::ReviewSeverity::Medium,
+            ..Default::default()
+        };
+        let section = role_policy_section("docs", &role);
+        assert!(section.contains("`docs/**`"));
+        assert!(section.contains("BLOCK if any CRITICAL/HIGH/MEDIUM issues exist"));
+
+        let advisory = ReviewRoleConfig {
+            advisory: true,
+            ..Default::default()
+        };
+        let section = role_policy_section("docs", &advisory);
+        assert!(section.contains("Scope: all files"));
+        assert!(section.contains("never BLOCK"));
     }
 
     #[test]
@@ -0,0 +1,183 @@
+//! State files shared between concurrent edict processes.
+//!
+//! Each message gets a fresh responder and several loops can run at once, so many
+//! processes may update the same file. Updates hold an exclusive lock on a
+//! `<file>.lock` sibling for the whole read-modify-write and replace the file by
+//! renaming a temp file over it, so a reader never sees a half-written file.
+//! Appends to JSONL logs take the same lock, so a rewrite can't drop them.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Read a store. A missing file is an empty store; an unparseable one is an error.
+pub fn read<T: Default + DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    match fs::read_to_string(path) {
+        Ok(content) => {
+            serde_json::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
+    }
+}
+
+/// Apply `f` to a store under an exclusive lock, writing the result atomically.
+///
+/// The store is written only when `f` succeeds.
+pub fn update<T, R>(path: &Path, f: impl FnOnce(&mut T) -> R) -> anyhow::Result<R>
+where
+    T: Default + Serialize + DeserializeOwned,
+{
+    let _lock = lock(path)?;
+    let mut state: T = read(path)?;
+    let result = f(&mut state);
+    replace(path, &serde_json::to_vec(&state)?)?;
+    Ok(result)
+}
+
+/// Append a line (or several, newline-separated) to a JSONL log under the file's lock.
+pub fn append_line(path: &Path, line: &str) -> anyhow::Result<()> {
+    let _lock = lock(path)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("opening {}", path.display()))?;
+    writeln!(file, "{line}").with_context(|| format!("appending to {}", path.display()))
+}
+
+/// Rewrite a text file under its lock. `f` gets the current content (empty when the
+/// file is missing) and returns the new content, or `None` to leave the file alone.
+pub fn rewrite(path: &Path, f: impl FnOnce(&str) -> Option<String>) -> anyhow::Result<()> {
+    let _lock = lock(path)?;
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+    };
+    match f(&content) {
+        Some(new) => replace(path, new.as_bytes()),
+        None => Ok(()),
+    }
+}
+
+/// Take the exclusive lock for `path`, creating its directory if needed. The lock
+/// is released when the returned file is dropped.
+fn lock(path: &Path) -> anyhow::Result<File> {
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+
+    let lock_path = sibling(path, "lock");
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("opening {}", lock_path.display()))?;
+    lock.lock()
+        .with_context(|| format!("locking {}", lock_path.display()))?;
+    Ok(lock)
+}
+
+/// Replace `path` with `contents` by renaming a synced temp file over it.
+fn replace(path: &Path, contents: &[u8]) -> anyhow::Result<()> {
+    let tmp_path = sibling(path, &format!("tmp.{}", std::process::id()));
+    let write = (|| -> std::io::Result<()> {
+        let mut tmp = File::create(&tmp_path)?;
+        tmp.write_all(contents)?;
+        tmp.sync_all()?;
+        fs::rename(&tmp_path, path)
+    })();
+    if let Err(e) = write {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e).with_context(|| format!("writing {}", path.display()));
+    }
+    Ok(())
+}
+
+/// `<dir>/<name>.<suffix>` next to `path`.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn concurrent_updates_are_not_lost() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("cache").join("counts.json");
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..25 {
+                        update(&path, |counts: &mut BTreeMap<String, u32>| {
+                            *counts.entry("n".to_string()).or_default() += 1;
+                        })
+                        .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for t in threads {
+            t.join().unwrap();
+        }
+        let counts: BTreeMap<String, u32> = read(&path).unwrap();
+        assert_eq!(counts["n"], 200);
+    }
+
+    #[test]
+    fn appends_survive_concurrent_rewrites() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("log.jsonl");
+        let appender = {
+            let path = path.clone();
+            std::thread::spawn(move || {
+                for i in 0..100 {
+                    append_line(&path, &format!("keep {i}")).unwrap();
+                }
+            })
+        };
+        for _ in 0..50 {
+            rewrite(&path, |content| {
+                let kept: String = content
+                    .lines()
+                    .filter(|l| l.starts_with("keep"))
+                    .map(|l| format!("{l}\n"))
+                    .collect();
+                Some(format!("{kept}drop\n"))
+            })
+            .unwrap();
+        }
+        appender.join().unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content.lines().filter(|l| l.starts_with("keep")).count(),
+            100
+        );
+    }
+
+    #[test]
+    fn unparseable_store_is_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("counts.json");
+        fs::write(&path, "{\"n\": ").unwrap();
+        assert!(read::<BTreeMap<String, u32>>(&path).is_err());
+        assert!(update(&path, |_: &mut BTreeMap<String, u32>| ()).is_err());
+        // The broken file is left for inspection, not overwritten
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"n\": ");
+    }
+}
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use super::doctor::OutputFormat;
 use super::protocol::context::ProtocolContext;
 use super::protocol::review_gate;
+use super::protocol::vote_snapshots::{self, SnapshotStore};
 use crate::config::Config;
 use crate::subprocess::Tool;
 
@@ -122,18 +123,19 @@ impl StatusArgs {
             .or_else(|| config.as_ref().map(|c| c.default_agent()))
             .unwrap_or_else(|| format!("{project}-dev"));
 
-        // Get required reviewers from config (format: ["security"] → ["<project>-security"])
+        // Get required reviewers from config (format: ["security"] → ["<project>-security"]).
+        // Status doesn't diff workspaces, so every active role counts (path rules ignored).
         let required_reviewers: Vec<String> = config
             .as_ref()
             .filter(|c| c.review.enabled)
             .map(|c| {
-                c.review
-                    .reviewers
-                    .iter()
-                    .map(|r| format!("{project}-{r}"))
-                    .collect()
+                crate::commands::protocol::reviewer_rules::required_reviewers(c, &project, None)
             })
             .unwrap_or_else(|| vec![format!("{project}-security")]);
+        let gate_policy = config
+            .as_ref()
+            .map(|c| review_gate::GatePolicy::from_config(&c.review, &project))
+            .unwrap_or_default();
 
         let mut report = StatusReport {
             ready_bones: ReadyBones {
@@ -251,7 +253,7 @@ impl StatusArgs {
 
         // 6. Generate advice based on cross-tool state
         if let Some(ref context) = ctx {
-            self.generate_advice(&mut report, context, &required_reviewers)?;
+            self.generate_advice(&mut report, context, &required_reviewers, &gate_policy)?;
         }
 
         match format {
@@ -275,6 +277,7 @@ impl StatusArgs {
         report: &mut StatusReport,
         ctx: &ProtocolContext,
         required_reviewers: &[String],
+        gate_policy: &review_gate::GatePolicy,
     ) -> anyhow::Result<()> {
         // Priority 1: CRITICAL - orphaned claims (bone closed but claim still active)
         for (bone_id, _pattern) in ctx.held_bone_claims() {
@@ -295,7 +298,10 @@ impl StatusArgs {
             }
         }
 
-        // Priority 2: HIGH - LGTM review with no finish action
+        // Priority 2: HIGH - LGTM review with no finish action (or a stale LGTM)
+        let snapshots = std::env::current_dir()
+            .ok()
+            .map(|root| SnapshotStore::new(&root));
         for (bone_id, _pattern) in ctx.held_bone_claims() {
             if !is_valid_bone_id(bone_id) {
                 continue;
@@ -306,10 +312,36 @@ impl StatusArgs {
                         if let Ok(review_detail) =
                             ctx.review_status(&review_summary.review_id, ws_name)
                         {
-                            let gate = review_gate::evaluate_review_gate(
+                            let mut gate = review_gate::evaluate_with_policy(
                                 &review_detail,
                                 required_reviewers,
+                                gate_policy,
                             );
+                            let stale = snapshots.as_ref().map_or_else(Vec::new, |store| {
+                                vote_snapshots::apply_staleness(
+                                    store,
+                                    ws_name,
+                                    &review_detail,
+                                    &mut gate,
+                                )
+                            });
+
+                            if gate.status == review_gate::ReviewGateStatus::NeedsReview
+                                && !stale.is_empty()
+                            {
+                                let reviewers: Vec<&str> =
+                                    stale.iter().map(|s| s.reviewer.as_str()).collect();
+                                report.advice.push(Advice {
+                                    severity: "HIGH".to_string(),
+                                    message: format!(
+                                        "Review {} approval by {} is stale or unverified → re-request review for bone {}",
+                                        review_detail.review_id,
+                                        reviewers.join(", "),
+                                        bone_id
+                                    ),
+                                    command: Some(format!("edict protocol review {}", bone_id)),
+                                });
+                            }
 
                             if gate.status == review_gate::ReviewGateStatus::Approved {
                                 report.advice.push(Advice {
@@ -338,9 +370,10 @@ impl StatusArgs {
                         if let Ok(review_detail) =
                             ctx.review_status(&review_summary.review_id, ws_name)
                         {
-                            let gate = review_gate::evaluate_review_gate(
+                            let gate = review_gate::evaluate_with_policy(
                                 &review_detail,
                                 required_reviewers,
+                                gate_policy,
                             );
 
                             if gate.status == review_gate::ReviewGateStatus::Blocked {
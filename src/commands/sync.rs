@@ -3,11 +3,12 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use clap::Args;
-use sha2::{Digest, Sha256};
 
+use super::migrations;
+use super::overrides::{DocSet, Staleness};
 use crate::config::Config;
 use crate::error::ExitError;
-use crate::subprocess::{Tool, run_command};
+use crate::subprocess::run_command;
 use crate::template::{TemplateContext, update_managed_section};
 
 #[derive(Debug, Args)]
@@ -21,6 +22,92 @@ pub struct SyncArgs {
     /// Disable auto-commit (default: enabled)
     #[arg(long)]
     pub no_commit: bool,
+    /// List versioned migrations and whether this project has applied them
+    #[arg(long)]
+    pub list_migrations: bool,
+    /// Print unified diffs of the files sync would change, without changing anything.
+    /// With --check, also exit non-zero if anything is stale
+    #[arg(long)]
+    pub diff: bool,
+    /// Only check or sync these components (comma-separated)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub only: Vec<SyncComponent>,
+}
+
+/// Parts of a project that `edict sync` manages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SyncComponent {
+    /// Workflow docs in .agents/edict/ (and legacy JS-era artifacts)
+    Docs,
+    /// The managed section of AGENTS.md
+    AgentsMd,
+    /// Reviewer prompts in .agents/edict/prompts/
+    Prompts,
+    /// Design docs in .agents/edict/design/
+    Design,
+    /// Per-repo botbox hook registrations (.claude/settings.json, Pi extension)
+    Hooks,
+    /// Versioned migrations (see --list-migrations)
+    Migrations,
+}
+
+/// A file sync would write, or delete when `new` is `None`.
+#[derive(Debug)]
+struct FileChange {
+    path: PathBuf,
+    old: Option<String>,
+    new: Option<String>,
+}
+
+impl FileChange {
+    /// Change from the file's current contents to `new`, if they differ.
+    fn to(path: PathBuf, new: Option<String>) -> Option<Self> {
+        let old = fs::read_to_string(&path).ok();
+        (old != new).then_some(Self { path, old, new })
+    }
+
+    /// Unified diff against the current file, with paths relative to `root`.
+    fn unified_diff(&self, root: &Path) -> String {
+        let rel = self.path.strip_prefix(root).unwrap_or(&self.path).display();
+        let original = match self.old {
+            Some(_) => format!("a/{rel}"),
+            None => "/dev/null".to_string(),
+        };
+        let modified = match self.new {
+            Some(_) => format!("b/{rel}"),
+            None => "/dev/null".to_string(),
+        };
+        diffy::DiffOptions::new()
+            .set_original_filename(original)
+            .set_modified_filename(modified)
+            .create_patch(
+                self.old.as_deref().unwrap_or_default(),
+                self.new.as_deref().unwrap_or_default(),
+            )
+            .to_string()
+    }
+
+    fn apply(&self) -> Result<()> {
+        match &self.new {
+            Some(content) => fs::write(&self.path, content)
+                .with_context(|| format!("Failed to write {}", self.path.display())),
+            None => fs::remove_file(&self.path)
+                .with_context(|| format!("Failed to remove {}", self.path.display())),
+        }
+    }
+}
+
+/// Changes that bring `dir` in line with `files`.
+fn file_changes(dir: &Path, files: &[(String, String)]) -> Vec<FileChange> {
+    files
+        .iter()
+        .filter_map(|(name, content)| FileChange::to(dir.join(name), Some(content.clone())))
+        .collect()
+}
+
+/// Staleness of a doc set against its version marker; unselected sets are never stale.
+fn staleness(set: Option<&DocSet>, marker: &Path) -> Result<Staleness> {
+    set.map_or(Ok(Staleness::default()), |set| set.staleness(marker))
 }
 
 /// Embedded workflow docs
@@ -119,10 +206,15 @@ impl SyncArgs {
         let config = Config::load(&config_path)
             .with_context(|| format!("Failed to parse {}", config_path.display()))?;
 
+        if self.list_migrations {
+            migrations::list(&config.version);
+            return Ok(());
+        }
+
         // Migrate .botbox.json -> .edict.toml if needed (JSON is oldest legacy)
         let json_path = project_root.join(crate::config::CONFIG_JSON);
         let toml_path = project_root.join(crate::config::CONFIG_TOML);
-        if json_path.exists() && !toml_path.exists() {
+        if json_path.exists() && !toml_path.exists() && !self.diff {
             let json_content = fs::read_to_string(&json_path)?;
             match crate::config::json_to_toml(&json_content) {
                 Ok(toml_content) => {
@@ -138,7 +230,7 @@ impl SyncArgs {
 
         // Migrate .botbox.toml -> .edict.toml (botbox era → edict era)
         let legacy_toml_path = project_root.join(crate::config::CONFIG_TOML_LEGACY);
-        if legacy_toml_path.exists() && !toml_path.exists() {
+        if legacy_toml_path.exists() && !toml_path.exists() && !self.diff {
             match fs::rename(&legacy_toml_path, &toml_path) {
                 Ok(()) => println!("Migrated .botbox.toml -> .edict.toml"),
                 Err(e) => tracing::warn!("failed to rename .botbox.toml to .edict.toml: {e}"),
@@ -146,13 +238,16 @@ impl SyncArgs {
         }
 
         // Migrate .agents/botbox/ -> .agents/edict/ (botbox era → edict era)
-        if agents_dir_legacy.exists() && !agents_dir_edict.exists() {
+        if agents_dir_legacy.exists() && !agents_dir_edict.exists() && !self.diff {
             match fs::rename(&agents_dir_legacy, &agents_dir_edict) {
                 Ok(()) => println!("Migrated .agents/botbox/ -> .agents/edict/"),
                 Err(e) => tracing::warn!("failed to rename .agents/botbox/ to .agents/edict/: {e}"),
             }
         }
 
+        // Config file after any rename above
+        let config_path = crate::config::find_config(&project_root).unwrap_or(config_path);
+
         // Resolved agents dir (after any migration above)
         let agents_dir = if agents_dir_edict.exists() {
             agents_dir_edict
@@ -160,29 +255,117 @@ impl SyncArgs {
             agents_dir_legacy
         };
 
-        // Check staleness for each component
-        let docs_stale = self.check_docs_staleness(&agents_dir)?;
-        let managed_stale = self.check_managed_section_staleness(&project_root, &config)?;
-        let prompts_stale = self.check_prompts_staleness(&agents_dir)?;
-        let design_docs_stale = self.check_design_docs_staleness(&agents_dir)?;
+        // Docs rendered for this project's config, with local overrides, for each selected
+        // component. Reviewer prompts stay templates: the reviewer loop renders them per run.
+        let ctx = TemplateContext::from_config(&config);
+        let docs =
+            self.layered_rendered(SyncComponent::Docs, &project_root, "", WORKFLOW_DOCS, &ctx)?;
+        let prompts = self.layered(
+            SyncComponent::Prompts,
+            &project_root,
+            "prompts",
+            REVIEWER_PROMPTS,
+        )?;
+        let design_docs = self.layered_rendered(
+            SyncComponent::Design,
+            &project_root,
+            "design",
+            DESIGN_DOCS,
+            &ctx,
+        )?;
+
+        // Check staleness for each selected component
+        let docs_stale = staleness(docs.as_ref(), &agents_dir.join(".version"))?;
+        let managed_stale = self.selected(SyncComponent::AgentsMd)
+            && self.check_managed_section_staleness(&project_root, &config)?;
+        let prompts_stale = staleness(
+            prompts.as_ref(),
+            &agents_dir.join("prompts/.prompts-version"),
+        )?;
+        let design_docs_stale = staleness(
+            design_docs.as_ref(),
+            &agents_dir.join("design/.design-docs-version"),
+        )?;
+        let hook_changes = if self.selected(SyncComponent::Hooks) {
+            per_repo_hook_changes(&project_root)?
+        } else {
+            Vec::new()
+        };
+        let legacy_changes = if self.selected(SyncComponent::Docs) {
+            legacy_artifact_changes(&agents_dir)?
+        } else {
+            Vec::new()
+        };
+
+        let pending_migrations = if self.selected(SyncComponent::Migrations) {
+            migrations::pending(migrations::MIGRATIONS, &config.version)
+        } else {
+            Vec::new()
+        };
+
+        let any_stale = docs_stale.is_stale()
+            || managed_stale
+            || prompts_stale.is_stale()
+            || design_docs_stale.is_stale()
+            || !hook_changes.is_empty()
+            || !legacy_changes.is_empty()
+            || !pending_migrations.is_empty();
+
+        if self.diff {
+            let mut changes = Vec::new();
+            if let Some(docs) = docs.as_ref().filter(|_| docs_stale.is_stale()) {
+                changes.extend(file_changes(&agents_dir, &docs.files));
+            }
+            if managed_stale {
+                changes.extend(self.managed_section_change(&project_root, &config)?);
+            }
+            if let Some(prompts) = prompts.as_ref().filter(|_| prompts_stale.is_stale()) {
+                changes.extend(file_changes(&agents_dir.join("prompts"), &prompts.files));
+            }
+            if let Some(design) = design_docs.as_ref().filter(|_| design_docs_stale.is_stale()) {
+                changes.extend(file_changes(&agents_dir.join("design"), &design.files));
+            }
+            changes.extend(hook_changes);
+            changes.extend(legacy_changes);
 
-        let any_stale =
-            docs_stale || managed_stale || prompts_stale || design_docs_stale;
+            for change in &changes {
+                print!("{}", change.unified_diff(&project_root));
+            }
+            for migration in &pending_migrations {
+                println!("Pending migration {}: {}", migration.id, migration.title);
+            }
+            if !any_stale {
+                println!("All components up to date");
+            } else if self.check {
+                return Err(ExitError::new(1, "Project is out of sync".to_string()).into());
+            }
+            return Ok(());
+        }
 
         if self.check {
             if any_stale {
                 let mut parts = Vec::new();
-                if docs_stale {
-                    parts.push("workflow docs");
+                if !pending_migrations.is_empty() {
+                    let ids: Vec<&str> = pending_migrations.iter().map(|m| m.id).collect();
+                    parts.push(format!("migrations ({})", ids.join(", ")));
+                }
+                if docs_stale.is_stale() {
+                    parts.push(docs_stale.describe("workflow docs"));
                 }
                 if managed_stale {
-                    parts.push("AGENTS.md managed section");
+                    parts.push("AGENTS.md managed section".to_string());
+                }
+                if prompts_stale.is_stale() {
+                    parts.push(prompts_stale.describe("reviewer prompts"));
                 }
-                if prompts_stale {
-                    parts.push("reviewer prompts");
+                if design_docs_stale.is_stale() {
+                    parts.push(design_docs_stale.describe("design docs"));
                 }
-                if design_docs_stale {
-                    parts.push("design docs");
+                if !hook_changes.is_empty() {
+                    parts.push("per-repo hooks".to_string());
+                }
+                if !legacy_changes.is_empty() {
+                    parts.push("legacy artifacts".to_string());
                 }
                 tracing::warn!(components = %parts.join(", "), "stale components detected");
                 return Err(ExitError::new(1, "Project is out of sync".to_string()).into());
@@ -193,15 +376,15 @@ impl SyncArgs {
         }
 
         // Clean up per-repo hooks (now managed globally)
-        self.cleanup_per_repo_hooks(&project_root)?;
+        cleanup_per_repo_hooks(&project_root, &hook_changes)?;
 
         // Perform updates
         let mut changed_files = Vec::new();
 
-        if docs_stale {
-            self.sync_workflow_docs(&agents_dir)?;
+        if let Some(docs) = docs.as_ref().filter(|_| docs_stale.is_stale()) {
+            docs.write(&agents_dir, ".version")?;
             changed_files.push(".agents/edict/*.md");
-            println!("Updated workflow docs");
+            println!("Updated {}", docs_stale.describe("workflow docs"));
         }
 
         if managed_stale {
@@ -210,41 +393,36 @@ impl SyncArgs {
             println!("Updated AGENTS.md managed section");
         }
 
-        if prompts_stale {
-            self.sync_prompts(&agents_dir)?;
+        if let Some(prompts) = prompts.as_ref().filter(|_| prompts_stale.is_stale()) {
+            prompts.write(&agents_dir.join("prompts"), ".prompts-version")?;
             changed_files.push(".agents/edict/prompts/*.md");
-            println!("Updated reviewer prompts");
+            println!("Updated {}", prompts_stale.describe("reviewer prompts"));
         }
 
-        if design_docs_stale {
-            self.sync_design_docs(&agents_dir)?;
+        if let Some(design) = design_docs.as_ref().filter(|_| design_docs_stale.is_stale()) {
+            design.write(&agents_dir.join("design"), ".design-docs-version")?;
             changed_files.push(".agents/edict/design/*.md");
-            println!("Updated design docs");
+            println!("Updated {}", design_docs_stale.describe("design docs"));
         }
 
         // Clean up legacy JS artifacts (scripts, shell hooks)
-        self.cleanup_legacy_artifacts(&agents_dir, &mut changed_files);
-
-        // Migrate rite hooks from bun .mjs to edict run
-        migrate_rite_hooks(&config);
-
-        // Migrate rite hooks from botbox: descriptions to edict: descriptions
-        migrate_botbox_rite_hooks_to_edict(&config, &project_root);
-
-        // Fix hook --cwd for maw v2 (ws/default → repo root)
-        migrate_hook_cwd(&config, &project_root);
-
-        // Migrate router hook claim from agent://{name}-router → agent://{name}-dev
-        migrate_router_hook_claim(&config, &project_root);
-
-        // Migrate botty → vessel (config key + rite hooks)
-        if !self.check {
-            migrate_vessel_hooks(&config, &project_root, &config_path);
+        if self.selected(SyncComponent::Docs) {
+            self.cleanup_legacy_artifacts(&agents_dir, &mut changed_files);
         }
 
-        // Migrate beads → bones (config, data, tooling files)
-        if !self.check {
-            migrate_beads_to_bones(&project_root, &config_path)?;
+        // Versioned migrations (rite hooks, botty → vessel, beads → bones, ...)
+        if self.selected(SyncComponent::Migrations) {
+            let applied = migrations::run_pending(
+                migrations::MIGRATIONS,
+                &migrations::MigrationContext {
+                    project_root: &project_root,
+                    config_path: &config_path,
+                    config: &config,
+                },
+            )?;
+            if !applied.is_empty() {
+                changed_files.push(crate::config::CONFIG_TOML);
+            }
         }
 
         // Auto-commit if changes were made
@@ -256,6 +434,40 @@ impl SyncArgs {
         Ok(())
     }
 
+    /// Whether `--only` (if given) includes `component`.
+    fn selected(&self, component: SyncComponent) -> bool {
+        self.only.is_empty() || self.only.contains(&component)
+    }
+
+    /// `embedded` with overrides from .agents/edict-local/<subdir>, if `component` is selected.
+    fn layered(
+        &self,
+        component: SyncComponent,
+        project_root: &Path,
+        subdir: &str,
+        embedded: &[(impl AsRef<str>, impl AsRef<str>)],
+    ) -> Result<Option<DocSet>> {
+        if !self.selected(component) {
+            return Ok(None);
+        }
+        DocSet::layer(project_root, subdir, embedded).map(Some)
+    }
+
+    /// `templates` rendered for `ctx`, with overrides, if `component` is selected.
+    fn layered_rendered(
+        &self,
+        component: SyncComponent,
+        project_root: &Path,
+        subdir: &str,
+        templates: &[(&str, &str)],
+        ctx: &TemplateContext,
+    ) -> Result<Option<DocSet>> {
+        if !self.selected(component) {
+            return Ok(None);
+        }
+        DocSet::layer_rendered(project_root, subdir, templates, ctx).map(Some)
+    }
+
     fn handle_bare_repo(&self, project_root: &Path) -> Result<()> {
         // Canonicalize project_root to prevent path traversal
         let project_root = project_root
@@ -276,6 +488,24 @@ impl SyncArgs {
         if self.check {
             args.push("--check");
         }
+        let only = self
+            .only
+            .iter()
+            .filter_map(clap::ValueEnum::to_possible_value)
+            .map(|v| v.get_name().to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        if !only.is_empty() {
+            args.extend(["--only", &only]);
+        }
+        if self.diff {
+            args.push("--diff");
+            return run_command("maw", &args, Some(&project_root)).map(|out| print!("{out}"));
+        }
+        if self.list_migrations {
+            args.push("--list-migrations");
+            return run_command("maw", &args, Some(&project_root)).map(|out| print!("{out}"));
+        }
         if self.no_commit {
             args.push("--no-commit");
         }
@@ -424,18 +654,6 @@ impl SyncArgs {
         }
     }
 
-    fn check_docs_staleness(&self, agents_dir: &Path) -> Result<bool> {
-        let version_file = agents_dir.join(".version");
-        let current = compute_docs_version();
-
-        if !version_file.exists() {
-            return Ok(true);
-        }
-
-        let installed = fs::read_to_string(&version_file)?.trim().to_string();
-        Ok(installed != current)
-    }
-
     fn check_managed_section_staleness(
         &self,
         project_root: &Path,
@@ -453,135 +671,6 @@ impl SyncArgs {
         Ok(content != updated)
     }
 
-    fn check_prompts_staleness(&self, agents_dir: &Path) -> Result<bool> {
-        let version_file = agents_dir.join("prompts/.prompts-version");
-        let current = compute_prompts_version();
-
-        if !version_file.exists() {
-            return Ok(true);
-        }
-
-        let installed = fs::read_to_string(&version_file)?.trim().to_string();
-        Ok(installed != current)
-    }
-
-    /// Clean up per-repo hooks that are now managed globally.
-    /// Removes botbox hooks from per-repo .claude/settings.json and .pi/extensions/.
-    fn cleanup_per_repo_hooks(&self, project_root: &Path) -> Result<()> {
-        if self.check {
-            return Ok(());
-        }
-
-        // Clean up per-repo .claude/settings.json botbox hooks
-        let settings_path = project_root.join(".claude/settings.json");
-        if settings_path.exists() {
-            let content = fs::read_to_string(&settings_path)?;
-            if let Ok(mut settings) = serde_json::from_str::<serde_json::Value>(&content) {
-                let mut changed = false;
-                if let Some(hooks) = settings.get_mut("hooks").and_then(|h| h.as_object_mut()) {
-                    for (_event, entries) in hooks.iter_mut() {
-                        if let Some(arr) = entries.as_array_mut() {
-                            let before = arr.len();
-                            arr.retain(|entry| {
-                                !entry["hooks"]
-                                    .as_array()
-                                    .is_some_and(|hooks| {
-                                        hooks.iter().any(|h| {
-                                            let cmd = &h["command"];
-                                            if let Some(s) = cmd.as_str() {
-                                                s.contains("botbox hooks run")
-                                            } else if let Some(a) = cmd.as_array() {
-                                                a.len() >= 3
-                                                    && a[0].as_str() == Some("botbox")
-                                                    && a[1].as_str() == Some("hooks")
-                                                    && a[2].as_str() == Some("run")
-                                            } else {
-                                                false
-                                            }
-                                        })
-                                    })
-                            });
-                            if arr.len() != before {
-                                changed = true;
-                            }
-                        }
-                    }
-                    // Remove empty event arrays
-                    hooks.retain(|_, v| {
-                        v.as_array().map(|a| !a.is_empty()).unwrap_or(true)
-                    });
-                }
-
-                if changed {
-                    // Remove hooks key entirely if empty
-                    if settings
-                        .get("hooks")
-                        .and_then(|h| h.as_object())
-                        .is_some_and(|h| h.is_empty())
-                    {
-                        settings.as_object_mut().unwrap().remove("hooks");
-                    }
-
-                    // Only write back if there's other content; delete if empty
-                    if settings.as_object().is_some_and(|o| o.is_empty()) {
-                        fs::remove_file(&settings_path)?;
-                        // Also remove .claude dir if empty
-                        let claude_dir = project_root.join(".claude");
-                        if claude_dir.exists() && fs::read_dir(&claude_dir)?.next().is_none() {
-                            fs::remove_dir(&claude_dir)?;
-                        }
-                    } else {
-                        fs::write(&settings_path, serde_json::to_string_pretty(&settings)?)?;
-                    }
-                    println!("Cleaned up per-repo botbox hooks from .claude/settings.json (now managed globally via `botbox hooks install`)");
-                }
-            }
-        }
-
-        // Clean up per-repo Pi extension
-        let pi_ext = project_root.join(".pi/extensions/botbox-hooks.ts");
-        if pi_ext.exists() {
-            fs::remove_file(&pi_ext)?;
-            // Clean up empty dirs
-            let pi_ext_dir = project_root.join(".pi/extensions");
-            if pi_ext_dir.exists() && fs::read_dir(&pi_ext_dir)?.next().is_none() {
-                fs::remove_dir(&pi_ext_dir)?;
-            }
-            let pi_dir = project_root.join(".pi");
-            if pi_dir.exists() && fs::read_dir(&pi_dir)?.next().is_none() {
-                fs::remove_dir(&pi_dir)?;
-            }
-            println!("Cleaned up per-repo Pi extension (now managed globally via `botbox hooks install`)");
-        }
-
-        Ok(())
-    }
-
-    fn check_design_docs_staleness(&self, agents_dir: &Path) -> Result<bool> {
-        let version_file = agents_dir.join("design/.design-docs-version");
-        let current = compute_design_docs_version();
-
-        if !version_file.exists() {
-            return Ok(true);
-        }
-
-        let installed = fs::read_to_string(&version_file)?.trim().to_string();
-        Ok(installed != current)
-    }
-
-    fn sync_workflow_docs(&self, agents_dir: &Path) -> Result<()> {
-        for (name, content) in WORKFLOW_DOCS {
-            let path = agents_dir.join(name);
-            fs::write(&path, content)
-                .with_context(|| format!("Failed to write {}", path.display()))?;
-        }
-
-        let version = compute_docs_version();
-        fs::write(agents_dir.join(".version"), version)?;
-
-        Ok(())
-    }
-
     fn sync_managed_section(&self, project_root: &Path, config: &Config) -> Result<()> {
         let agents_md = project_root.join("AGENTS.md");
         if !agents_md.exists() {
@@ -596,38 +685,21 @@ impl SyncArgs {
         Ok(())
     }
 
-    fn sync_prompts(&self, agents_dir: &Path) -> Result<()> {
-        let prompts_dir = agents_dir.join("prompts");
-        fs::create_dir_all(&prompts_dir)?;
-
-        for (name, content) in REVIEWER_PROMPTS {
-            let path = prompts_dir.join(name);
-            fs::write(&path, content)
-                .with_context(|| format!("Failed to write {}", path.display()))?;
-        }
-
-        let version = compute_prompts_version();
-        fs::write(prompts_dir.join(".prompts-version"), version)?;
-
-        Ok(())
-    }
-
-    // sync_hooks removed — hooks are now installed globally via `botbox hooks install`
-
-    fn sync_design_docs(&self, agents_dir: &Path) -> Result<()> {
-        let design_dir = agents_dir.join("design");
-        fs::create_dir_all(&design_dir)?;
-
-        for (name, content) in DESIGN_DOCS {
-            let path = design_dir.join(name);
-            fs::write(&path, content)
-                .with_context(|| format!("Failed to write {}", path.display()))?;
+    /// AGENTS.md with an up-to-date managed section, if it changes.
+    fn managed_section_change(
+        &self,
+        project_root: &Path,
+        config: &Config,
+    ) -> Result<Option<FileChange>> {
+        let agents_md = project_root.join("AGENTS.md");
+        if !agents_md.exists() {
+            return Ok(None);
         }
 
-        let version = compute_design_docs_version();
-        fs::write(design_dir.join(".design-docs-version"), version)?;
-
-        Ok(())
+        let content = fs::read_to_string(&agents_md)?;
+        let ctx = TemplateContext::from_config(config);
+        let updated = update_managed_section(&content, &ctx)?;
+        Ok(FileChange::to(agents_md, Some(updated)))
     }
 
     fn auto_commit(&self, project_root: &Path, changed_files: &[&str]) -> Result<()> {
@@ -698,726 +770,138 @@ impl SyncArgs {
     }
 }
 
-/// Migrate rite hooks from `botbox:` descriptions to `edict:` descriptions.
-///
-/// Finds hooks with `botbox:{name}:responder` or `botbox:{name}:reviewer-*` descriptions,
-/// removes them, and re-registers with `edict:` prefix and `edict run` commands.
-/// Called during `edict sync` on projects that were previously set up with `botbox`.
-fn migrate_botbox_rite_hooks_to_edict(config: &Config, project_root: &Path) {
-    let output = match Tool::new("rite")
-        .args(&["hooks", "list", "--format", "json"])
-        .run()
-    {
-        Ok(o) if o.success() => o,
-        _ => return,
-    };
-
-    let parsed: serde_json::Value = match serde_json::from_str(&output.stdout) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
-
-    let hooks = match parsed.get("hooks").and_then(|h| h.as_array()) {
-        Some(h) => h,
-        None => return,
-    };
-
-    let name = &config.project.name;
-
-    // Resolve the correct cwd (bare root or project root)
-    let bare_root = if project_root.ends_with("ws/default") {
-        project_root
-            .parent()
-            .and_then(Path::parent)
-            .filter(|r| r.join(".manifold").exists())
-    } else if project_root.join(".manifold").exists() {
-        Some(project_root)
-    } else {
-        None
-    };
-    let root_str = bare_root
-        .map(|r| r.display().to_string())
-        .unwrap_or_else(|| project_root.display().to_string());
-
-    for hook in hooks {
-        let desc = hook
-            .get("description")
-            .and_then(|d| d.as_str())
-            .unwrap_or("");
-
-        // Only process botbox-era hooks for this project
-        if !desc.starts_with(&format!("botbox:{name}:")) {
-            continue;
-        }
-
-        let id = match hook.get("id").and_then(|i| i.as_str()) {
-            Some(id) => id,
-            None => continue,
-        };
-
-        // Remove old botbox hook
-        if Tool::new("rite")
-            .args(&["hooks", "remove", id])
-            .run()
-            .is_err()
-        {
-            tracing::warn!(hook_id = %id, "failed to remove botbox-era hook during edict migration");
-            continue;
-        }
-
-        let agent = config.default_agent();
-        if desc.ends_with(":responder") {
-            let responder_ml = config
-                .agents
-                .responder
-                .as_ref()
-                .and_then(|r| r.memory_limit.as_deref());
-            super::init::register_router_hook(&root_str, &root_str, name, &agent, responder_ml);
-            println!("  Migrated hook {desc} → edict:{name}:responder");
-        } else if let Some(role) = desc.strip_prefix(&format!("botbox:{name}:reviewer-")) {
-            let reviewer_agent = format!("{name}-{role}");
-            let reviewer_ml = config
-                .agents
-                .reviewer
-                .as_ref()
-                .and_then(|r| r.memory_limit.as_deref());
-            super::init::register_reviewer_hook(
-                &root_str,
-                &root_str,
-                name,
-                &agent,
-                &reviewer_agent,
-                reviewer_ml,
-            );
-            println!("  Migrated hook {desc} → edict:{name}:reviewer-{role}");
-        }
-    }
-}
-
-/// Migrate rite hooks from legacy formats to current `edict run` commands with descriptions.
-///
-/// Lists all hooks for this project's channel, identifies legacy hooks
-/// (bun-based, old naming, missing descriptions), removes them, and
-/// re-registers via `ensure_rite_hook` with proper descriptions for
-/// future idempotent management.
-fn migrate_rite_hooks(config: &Config) {
-    let output = match Tool::new("rite")
-        .args(&["hooks", "list", "--format", "json"])
-        .run()
-    {
-        Ok(o) if o.success() => o,
-        _ => return, // rite not available, skip silently
-    };
-
-    let parsed: serde_json::Value = match serde_json::from_str(&output.stdout) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
-
-    let hooks = match parsed.get("hooks").and_then(|h| h.as_array()) {
-        Some(h) => h,
-        None => return,
-    };
-
-    let name = &config.project.name;
-    let agent = config.default_agent();
-    let env_inherit = "RITE_CHANNEL,RITE_MESSAGE_ID,RITE_HOOK_ID,SSH_AUTH_SOCK,OTEL_EXPORTER_OTLP_ENDPOINT,TRACEPARENT";
-
-    for hook in hooks {
-        let id = match hook.get("id").and_then(|i| i.as_str()) {
-            Some(id) => id.to_string(),
-            None => continue,
-        };
-
-        let channel = hook.get("channel").and_then(|c| c.as_str()).unwrap_or("");
-
-        // Only migrate hooks for this project's channel
-        if channel != name {
-            continue;
-        }
-
-        // Skip hooks that already have an edict: or botbox: description (already migrated by
-        // migrate_rite_hooks or migrate_botbox_rite_hooks_to_edict respectively)
-        let existing_desc = hook
-            .get("description")
-            .and_then(|d| d.as_str())
-            .unwrap_or("");
-        if existing_desc.starts_with("edict:") || existing_desc.starts_with("botbox:") {
-            continue;
-        }
-
-        let cmd = hook.get("command").and_then(|c| c.as_array());
-        let cmd = match cmd {
-            Some(c) => c,
-            None => continue,
-        };
-
-        let cmd_strs: Vec<&str> = cmd.iter().filter_map(|v| v.as_str()).collect();
-
-        // Determine what kind of hook this is
-        let is_router = cmd_strs.iter().any(|s| {
-            s.contains("responder") || s.contains("respond.mjs") || s.contains("router.mjs")
-        });
-        let is_reviewer = cmd_strs
-            .iter()
-            .any(|s| s.contains("reviewer-loop") || s.contains("reviewer-loop.mjs"));
-
-        if !is_router && !is_reviewer {
-            continue;
-        }
-
-        let spawn_cwd = cmd_strs
-            .windows(2)
-            .find(|w| w[0] == "--cwd")
-            .map(|w| w[1])
-            .unwrap_or(".");
-
-        // Remove old hook (ensure_rite_hook handles dedup by description,
-        // but these legacy hooks have no description so we remove manually)
-        let remove = Tool::new("rite").args(&["hooks", "remove", &id]).run();
-
-        if remove.is_err() || !remove.as_ref().unwrap().success() {
-            tracing::warn!(hook_id = %id, "failed to remove legacy hook");
-            continue;
-        }
-
-        if is_router {
-            let claim_uri = format!("agent://{name}-dev");
-            let spawn_name = format!("{name}-responder");
-            let description = format!("edict:{name}:responder");
-            let responder_ml = config
-                .agents
-                .responder
-                .as_ref()
-                .and_then(|r| r.memory_limit.as_deref());
-
-            let mut router_args: Vec<&str> = vec![
-                "--agent",
-                &agent,
-                "--channel",
-                name,
-                "--claim",
-                &claim_uri,
-                "--claim-owner",
-                &agent,
-                "--cwd",
-                spawn_cwd,
-                "--ttl",
-                "600",
-                "--",
-                "vessel",
-                "spawn",
-                "--env-inherit",
-                env_inherit,
-            ];
-            if let Some(limit) = responder_ml {
-                router_args.push("--memory-limit");
-                router_args.push(limit);
-            }
-            router_args.extend_from_slice(&[
-                "--name",
-                &spawn_name,
-                "--cwd",
-                spawn_cwd,
-                "--",
-                "edict",
-                "run",
-                "responder",
-            ]);
-
-            match crate::subprocess::ensure_rite_hook(&description, &router_args) {
-                Ok(_) => println!("  Migrated router hook {id} → edict run responder"),
-                Err(e) => tracing::warn!("failed to re-register router hook: {e}"),
-            }
-        } else if is_reviewer {
-            let reviewer_agent = hook
-                .get("condition")
-                .and_then(|c| c.get("agent"))
-                .and_then(|a| a.as_str())
-                .unwrap_or("")
-                .to_string();
-
-            if reviewer_agent.is_empty() {
-                tracing::warn!(hook_id = %id, "could not determine reviewer agent for hook");
-                continue;
-            }
-
-            let role = reviewer_agent
-                .strip_prefix(&format!("{name}-"))
-                .unwrap_or(&reviewer_agent);
-            let claim_uri = format!("agent://{reviewer_agent}");
-            let description = format!("edict:{name}:reviewer-{role}");
-            let reviewer_ml = config
-                .agents
-                .reviewer
-                .as_ref()
-                .and_then(|r| r.memory_limit.as_deref());
-
-            let mut reviewer_args: Vec<&str> = vec![
-                "--agent",
-                &agent,
-                "--channel",
-                name,
-                "--mention",
-                &reviewer_agent,
-                "--claim",
-                &claim_uri,
-                "--claim-owner",
-                &reviewer_agent,
-                "--ttl",
-                "600",
-                "--priority",
-                "1",
-                "--cwd",
-                spawn_cwd,
-                "--",
-                "vessel",
-                "spawn",
-                "--env-inherit",
-                env_inherit,
-            ];
-            if let Some(limit) = reviewer_ml {
-                reviewer_args.push("--memory-limit");
-                reviewer_args.push(limit);
-            }
-            reviewer_args.extend_from_slice(&[
-                "--name",
-                &reviewer_agent,
-                "--cwd",
-                spawn_cwd,
-                "--",
-                "edict",
-                "run",
-                "reviewer-loop",
-                "--agent",
-                &reviewer_agent,
-            ]);
-
-            match crate::subprocess::ensure_rite_hook(&description, &reviewer_args) {
-                Ok(_) => println!(
-                    "  Migrated reviewer hook {id} → edict run reviewer-loop --agent {reviewer_agent}"
-                ),
-                Err(e) => tracing::warn!(agent = %reviewer_agent, "failed to re-register reviewer hook: {e}"),
+/// Whether a hook entry runs `botbox hooks run`, as string or argv command.
+fn is_botbox_hook_entry(entry: &serde_json::Value) -> bool {
+    entry["hooks"].as_array().is_some_and(|hooks| {
+        hooks.iter().any(|h| {
+            let cmd = &h["command"];
+            if let Some(s) = cmd.as_str() {
+                s.contains("botbox hooks run")
+            } else if let Some(a) = cmd.as_array() {
+                a.len() >= 3
+                    && a[0].as_str() == Some("botbox")
+                    && a[1].as_str() == Some("hooks")
+                    && a[2].as_str() == Some("run")
+            } else {
+                false
             }
-        }
-    }
+        })
+    })
 }
 
-/// Fix hook --cwd for maw v2 bare repos.
-///
-/// Earlier versions of `detect_hook_paths` checked for `.jj` to identify bare repos,
-/// which broke after the migration to Git+manifold. This re-registers hooks that have
-/// `--cwd .../ws/default` with `--cwd .../` (the repo root) instead.
-fn migrate_hook_cwd(config: &Config, project_root: &Path) {
-    // Detect maw v2: project_root may be ws/default/ (inner sync) or the bare root
-    let bare_root = if project_root.ends_with("ws/default") {
-        project_root.parent().and_then(Path::parent)
-    } else if project_root.join(".manifold").exists() {
-        Some(project_root)
-    } else {
-        None
-    };
-
-    let bare_root = match bare_root {
-        Some(r) if r.join(".manifold").exists() => r,
-        _ => return,
-    };
-
-    let ws_default_str = bare_root
-        .join("ws")
-        .join("default")
-        .display()
-        .to_string();
-    let root_str = bare_root.display().to_string();
-
-    let output = match Tool::new("rite")
-        .args(&["hooks", "list", "--format", "json"])
-        .run()
-    {
-        Ok(o) if o.success() => o,
-        _ => return,
-    };
-
-    let parsed: serde_json::Value = match serde_json::from_str(&output.stdout) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
-
-    let hooks = match parsed.get("hooks").and_then(|h| h.as_array()) {
-        Some(h) => h,
-        None => return,
-    };
-
-    let name = &config.project.name;
-    let agent = config.default_agent();
-    let reviewers: Vec<String> = config
-        .review
-        .reviewers
-        .iter()
-        .map(|r| format!("{name}-{r}"))
-        .collect();
-
-    for hook in hooks {
-        let desc = hook
-            .get("description")
-            .and_then(|d| d.as_str())
-            .unwrap_or("");
-        // Accept both current and legacy description prefixes
-        let is_ours = desc.starts_with(&format!("edict:{name}:"))
-            || desc.starts_with(&format!("botbox:{name}:"));
-        if !is_ours {
-            continue;
-        }
-
-        let cmd = match hook.get("command").and_then(|c| c.as_array()) {
-            Some(c) => c,
-            None => continue,
-        };
-        let cmd_strs: Vec<&str> = cmd.iter().filter_map(|v| v.as_str()).collect();
-
-        // Check if any --cwd arg still points to ws/default
-        let has_stale_cwd = cmd_strs
-            .windows(2)
-            .any(|w| w[0] == "--cwd" && w[1] == ws_default_str);
-        if !has_stale_cwd {
-            continue;
-        }
-
-        // Re-register with the correct cwd via the init helpers
-        let id = match hook.get("id").and_then(|i| i.as_str()) {
-            Some(id) => id,
-            None => continue,
-        };
-
-        // Remove old hook first
-        if Tool::new("rite")
-            .args(&["hooks", "remove", id])
-            .run()
-            .is_err()
-        {
-            continue;
-        }
-
-        let is_router = desc.ends_with(":responder");
-        if is_router {
-            let responder_ml = config
-                .agents
-                .responder
-                .as_ref()
-                .and_then(|r| r.memory_limit.as_deref());
-            super::init::register_router_hook(&root_str, &root_str, name, &agent, responder_ml);
-            println!("  Fixed hook --cwd: {desc} → repo root");
-        } else {
-            let reviewer_ml = config
-                .agents
-                .reviewer
-                .as_ref()
-                .and_then(|r| r.memory_limit.as_deref());
-            // Find which reviewer this is for
-            for reviewer in &reviewers {
-                if desc.contains(&reviewer.replace(&format!("{name}-"), "")) {
-                    super::init::register_reviewer_hook(
-                        &root_str, &root_str, name, &agent, reviewer, reviewer_ml,
-                    );
-                    println!("  Fixed hook --cwd: {desc} → repo root");
-                    break;
+/// Per-repo hooks that are now managed globally: botbox hooks in
+/// .claude/settings.json and the .pi/extensions/ Pi extension.
+fn per_repo_hook_changes(project_root: &Path) -> Result<Vec<FileChange>> {
+    let mut changes = Vec::new();
+
+    let settings_path = project_root.join(".claude/settings.json");
+    if settings_path.exists() {
+        let content = fs::read_to_string(&settings_path)?;
+        if let Ok(mut settings) = serde_json::from_str::<serde_json::Value>(&content) {
+            let mut changed = false;
+            if let Some(hooks) = settings.get_mut("hooks").and_then(|h| h.as_object_mut()) {
+                for (_event, entries) in hooks.iter_mut() {
+                    if let Some(arr) = entries.as_array_mut() {
+                        let before = arr.len();
+                        arr.retain(|entry| !is_botbox_hook_entry(entry));
+                        if arr.len() != before {
+                            changed = true;
+                        }
+                    }
                 }
+                // Remove empty event arrays
+                hooks.retain(|_, v| v.as_array().map(|a| !a.is_empty()).unwrap_or(true));
             }
-        }
-    }
-}
 
-/// Migrate router hook claim pattern from `agent://{name}-router` to `agent://{name}-dev`
-/// and spawn name from `{name}-router` to `{name}-responder`.
-///
-/// Earlier versions used a vestigial `-router` claim that nobody actually staked.
-/// The new pattern uses `-dev` which matches the responder's own agent claim,
-/// preventing re-trigger while processing.
-fn migrate_router_hook_claim(config: &Config, project_root: &Path) {
-    let output = match Tool::new("rite")
-        .args(&["hooks", "list", "--format", "json"])
-        .run()
-    {
-        Ok(o) if o.success() => o,
-        _ => return,
-    };
-
-    let parsed: serde_json::Value = match serde_json::from_str(&output.stdout) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
-
-    let hooks = match parsed.get("hooks").and_then(|h| h.as_array()) {
-        Some(h) => h,
-        None => return,
-    };
-
-    let name = &config.project.name;
-    let old_claim = format!("agent://{name}-router");
-
-    for hook in hooks {
-        let desc = hook
-            .get("description")
-            .and_then(|d| d.as_str())
-            .unwrap_or("");
-        if desc != format!("edict:{name}:responder") && desc != format!("botbox:{name}:responder") {
-            continue;
-        }
-
-        // Check if the hook still uses the old claim pattern
-        let claim = hook
-            .get("condition")
-            .and_then(|c| c.get("pattern"))
-            .and_then(|p| p.as_str())
-            .unwrap_or("");
-        if claim != old_claim {
-            continue;
-        }
-
-        let id = match hook.get("id").and_then(|i| i.as_str()) {
-            Some(id) => id,
-            None => continue,
-        };
-
-        // Remove old hook and re-register with new claim pattern
-        if Tool::new("rite")
-            .args(&["hooks", "remove", id])
-            .run()
-            .is_err()
-        {
-            continue;
-        }
-
-        let agent = config.default_agent();
-        // Resolve hook paths the same way migrate_hook_cwd does
-        let bare_root = if project_root.ends_with("ws/default") {
-            project_root
-                .parent()
-                .and_then(Path::parent)
-                .filter(|r| r.join(".manifold").exists())
-        } else if project_root.join(".manifold").exists() {
-            Some(project_root)
-        } else {
-            None
-        };
-        let root_str = bare_root
-            .map(|r| r.display().to_string())
-            .unwrap_or_else(|| project_root.display().to_string());
-        let responder_ml = config
-            .agents
-            .responder
-            .as_ref()
-            .and_then(|r| r.memory_limit.as_deref());
-        super::init::register_router_hook(&root_str, &root_str, name, &agent, responder_ml);
-        println!("  Migrated router hook claim: agent://{name}-router → agent://{name}-dev");
-    }
-}
+            if changed {
+                // Remove hooks key entirely if empty
+                if settings
+                    .get("hooks")
+                    .and_then(|h| h.as_object())
+                    .is_some_and(|h| h.is_empty())
+                {
+                    settings.as_object_mut().unwrap().remove("hooks");
+                }
 
-/// Migrate botty → vessel: update config key on disk and re-register rite hooks.
-///
-/// Idempotent — skips steps already done.
-fn migrate_vessel_hooks(config: &Config, project_root: &Path, config_path: &Path) {
-    // 1. Update config TOML on disk: botty = true → vessel = true
-    if let Ok(content) = fs::read_to_string(config_path) {
-        if content.contains("botty = ") {
-            let updated = content.replace("botty = ", "vessel = ");
-            if let Err(e) = fs::write(config_path, updated) {
-                tracing::warn!("failed to update config botty→vessel: {e}");
-            } else {
-                println!("Migrated config: tools.botty → tools.vessel");
+                // Only write back if there's other content; delete if empty
+                let new = if settings.as_object().is_some_and(|o| o.is_empty()) {
+                    None
+                } else {
+                    Some(serde_json::to_string_pretty(&settings)?)
+                };
+                changes.push(FileChange {
+                    path: settings_path,
+                    old: Some(content),
+                    new,
+                });
             }
         }
     }
 
-    // 2. Re-register edict hooks that still call `botty spawn` with `vessel spawn`.
-    //    ensure_rite_hook deduplicates by description, so calling register_*_hook
-    //    will remove the old hook and re-add it with the updated command.
-    let output = match Tool::new("rite")
-        .args(&["hooks", "list", "--format", "json"])
-        .run()
-    {
-        Ok(o) if o.success() => o,
-        _ => return,
-    };
-
-    let parsed: serde_json::Value = match serde_json::from_str(&output.stdout) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
-
-    let hooks = match parsed.get("hooks").and_then(|h| h.as_array()) {
-        Some(h) => h.to_vec(),
-        None => return,
-    };
-
-    let name = &config.project.name;
-
-    // Resolve root path (same logic as other hook migrations)
-    let bare_root = if project_root.ends_with("ws/default") {
-        project_root
-            .parent()
-            .and_then(Path::parent)
-            .filter(|r| r.join(".manifold").exists())
-    } else if project_root.join(".manifold").exists() {
-        Some(project_root)
-    } else {
-        None
-    };
-    let root_str = bare_root
-        .map(|r| r.display().to_string())
-        .unwrap_or_else(|| project_root.display().to_string());
-    let agent = config.default_agent();
-
-    for hook in &hooks {
-        // Only migrate hooks whose command array contains "botty"
-        let uses_botty = hook
-            .get("command")
-            .and_then(|c| c.as_array())
-            .map(|arr| arr.iter().any(|v| v.as_str() == Some("botty")))
-            .unwrap_or(false);
-        if !uses_botty {
-            continue;
-        }
-
-        let desc = hook
-            .get("description")
-            .and_then(|d| d.as_str())
-            .unwrap_or("");
-
-        if desc == format!("edict:{name}:responder") {
-            let ml = config
-                .agents
-                .responder
-                .as_ref()
-                .and_then(|r| r.memory_limit.as_deref());
-            super::init::register_router_hook(&root_str, &root_str, name, &agent, ml);
-            println!("  Migrated router hook: vessel spawn (was botty)");
-        } else if let Some(role) = desc
-            .strip_prefix(&format!("edict:{name}:reviewer-"))
-            .filter(|r| !r.is_empty())
-        {
-            let reviewer_agent = format!("{name}-{role}");
-            let ml = config
-                .agents
-                .reviewer
-                .as_ref()
-                .and_then(|r| r.memory_limit.as_deref());
-            super::init::register_reviewer_hook(&root_str, &root_str, name, &agent, &reviewer_agent, ml);
-            println!("  Migrated reviewer hook {role}: vessel spawn (was botty)");
-        }
+    let pi_ext = project_root.join(".pi/extensions/botbox-hooks.ts");
+    if pi_ext.exists() {
+        changes.push(FileChange {
+            old: Some(fs::read_to_string(&pi_ext)?),
+            path: pi_ext,
+            new: None,
+        });
     }
-}
 
-/// Migrate beads → bones: config key, data directory, .maw.toml, .sealignore, .gitignore.
-///
-/// This is idempotent — checks each step before acting.
-fn migrate_beads_to_bones(project_root: &Path, config_path: &Path) -> Result<()> {
-    let beads_dir = project_root.join(".beads");
-    let bones_dir = project_root.join(".bones");
-
-    // 1. If config has `tools.beads` (in TOML), rename to `tools.bones`
-    //    The serde alias handles deserialization, but we want the file itself updated.
-    if config_path.exists() {
-        let content = fs::read_to_string(config_path)?;
-        if content.contains("beads") && !content.contains("bones") {
-            let updated = content.replace("beads = ", "bones = ");
-            fs::write(config_path, updated)?;
-            println!("Migrated config: tools.beads → tools.bones");
-        }
-    }
+    Ok(changes)
+}
 
-    // 2. If .beads/ exists and .bones/ doesn't → run `bn init` + migrate data
-    if beads_dir.exists() && !bones_dir.exists() {
-        let beads_db = beads_dir.join("beads.db");
-        // Initialize bones first
-        match run_command("bn", &["init"], Some(project_root)) {
-            Ok(_) => println!("Initialized bones"),
-            Err(e) => tracing::warn!("bn init failed: {e}"),
-        }
-        // Migrate data if beads.db exists
-        if beads_db.exists() {
-            let db_path = beads_db.to_string_lossy().to_string();
-            match run_command(
-                "bn",
-                &["data", "migrate-from-beads", "--beads-db", &db_path],
-                Some(project_root),
-            ) {
-                Ok(_) => println!("Migrated beads data to bones"),
-                Err(e) => tracing::warn!("beads data migration failed: {e}"),
+/// Legacy JS-era artifacts `cleanup_legacy_artifacts` removes: the scripts/ and
+/// hooks/ directories and their version markers.
+fn legacy_artifact_changes(agents_dir: &Path) -> Result<Vec<FileChange>> {
+    fn removals(path: &Path, changes: &mut Vec<FileChange>) -> Result<()> {
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            entries.sort();
+            for entry in entries {
+                removals(&entry, changes)?;
             }
+        } else if path.exists() {
+            let old = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+            changes.push(FileChange {
+                path: path.to_path_buf(),
+                old: Some(String::from_utf8_lossy(&old).into_owned()),
+                new: None,
+            });
         }
+        Ok(())
     }
 
-    // 3. Update .maw.toml: remove .beads/** entry (set auto_resolve_from_main to empty)
-    let maw_toml = project_root.join(".maw.toml");
-    if maw_toml.exists() {
-        let content = fs::read_to_string(&maw_toml)?;
-        if content.contains(".beads/") {
-            // Remove the .beads/** line and set to empty array if it was the only entry
-            let updated = content
-                .lines()
-                .map(|line| {
-                    if line.contains(".beads/") {
-                        // Skip this line
-                        None
-                    } else {
-                        Some(line)
-                    }
-                })
-                .flatten()
-                .collect::<Vec<_>>()
-                .join("\n");
-            // If the array is now effectively empty, replace with empty
-            let updated = updated.replace(
-                "auto_resolve_from_main = [\n]",
-                "auto_resolve_from_main = []",
-            );
-            fs::write(&maw_toml, format!("{updated}\n"))?;
-            println!("Updated .maw.toml: removed .beads/** entry");
-        }
-    }
-
-    // 4. Update .sealignore: remove .beads/ line (bones handles its own sealignore)
-    let sealignore = project_root.join(".sealignore");
-    if sealignore.exists() {
-        let content = fs::read_to_string(&sealignore)?;
-        if content.contains(".beads/") {
-            let updated: String = content
-                .lines()
-                .filter(|line| line.trim() != ".beads/")
-                .collect::<Vec<_>>()
-                .join("\n");
-            let updated = if content.ends_with('\n') {
-                format!("{updated}\n")
-            } else {
-                updated
-            };
-            fs::write(&sealignore, updated)?;
-            println!("Updated .sealignore: removed .beads/ entry");
-        }
+    let mut changes = Vec::new();
+    for name in ["scripts", "hooks", ".scripts-version", ".hooks-version"] {
+        removals(&agents_dir.join(name), &mut changes)?;
     }
+    Ok(changes)
+}
 
-    // 5. Update .gitignore: remove .bv/ line (bones is tracked, not ignored)
-    let gitignore = project_root.join(".gitignore");
-    if gitignore.exists() {
-        let content = fs::read_to_string(&gitignore)?;
-        if content.contains(".bv/") {
-            let updated: String = content
-                .lines()
-                .filter(|line| line.trim() != ".bv/")
-                .collect::<Vec<_>>()
-                .join("\n");
-            // Preserve trailing newline if original had one
-            let updated = if content.ends_with('\n') {
-                format!("{updated}\n")
-            } else {
-                updated
-            };
-            fs::write(&gitignore, updated)?;
-            println!("Updated .gitignore: removed .bv/ entry");
+/// Apply `per_repo_hook_changes`, removing directories they leave empty.
+fn cleanup_per_repo_hooks(project_root: &Path, changes: &[FileChange]) -> Result<()> {
+    for change in changes {
+        change.apply()?;
+        if change.path.ends_with(".claude/settings.json") {
+            // Also remove .claude dir if empty
+            let claude_dir = project_root.join(".claude");
+            if change.new.is_none() && fs::read_dir(&claude_dir)?.next().is_none() {
+                fs::remove_dir(&claude_dir)?;
+            }
+            println!("Cleaned up per-repo botbox hooks from .claude/settings.json (now managed globally via `botbox hooks install`)");
+        } else {
+            // Clean up empty dirs
+            for dir in [".pi/extensions", ".pi"] {
+                let dir = project_root.join(dir);
+                if dir.exists() && fs::read_dir(&dir)?.next().is_none() {
+                    fs::remove_dir(&dir)?;
+                }
+            }
+            println!("Cleaned up per-repo Pi extension (now managed globally via `botbox hooks install`)");
         }
     }
-
     Ok(())
 }
 
@@ -1454,39 +938,10 @@ fn find_jj_root(from: &Path) -> Option<PathBuf> {
         .map(|p| p.to_path_buf())
 }
 
-/// Compute SHA-256 hash of all workflow docs
-fn compute_docs_version() -> String {
-    let mut hasher = Sha256::new();
-    for (name, content) in WORKFLOW_DOCS {
-        hasher.update(name.as_bytes());
-        hasher.update(content.as_bytes());
-    }
-    format!("{:x}", hasher.finalize())[..32].to_string()
-}
-
-/// Compute SHA-256 hash of all reviewer prompts
-fn compute_prompts_version() -> String {
-    let mut hasher = Sha256::new();
-    for (name, content) in REVIEWER_PROMPTS {
-        hasher.update(name.as_bytes());
-        hasher.update(content.as_bytes());
-    }
-    format!("{:x}", hasher.finalize())[..32].to_string()
-}
-
-/// Compute SHA-256 hash of all design docs
-fn compute_design_docs_version() -> String {
-    let mut hasher = Sha256::new();
-    for (name, content) in DESIGN_DOCS {
-        hasher.update(name.as_bytes());
-        hasher.update(content.as_bytes());
-    }
-    format!("{:x}", hasher.finalize())[..32].to_string()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::overrides::version_hash;
 
     #[test]
     fn test_find_jj_root_direct() {
@@ -1517,15 +972,15 @@ mod tests {
 
     #[test]
     fn test_version_hashes() {
-        let docs_ver = compute_docs_version();
+        let docs_ver = version_hash(WORKFLOW_DOCS.iter().copied());
         assert_eq!(docs_ver.len(), 32);
         assert!(docs_ver.chars().all(|c| c.is_ascii_hexdigit()));
 
-        let prompts_ver = compute_prompts_version();
+        let prompts_ver = version_hash(REVIEWER_PROMPTS.iter().copied());
         assert_eq!(prompts_ver.len(), 32);
         assert!(prompts_ver.chars().all(|c| c.is_ascii_hexdigit()));
 
-        let design_ver = compute_design_docs_version();
+        let design_ver = version_hash(DESIGN_DOCS.iter().copied());
         assert_eq!(design_ver.len(), 32);
         assert!(design_ver.chars().all(|c| c.is_ascii_hexdigit()));
     }
@@ -1558,4 +1013,119 @@ mod tests {
                 .any(|(n, _)| *n == "reviewer-security.md")
         );
     }
+
+    #[test]
+    fn test_file_change_unified_diff() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("AGENTS.md");
+        fs::write(&path, "# Project\nold line\n").unwrap();
+
+        assert!(FileChange::to(path.clone(), Some("# Project\nold line\n".into())).is_none());
+        let change = FileChange::to(path.clone(), Some("# Project\nnew line\n".into())).unwrap();
+        let diff = change.unified_diff(dir.path());
+        assert!(diff.starts_with("--- a/AGENTS.md\n+++ b/AGENTS.md\n"));
+        assert!(diff.contains("-old line\n+new line\n"));
+
+        let created = FileChange::to(dir.path().join("new.md"), Some("hello\n".into())).unwrap();
+        assert!(created.unified_diff(dir.path()).starts_with("--- /dev/null\n+++ b/new.md\n"));
+
+        let removed = FileChange {
+            path: path.clone(),
+            old: Some("x\n".into()),
+            new: None,
+        };
+        assert!(removed.unified_diff(dir.path()).contains("+++ /dev/null\n"));
+        removed.apply().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_legacy_artifact_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(legacy_artifact_changes(dir.path()).unwrap().is_empty());
+
+        fs::create_dir_all(dir.path().join("scripts/lib")).unwrap();
+        fs::write(dir.path().join("scripts/lib/loop.mjs"), "run()\n").unwrap();
+        fs::write(dir.path().join(".hooks-version"), "3\n").unwrap();
+        let changes = legacy_artifact_changes(dir.path()).unwrap();
+        let paths: Vec<_> = changes.iter().map(|c| c.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                dir.path().join("scripts/lib/loop.mjs"),
+                dir.path().join(".hooks-version"),
+            ]
+        );
+        assert!(changes.iter().all(|c| c.new.is_none()));
+        assert!(
+            changes[0]
+                .unified_diff(dir.path())
+                .starts_with("--- a/scripts/lib/loop.mjs\n+++ /dev/null\n")
+        );
+    }
+
+    #[test]
+    fn test_per_repo_hook_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(per_repo_hook_changes(dir.path()).unwrap().is_empty());
+
+        fs::create_dir_all(dir.path().join(".claude")).unwrap();
+        let settings = serde_json::json!({
+            "hooks": {
+                "SessionStart": [
+                    {"hooks": [{"type": "command", "command": "botbox hooks run session-start"}]},
+                    {"hooks": [{"type": "command", "command": "my-own-hook"}]}
+                ],
+                "PreCompact": [
+                    {"hooks": [{"type": "command", "command": ["botbox", "hooks", "run", "x"]}]}
+                ]
+            }
+        });
+        let settings_path = dir.path().join(".claude/settings.json");
+        fs::write(&settings_path, settings.to_string()).unwrap();
+
+        let changes = per_repo_hook_changes(dir.path()).unwrap();
+        assert_eq!(changes.len(), 1);
+        let new: serde_json::Value =
+            serde_json::from_str(changes[0].new.as_deref().unwrap()).unwrap();
+        assert_eq!(new["hooks"]["SessionStart"].as_array().unwrap().len(), 1);
+        assert!(new["hooks"].get("PreCompact").is_none());
+
+        // Only botbox hooks: the settings file is deleted, then the empty .claude dir
+        fs::write(
+            &settings_path,
+            serde_json::json!({"hooks": {"PreCompact": settings["hooks"]["PreCompact"]}})
+                .to_string(),
+        )
+        .unwrap();
+        fs::create_dir_all(dir.path().join(".pi/extensions")).unwrap();
+        fs::write(dir.path().join(".pi/extensions/botbox-hooks.ts"), "export {}\n").unwrap();
+        let changes = per_repo_hook_changes(dir.path()).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|c| c.new.is_none()));
+
+        cleanup_per_repo_hooks(dir.path(), &changes).unwrap();
+        assert!(!dir.path().join(".claude").exists());
+        assert!(!dir.path().join(".pi").exists());
+    }
+
+    #[test]
+    fn test_only_components() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            sync: SyncArgs,
+        }
+
+        let cli = Cli::parse_from(["edict", "--diff", "--only", "docs,agents-md,hooks"]);
+        assert!(cli.sync.diff);
+        assert!(cli.sync.selected(SyncComponent::Docs));
+        assert!(cli.sync.selected(SyncComponent::AgentsMd));
+        assert!(!cli.sync.selected(SyncComponent::Migrations));
+
+        let cli = Cli::parse_from(["edict"]);
+        assert!(cli.sync.selected(SyncComponent::Prompts));
+    }
 }
@@ -3,6 +3,7 @@ use std::process;
 
 use anyhow::Context;
 
+use crate::commands::kb::{self, KbDocument, KnowledgeBase};
 use crate::config::Config;
 use crate::subprocess::Tool;
 
@@ -21,6 +22,8 @@ pub struct WorkerLoop {
     dispatched_siblings: Option<String>,
     dispatched_mission_outcome: Option<String>,
     dispatched_file_hints: Option<String>,
+    /// Knowledge-base matches for the dispatched bone, pre-formatted for the prompt.
+    past_learnings: Option<String>,
 }
 
 impl WorkerLoop {
@@ -130,6 +133,10 @@ impl WorkerLoop {
             }
         });
 
+        let past_learnings = dispatched_bone
+            .as_deref()
+            .and_then(|bone| kb::learnings_for_bone(&project_root, bone));
+
         Ok(Self {
             project_root,
             agent,
@@ -144,6 +151,7 @@ impl WorkerLoop {
             dispatched_siblings,
             dispatched_mission_outcome,
             dispatched_file_hints,
+            past_learnings,
         })
     }
 
@@ -164,6 +172,11 @@ impl WorkerLoop {
             &[("agent", &self.agent), ("project", &self.project)],
         );
 
+        // Keep the transcript tail so future agents can find what happened here
+        let bones = self.dispatched_bone.iter().cloned().collect();
+        KnowledgeBase::new(&self.project_root)
+            .record(&[KbDocument::transcript(&self.agent, bones, &output)]);
+
         // Parse completion signal
         let status = parse_completion_signal(&output);
         crate::telemetry::metrics::counter(
@@ -208,6 +221,16 @@ impl WorkerLoop {
 
             let ws_path = self.project_root.join("ws").join(ws);
 
+            let learnings_section = self
+                .past_learnings
+                .as_ref()
+                .map(|l| {
+                    format!(
+                        "\nPAST LEARNINGS (from the project knowledge base — check before re-solving):\n{l}"
+                    )
+                })
+                .unwrap_or_default();
+
             format!(
                 r#"## DISPATCHED WORKER — FAST PATH
 
@@ -217,7 +240,7 @@ Skip steps 0 (RESUME CHECK), 1 (INBOX), and 2 (TRIAGE) entirely.
 Pre-assigned bone: {bone}
 Pre-assigned workspace: {ws}
 Workspace path: {ws_path}
-{mission_section}
+{mission_section}{learnings_section}
 
 Go directly to:
 1. Verify your bone: maw exec default -- bn show {bone}
@@ -231,6 +254,7 @@ Go directly to:
                 ws = ws,
                 ws_path = ws_path.display(),
                 mission_section = mission_section,
+                learnings_section = learnings_section,
             )
         } else {
             String::new()
@@ -919,6 +943,7 @@ mod tests {
             dispatched_siblings: None,
             dispatched_mission_outcome: None,
             dispatched_file_hints: None,
+            past_learnings: None,
         };
 
         let prompt = worker.build_prompt();
@@ -950,6 +975,7 @@ mod tests {
             dispatched_siblings: None,
             dispatched_mission_outcome: None,
             dispatched_file_hints: None,
+            past_learnings: None,
         };
 
         let prompt = worker.build_prompt();
@@ -959,6 +985,30 @@ mod tests {
         assert!(prompt.contains("Skip steps 0 (RESUME CHECK), 1 (INBOX), and 2 (TRIAGE)"));
     }
 
+    #[test]
+    fn build_prompt_dispatched_includes_past_learnings() {
+        let worker = WorkerLoop {
+            project_root: PathBuf::from("/test"),
+            agent: "test-worker".to_string(),
+            project: "testproject".to_string(),
+            model_pool: vec!["haiku".to_string()],
+            timeout: 900,
+            review_enabled: true,
+            critical_approvers: vec![],
+            dispatched_bone: Some("bd-test".to_string()),
+            dispatched_workspace: Some("test-ws".to_string()),
+            dispatched_mission: None,
+            dispatched_siblings: None,
+            dispatched_mission_outcome: None,
+            dispatched_file_hints: None,
+            past_learnings: Some("- [review] review cr-1: sccache OOM on parallel builds\n".to_string()),
+        };
+
+        let prompt = worker.build_prompt();
+        assert!(prompt.contains("PAST LEARNINGS"));
+        assert!(prompt.contains("sccache OOM on parallel builds"));
+    }
+
     #[test]
     fn build_prompt_contains_all_protocol_commands() {
         unsafe {
@@ -980,6 +1030,7 @@ mod tests {
             dispatched_siblings: None,
             dispatched_mission_outcome: None,
             dispatched_file_hints: None,
+            past_learnings: None,
         };
 
         let prompt = worker.build_prompt();
@@ -1028,6 +1079,7 @@ mod tests {
             dispatched_siblings: None,
             dispatched_mission_outcome: None,
             dispatched_file_hints: None,
+            past_learnings: None,
         };
 
         let prompt = worker.build_prompt();
@@ -1057,6 +1109,7 @@ mod tests {
             dispatched_siblings: None,
             dispatched_mission_outcome: None,
             dispatched_file_hints: None,
+            past_learnings: None,
         };
 
         let prompt = worker.build_prompt();
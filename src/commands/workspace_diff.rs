@@ -0,0 +1,281 @@
+//! A workspace's changes relative to trunk, parsed into added lines per file.
+//!
+//! Used by the deterministic checks that run before review and before merge. Only
+//! added lines matter to those checks, so the diff is taken with `-U0`. Untracked
+//! files are included as if every line were added.
+
+use anyhow::Result;
+
+use crate::subprocess::Tool;
+
+/// An added line and its line number in the new file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddedLine {
+    pub line: u32,
+    pub text: String,
+}
+
+/// One file's changes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffFile {
+    pub path: String,
+    pub added: Vec<AddedLine>,
+    pub removed: usize,
+    /// Git reported a binary change (no line information).
+    pub binary: bool,
+    /// The file was deleted.
+    pub deleted: bool,
+}
+
+impl DiffFile {
+    /// Changed lines (added + removed).
+    pub fn changed_lines(&self) -> usize {
+        self.added.len() + self.removed
+    }
+}
+
+/// Parse `git diff -U0` output.
+pub fn parse_unified_diff(diff: &str) -> Vec<DiffFile> {
+    let mut files: Vec<DiffFile> = Vec::new();
+    let mut in_hunk = false;
+    let mut next_line = 0u32;
+
+    for line in diff.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            // "a/<old> b/<new>"; the +++ header (if any) refines the path below
+            let path = rest
+                .rsplit_once(" b/")
+                .map_or(rest, |(_, new)| new)
+                .to_string();
+            files.push(DiffFile {
+                path,
+                ..Default::default()
+            });
+            in_hunk = false;
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+        if !in_hunk {
+            if let Some(path) = line.strip_prefix("+++ ") {
+                match path {
+                    "/dev/null" => file.deleted = true,
+                    p => file.path = p.strip_prefix("b/").unwrap_or(p).to_string(),
+                }
+                continue;
+            }
+            if line.starts_with("Binary files ") {
+                file.binary = true;
+                continue;
+            }
+        }
+        if line.starts_with("@@") {
+            in_hunk = true;
+            next_line = hunk_new_start(line).unwrap_or(0);
+            continue;
+        }
+        if !in_hunk {
+            continue;
+        }
+        if let Some(text) = line.strip_prefix('+') {
+            file.added.push(AddedLine {
+                line: next_line,
+                text: text.to_string(),
+            });
+            next_line += 1;
+        } else if line.starts_with('-') {
+            file.removed += 1;
+        } else if line.starts_with(' ') {
+            next_line += 1;
+        }
+    }
+    files
+}
+
+/// New-file start line from a hunk header: `@@ -a,b +c,d @@` → c.
+fn hunk_new_start(header: &str) -> Option<u32> {
+    let plus = header.split_whitespace().find(|t| t.starts_with('+'))?;
+    plus[1..].split(',').next()?.parse().ok()
+}
+
+/// Resolve the trunk branch: `configured` (`project.trunk`) if set, else the branch
+/// `origin/HEAD` points at, else whichever of `main` or `master` exists.
+pub fn trunk_branch(workspace: &str, configured: Option<&str>) -> Result<String> {
+    if let Some(trunk) = configured.filter(|t| !t.trim().is_empty()) {
+        if trunk.starts_with('-') {
+            anyhow::bail!("invalid project.trunk {trunk:?}");
+        }
+        return Ok(trunk.to_string());
+    }
+    let remote_head = Tool::new("git")
+        .args(&[
+            "symbolic-ref",
+            "--quiet",
+            "--short",
+            "refs/remotes/origin/HEAD",
+        ])
+        .in_workspace(workspace)?
+        .run()?;
+    if remote_head.success()
+        && let Some(branch) = remote_head_branch(&remote_head.stdout)
+    {
+        return Ok(branch.to_string());
+    }
+    for candidate in ["main", "master"] {
+        let exists = Tool::new("git")
+            .args(&[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("refs/heads/{candidate}"),
+            ])
+            .in_workspace(workspace)?
+            .run()?;
+        if exists.success() {
+            return Ok(candidate.to_string());
+        }
+    }
+    anyhow::bail!(
+        "could not detect the trunk branch in {workspace}; set project.trunk in .edict.toml"
+    )
+}
+
+/// Branch name from `git symbolic-ref --short refs/remotes/origin/HEAD` output.
+fn remote_head_branch(output: &str) -> Option<&str> {
+    output
+        .trim()
+        .strip_prefix("origin/")
+        .filter(|b| !b.is_empty())
+}
+
+/// Collect a workspace's diff against its merge-base with trunk, plus untracked files.
+pub fn workspace_diff(workspace: &str, trunk: Option<&str>) -> Result<Vec<DiffFile>> {
+    let trunk = trunk_branch(workspace, trunk)?;
+    let base = Tool::new("git")
+        .args(&["merge-base", &trunk, "HEAD"])
+        .in_workspace(workspace)?
+        .run_ok()?;
+    let base = base.stdout.trim();
+    if base.is_empty() || !base.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("unexpected merge-base output in {workspace}: {base:?}");
+    }
+
+    let diff = Tool::new("git")
+        .args(&["diff", "-U0", "--no-color", "--no-ext-diff", base])
+        .in_workspace(workspace)?
+        .run_ok()?;
+    let mut files = parse_unified_diff(&diff.stdout);
+
+    for path in &untracked_paths(workspace)? {
+        // --no-index exits 1 when the files differ, so don't use run_ok
+        let out = Tool::new("git")
+            .args(&[
+                "diff",
+                "--no-index",
+                "-U0",
+                "--no-color",
+                "--",
+                "/dev/null",
+                path.as_str(),
+            ])
+            .in_workspace(workspace)?
+            .run()?;
+        files.extend(parse_unified_diff(&out.stdout));
+    }
+    Ok(files)
+}
+
+/// Untracked, non-ignored files in a workspace (`git ls-files --others --exclude-standard`).
+pub fn untracked_paths(workspace: &str) -> Result<Vec<String>> {
+    let untracked = Tool::new("git")
+        .args(&["ls-files", "--others", "--exclude-standard"])
+        .in_workspace(workspace)?
+        .run_ok()?;
+    Ok(untracked
+        .stdout
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3,0 +4,2 @@ fn main() {
++    let a = 1;
++++counter;
+@@ -10 +12 @@
+-    old();
++    new();
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+diff --git a/logo.png b/logo.png
+Binary files a/logo.png and b/logo.png differ
+";
+
+    #[test]
+    fn parses_added_lines_with_numbers() {
+        let files = parse_unified_diff(DIFF);
+        assert_eq!(files.len(), 3);
+
+        let lib = &files[0];
+        assert_eq!(lib.path, "src/lib.rs");
+        assert_eq!(
+            lib.added,
+            vec![
+                AddedLine {
+                    line: 4,
+                    text: "    let a = 1;".into()
+                },
+                AddedLine {
+                    line: 5,
+                    text: "++counter;".into()
+                },
+                AddedLine {
+                    line: 12,
+                    text: "    new();".into()
+                },
+            ]
+        );
+        assert_eq!(lib.removed, 1);
+        assert_eq!(lib.changed_lines(), 4);
+
+        assert!(files[1].deleted);
+        assert_eq!(files[1].path, "old.txt");
+        assert!(files[2].binary);
+    }
+
+    #[test]
+    fn parses_untracked_no_index_diff() {
+        let diff = "\
+diff --git a/.env b/.env
+new file mode 100644
+--- /dev/null
++++ b/.env
+@@ -0,0 +1 @@
++TOKEN=abc
+";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files[0].path, ".env");
+        assert_eq!(files[0].added[0].line, 1);
+    }
+
+    #[test]
+    fn remote_head_branch_strips_remote() {
+        assert_eq!(remote_head_branch("origin/trunk\n"), Some("trunk"));
+        assert_eq!(remote_head_branch("origin/"), None);
+        assert_eq!(remote_head_branch(""), None);
+    }
+}
@@ -0,0 +1,299 @@
+//! Work-state summary re-injected by the `pre-compact` hook.
+//!
+//! Compaction drops most of the conversation, and agents that compact mid-bone tend
+//! to forget which bone and workspace they were in. The summary is rebuilt from
+//! `ProtocolContext` (held claims, bone titles, reviews), the last protocol guidance
+//! the agent was given and the `EDICT_*` dispatch environment.
+
+use std::path::Path;
+
+use crate::commands::protocol::context::ProtocolContext;
+use crate::commands::protocol::last_guidance::{GuidanceStore, RecordedGuidance};
+use crate::config::Config;
+
+/// Open threads listed per review.
+const MAX_THREADS: usize = 5;
+
+/// Dispatch environment set by dev-loop for workers (see mission.md).
+#[derive(Debug, Default)]
+pub struct MissionEnv {
+    pub mission: Option<String>,
+    pub outcome: Option<String>,
+    pub bone: Option<String>,
+    pub workspace: Option<String>,
+    pub siblings: Option<String>,
+    pub file_hints: Option<String>,
+}
+
+impl MissionEnv {
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        Self {
+            mission: var("EDICT_MISSION"),
+            outcome: var("EDICT_MISSION_OUTCOME"),
+            bone: var("EDICT_BONE"),
+            workspace: var("EDICT_WORKSPACE"),
+            siblings: var("EDICT_SIBLINGS"),
+            file_hints: var("EDICT_FILE_HINTS"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BoneState {
+    pub id: String,
+    pub title: String,
+    pub state: String,
+    pub workspace: Option<String>,
+    pub review: Option<ReviewState>,
+}
+
+#[derive(Debug)]
+pub struct ReviewState {
+    pub id: String,
+    pub status: String,
+    /// Unresolved threads as `file:line author: comment`.
+    pub open_threads: Vec<String>,
+    pub open_thread_count: usize,
+}
+
+/// What the agent was doing before compaction.
+#[derive(Debug, Default)]
+pub struct WorkState {
+    pub bones: Vec<BoneState>,
+    /// Workspace the agent works in: `$EDICT_WORKSPACE`, else a held workspace claim.
+    pub workspace: Option<String>,
+    pub last_guidance: Option<RecordedGuidance>,
+    pub mission: MissionEnv,
+}
+
+impl WorkState {
+    /// Collect from rite, maw, bn and seal. Unavailable tools leave their parts empty.
+    pub fn collect(project_root: &Path, agent: &str, config: &Config) -> Self {
+        let mission = MissionEnv::from_env();
+        let mut state = Self {
+            workspace: mission.workspace.clone(),
+            last_guidance: GuidanceStore::new(project_root).last(agent),
+            mission,
+            ..Self::default()
+        };
+
+        let Ok(ctx) = ProtocolContext::collect(&config.project.name, agent) else {
+            return state;
+        };
+        if state.workspace.is_none() {
+            state.workspace = ctx
+                .held_workspace_claims()
+                .into_iter()
+                .map(|(ws, _)| ws.to_string())
+                .find(|ws| ws != "default");
+        }
+        for (bone_id, _) in ctx.held_bone_claims() {
+            state.bones.push(bone_state(&ctx, bone_id));
+        }
+        state
+    }
+
+    /// Context to inject, or `None` when there is nothing to report.
+    pub fn render(&self) -> Option<String> {
+        let mut lines = Vec::new();
+
+        if let Some(ws) = &self.workspace {
+            lines.push(format!(
+                "Workspace: {ws}. Edit files under ws/{ws}/ and run commands with `maw exec {ws} -- <command>`."
+            ));
+        }
+
+        for bone in &self.bones {
+            let title = if bone.title.is_empty() {
+                String::new()
+            } else {
+                format!(" {}", bone.title)
+            };
+            let workspace = bone
+                .workspace
+                .as_ref()
+                .map(|ws| format!(" in workspace {ws}"))
+                .unwrap_or_default();
+            lines.push(format!(
+                "Claimed bone {}:{title} [{}]{workspace}",
+                bone.id, bone.state
+            ));
+            if let Some(review) = &bone.review {
+                lines.push(format!(
+                    "  Review {}: {}, {} open thread(s)",
+                    review.id, review.status, review.open_thread_count
+                ));
+                for thread in &review.open_threads {
+                    lines.push(format!("    - {thread}"));
+                }
+            }
+        }
+
+        if let Some(g) = &self.last_guidance {
+            lines.push(format!(
+                "Last protocol guidance (edict protocol {}, {}): {:?}",
+                g.command, g.snapshot_at, g.status
+            ));
+            if let Some(advice) = &g.advice {
+                lines.push(format!("  {advice}"));
+            }
+            for diagnostic in &g.diagnostics {
+                lines.push(format!("  ! {diagnostic}"));
+            }
+            for step in &g.steps {
+                lines.push(format!("  $ {step}"));
+            }
+        }
+
+        let m = &self.mission;
+        if let Some(mission) = &m.mission {
+            let outcome = m
+                .outcome
+                .as_ref()
+                .map(|o| format!(": {o}"))
+                .unwrap_or_default();
+            lines.push(format!("Mission {mission}{outcome}"));
+        }
+        if let Some(bone) = &m.bone {
+            lines.push(format!("Assigned bone (EDICT_BONE): {bone}"));
+        }
+        for (label, value) in [("Siblings", &m.siblings), ("File hints", &m.file_hints)] {
+            if let Some(value) = value {
+                lines.push(format!("{label}:"));
+                lines.extend(value.lines().map(|l| format!("  {l}")));
+            }
+        }
+
+        if lines.is_empty() {
+            return None;
+        }
+        lines.insert(0, "Work state before compaction:".to_string());
+        Some(lines.join("\n"))
+    }
+}
+
+fn bone_state(ctx: &ProtocolContext, bone_id: &str) -> BoneState {
+    let (title, state) = match ctx.bone_status(bone_id) {
+        Ok(bone) => (bone.title, bone.state),
+        Err(_) => (String::new(), "unknown".to_string()),
+    };
+    let workspace = ctx.workspace_for_bone(bone_id).map(str::to_string);
+
+    let review = workspace.as_deref().and_then(|ws| {
+        let summary = ctx.reviews_in_workspace(ws).ok()?.into_iter().next()?;
+        let detail = ctx.review_status(&summary.review_id, ws).ok()?;
+        let open_threads = detail
+            .threads
+            .iter()
+            .filter(|t| !t.resolved)
+            .take(MAX_THREADS)
+            .map(|t| {
+                let location = match (&t.file, t.line) {
+                    (Some(file), Some(line)) => format!("{file}:{line} "),
+                    (Some(file), None) => format!("{file} "),
+                    _ => String::new(),
+                };
+                let comment = t
+                    .comments
+                    .first()
+                    .map(|c| format!("{}: {}", c.author, truncate(&c.body, 80)))
+                    .unwrap_or_default();
+                format!("{location}{comment}")
+            })
+            .collect();
+        Some(ReviewState {
+            id: summary.review_id,
+            status: detail.status,
+            open_threads,
+            open_thread_count: detail.open_thread_count,
+        })
+    });
+
+    BoneState {
+        id: bone_id.to_string(),
+        title,
+        state,
+        workspace,
+        review,
+    }
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    let line = s.lines().next().unwrap_or_default();
+    if line.chars().count() <= max {
+        return line.to_string();
+    }
+    let mut out: String = line.chars().take(max - 3).collect();
+    out.push_str("...");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::protocol::render::{BoneRef, ProtocolStatus};
+
+    #[test]
+    fn empty_state_renders_nothing() {
+        assert!(WorkState::default().render().is_none());
+    }
+
+    #[test]
+    fn renders_bones_reviews_guidance_and_mission() {
+        let state = WorkState {
+            bones: vec![BoneState {
+                id: "bn-12".into(),
+                title: "Add OAuth config".into(),
+                state: "doing".into(),
+                workspace: Some("frost".into()),
+                review: Some(ReviewState {
+                    id: "cr-3".into(),
+                    status: "open".into(),
+                    open_threads: vec!["src/auth.rs:42 alice: handle expired tokens".into()],
+                    open_thread_count: 1,
+                }),
+            }],
+            workspace: Some("frost".into()),
+            last_guidance: Some(RecordedGuidance {
+                command: "finish".into(),
+                status: ProtocolStatus::Blocked,
+                snapshot_at: "2026-10-18T10:00:00Z".into(),
+                bone: Some(BoneRef {
+                    id: "bn-12".into(),
+                    title: "Add OAuth config".into(),
+                }),
+                workspace: Some("frost".into()),
+                steps: vec!["maw exec frost -- seal reviews request cr-3".into()],
+                diagnostics: vec!["review not approved".into()],
+                advice: None,
+            }),
+            mission: MissionEnv {
+                mission: Some("bn-m".into()),
+                outcome: Some("Users can log in via OAuth".into()),
+                siblings: Some("bn-13 (Callback) [owner:amber, state:doing]".into()),
+                ..MissionEnv::default()
+            },
+        };
+
+        let text = state.render().unwrap();
+        assert!(text.starts_with("Work state before compaction:"));
+        assert!(text.contains("Workspace: frost. Edit files under ws/frost/"));
+        assert!(text.contains("Claimed bone bn-12: Add OAuth config [doing] in workspace frost"));
+        assert!(text.contains("Review cr-3: open, 1 open thread(s)"));
+        assert!(text.contains("- src/auth.rs:42 alice: handle expired tokens"));
+        assert!(text.contains("(edict protocol finish, 2026-10-18T10:00:00Z): Blocked"));
+        assert!(text.contains("! review not approved"));
+        assert!(text.contains("$ maw exec frost -- seal reviews request cr-3"));
+        assert!(text.contains("Mission bn-m: Users can log in via OAuth"));
+        assert!(text.contains("Siblings:\n  bn-13 (Callback)"));
+    }
+
+    #[test]
+    fn truncates_long_comments() {
+        assert_eq!(truncate("short\nsecond line", 80), "short");
+        let long = truncate(&"x".repeat(100), 80);
+        assert_eq!(long.chars().count(), 80);
+        assert!(long.ends_with("..."));
+    }
+}
@@ -0,0 +1,235 @@
+//! Agent harnesses `edict hooks install` integrates with.
+//!
+//! Every integration routes the harness's lifecycle events to `edict hooks run`, so
+//! identity injection, inbox nudges, the tool-call policy and claim release behave the
+//! same whichever harness runs the agent.
+
+use super::HookRegistry;
+
+/// Version stamped into installed hook commands and plugins.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Codex's `hooks.json` uses Claude Code's event names.
+const CODEX_BINDINGS: &[(&str, &str)] = &[
+    ("SessionStart", "session-start --json"),
+    ("PreToolUse", "pre-tool-call"),
+    ("PostToolUse", "post-tool-call"),
+];
+
+const GEMINI_BINDINGS: &[(&str, &str)] = &[
+    ("SessionStart", "session-start --json"),
+    ("BeforeTool", "pre-tool-call"),
+    ("AfterTool", "post-tool-call"),
+    ("SessionEnd", "session-end"),
+];
+
+pub(crate) const PI_EDICT_HOOKS_EXTENSION: &str =
+    include_str!("../templates/extensions/edict-hooks.ts");
+
+pub(crate) const OPENCODE_EDICT_HOOKS_PLUGIN: &str =
+    include_str!("../templates/extensions/edict-opencode.ts");
+
+/// One harness event wired to `edict hooks run <hook> [args]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    pub event: &'static str,
+    /// Hook name plus extra arguments, e.g. `session-start --json`.
+    pub run: &'static str,
+}
+
+impl Binding {
+    /// Hook name (`session-start`, `pre-tool-call`, ...).
+    pub fn hook(&self) -> &'static str {
+        self.run.split_whitespace().next().unwrap_or_default()
+    }
+
+    /// Installed command, stamped with the edict version that wrote it.
+    pub fn command(&self) -> String {
+        format!("edict hooks run {} --edict-version {VERSION}", self.run)
+    }
+}
+
+/// Version stamp of an installed hook command, if it has one.
+pub fn command_version(command: &str) -> Option<&str> {
+    let mut words = command.split_whitespace();
+    words.find(|w| *w == "--edict-version")?;
+    words.next()
+}
+
+/// Hook name an installed `edict hooks run <hook>` command runs.
+pub fn command_hook(command: &str) -> Option<&str> {
+    let mut words = command.split_whitespace();
+    words.find(|w| *w == "run")?;
+    words.next()
+}
+
+/// Plugin file contents: the source behind a version header.
+pub fn plugin_source(source: &str) -> String {
+    format!(
+        "// edict {VERSION}: installed by `edict hooks install`, local edits are overwritten\n{source}"
+    )
+}
+
+/// Version in an installed plugin's header, if it has one.
+pub fn plugin_version(contents: &str) -> Option<&str> {
+    contents
+        .lines()
+        .next()?
+        .strip_prefix("// edict ")?
+        .split(':')
+        .next()
+}
+
+/// How edict is wired into a harness.
+#[derive(Debug, Clone)]
+pub enum Integration {
+    /// Claude-style `hooks` object in a JSON settings file.
+    Settings(Vec<Binding>),
+    /// Plugin source file written verbatim.
+    Plugin(&'static str),
+}
+
+#[derive(Debug, Clone)]
+pub struct Harness {
+    pub label: &'static str,
+    /// Integration file, relative to the home directory.
+    pub path: &'static str,
+    /// Integration file relative to the project root, for harnesses that read
+    /// project-level settings.
+    pub project_path: Option<&'static str>,
+    /// Directory (relative to home) whose presence means the harness is installed.
+    /// `None` means always install.
+    pub detect: Option<&'static str>,
+    pub integration: Integration,
+    /// Behaviors the harness has no event for.
+    pub limitations: &'static str,
+}
+
+fn bindings(pairs: &'static [(&'static str, &'static str)]) -> Vec<Binding> {
+    pairs
+        .iter()
+        .map(|&(event, run)| Binding { event, run })
+        .collect()
+}
+
+/// All supported harnesses.
+pub fn all() -> Vec<Harness> {
+    let claude = HookRegistry::all()
+        .iter()
+        .flat_map(|entry| {
+            entry.events.iter().map(|event| Binding {
+                event: event.as_str(),
+                run: entry.name,
+            })
+        })
+        .collect();
+
+    vec![
+        Harness {
+            label: "Claude Code",
+            path: ".claude/settings.json",
+            project_path: Some(".claude/settings.json"),
+            detect: None,
+            integration: Integration::Settings(claude),
+            limitations: "",
+        },
+        Harness {
+            label: "Pi",
+            path: ".pi/agent/extensions/edict-hooks.ts",
+            project_path: None,
+            detect: None,
+            integration: Integration::Plugin(PI_EDICT_HOOKS_EXTENSION),
+            limitations: "",
+        },
+        Harness {
+            label: "Codex CLI",
+            path: ".codex/hooks.json",
+            project_path: None,
+            detect: Some(".codex"),
+            integration: Integration::Settings(bindings(CODEX_BINDINGS)),
+            limitations: "no session-end event; the agent claim expires with its TTL",
+        },
+        Harness {
+            label: "Gemini CLI",
+            path: ".gemini/settings.json",
+            project_path: Some(".gemini/settings.json"),
+            detect: Some(".gemini"),
+            integration: Integration::Settings(bindings(GEMINI_BINDINGS)),
+            limitations: "",
+        },
+        Harness {
+            label: "opencode",
+            path: ".config/opencode/plugin/edict-hooks.ts",
+            project_path: Some(".opencode/plugin/edict-hooks.ts"),
+            detect: Some(".config/opencode"),
+            integration: Integration::Plugin(OPENCODE_EDICT_HOOKS_PLUGIN),
+            limitations: "",
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plugin_version_header() {
+        let contents = plugin_source(PI_EDICT_HOOKS_EXTENSION);
+        assert_eq!(plugin_version(&contents), Some(VERSION));
+        assert_eq!(plugin_version(PI_EDICT_HOOKS_EXTENSION), None);
+    }
+
+    #[test]
+    fn settings_harnesses_run_known_hooks() {
+        let known: Vec<&str> = HookRegistry::all().iter().map(|h| h.name).collect();
+        for harness in all() {
+            if let Integration::Settings(bindings) = &harness.integration {
+                for binding in bindings {
+                    assert!(
+                        known.contains(&binding.hook()),
+                        "{}: {}",
+                        harness.label,
+                        binding.run
+                    );
+                }
+                // Identity injection and inbox nudges everywhere
+                for hook in ["session-start", "post-tool-call"] {
+                    assert!(
+                        bindings.iter().any(|b| b.hook() == hook),
+                        "{}: {hook}",
+                        harness.label
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn claude_bindings_follow_registry() {
+        let harnesses = all();
+        let Integration::Settings(claude) = &harnesses[0].integration else {
+            panic!("claude uses settings.json");
+        };
+        assert!(claude.contains(&Binding {
+            event: "PreCompact",
+            run: "pre-compact"
+        }));
+        let command = Binding {
+            event: "SessionStart",
+            run: "session-start --json",
+        }
+        .command();
+        assert_eq!(
+            command,
+            format!("edict hooks run session-start --json --edict-version {VERSION}")
+        );
+        assert_eq!(command_version(&command), Some(VERSION));
+        assert_eq!(command_hook(&command), Some("session-start"));
+        assert_eq!(command_version("edict hooks run session-start"), None);
+        assert!(
+            !bindings(CODEX_BINDINGS)
+                .iter()
+                .any(|b| b.hook() == "session-end")
+        );
+    }
+}
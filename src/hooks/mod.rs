@@ -1,5 +1,14 @@
+mod compaction;
+pub mod harness;
+pub mod policy;
 mod registry;
 mod run;
+pub mod simulate;
+mod state;
+pub mod user;
 
 pub use registry::{HookEntry, HookEvent, HookRegistry};
-pub use run::{run_post_tool_call, run_session_end, run_session_start};
+pub use run::{
+    project_root, run_post_tool_call, run_pre_compact, run_pre_tool_call, run_session_end,
+    run_session_start,
+};
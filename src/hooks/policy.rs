@@ -0,0 +1,382 @@
+//! Tool-call policy for the `pre-tool-call` hook (`[hooks.policy]` in .edict.toml).
+//!
+//! Evaluates a proposed Bash or edit call before it runs. Shared by Claude Code's
+//! PreToolUse hook and the Pi extension's `tool_call` event, which both shell out to
+//! `edict hooks run pre-tool-call`.
+
+use std::path::{Component, Path, PathBuf};
+
+use globset::Glob;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::config::ToolPolicyConfig;
+
+/// A proposed tool call, normalized across harness tool names (Claude Code, Pi, Codex
+/// CLI, Gemini CLI, opencode).
+#[derive(Debug, Default)]
+pub struct ToolCall {
+    /// Shell command, for Bash calls.
+    pub command: Option<String>,
+    /// Target file, for edit calls.
+    pub path: Option<String>,
+}
+
+impl ToolCall {
+    /// Build from a tool name and its input (`tool_input` in Claude's payload, `input` in Pi's).
+    pub fn from_input(tool: &str, input: &serde_json::Value) -> Self {
+        let mut call = Self::default();
+        if is_shell_tool(tool) {
+            call.command = input["command"].as_str().map(str::to_string);
+        } else if is_edit_tool(tool) {
+            call.path = PATH_FIELDS
+                .iter()
+                .find_map(|key| input[*key].as_str())
+                .map(str::to_string);
+        }
+        call
+    }
+}
+
+/// Input fields holding an edit call's target file, across harnesses.
+const PATH_FIELDS: [&str; 4] = ["file_path", "filePath", "path", "notebook_path"];
+
+fn is_shell_tool(tool: &str) -> bool {
+    matches!(
+        tool.to_ascii_lowercase().as_str(),
+        "bash" | "shell" | "run_shell_command"
+    )
+}
+
+fn is_edit_tool(tool: &str) -> bool {
+    matches!(
+        tool.to_ascii_lowercase().as_str(),
+        "edit" | "write" | "multiedit" | "notebookedit" | "replace" | "write_file"
+    )
+}
+
+/// Whether the policy looks at calls to `tool` (shell and edit tools).
+pub fn is_guarded(tool: &str) -> bool {
+    is_shell_tool(tool) || is_edit_tool(tool)
+}
+
+/// The part of a tool input the policy reads: the command and target-path fields.
+///
+/// Extensions send this instead of the full input, which can be arbitrarily large.
+pub fn policy_input(input: &serde_json::Value) -> serde_json::Value {
+    let fields = std::iter::once("command")
+        .chain(PATH_FIELDS)
+        .filter_map(|key| Some((key.to_string(), input.get(key)?.clone())))
+        .collect();
+    serde_json::Value::Object(fields)
+}
+
+/// Where the call happens.
+pub struct Scope<'a> {
+    /// Project root: the maw repo root, or the directory holding .edict.toml.
+    pub root: Option<&'a Path>,
+    /// Whether the project is a maw repo (workspaces under `ws/`).
+    pub maw: bool,
+    /// The agent's claimed workspace (`$EDICT_WORKSPACE`).
+    pub workspace: Option<&'a str>,
+    /// Directory relative paths resolve against.
+    pub cwd: &'a Path,
+}
+
+/// Why a call was refused.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct Denial {
+    /// Policy rule that matched: `deny_jj`, `deny_commands`, `workspace_only` or
+    /// `protected_paths`; `unparseable_input` when the call couldn't be checked.
+    pub rule: &'static str,
+    pub reason: String,
+}
+
+impl Denial {
+    fn new(rule: &'static str, reason: impl Into<String>) -> Option<Self> {
+        Some(Self {
+            rule,
+            reason: reason.into(),
+        })
+    }
+}
+
+/// Check a call against the policy. `None` means allowed.
+pub fn evaluate(policy: &ToolPolicyConfig, call: &ToolCall, scope: &Scope) -> Option<Denial> {
+    if let Some(command) = &call.command {
+        return check_command(policy, command, scope);
+    }
+    if let Some(path) = &call.path {
+        return check_path(policy, path, scope);
+    }
+    None
+}
+
+fn check_command(policy: &ToolPolicyConfig, command: &str, scope: &Scope) -> Option<Denial> {
+    for segment in command_segments(command) {
+        let words: Vec<&str> = segment.split_whitespace().collect();
+        // `maw exec <ws> -- <cmd>` runs <cmd> in a workspace
+        let (exec_ws, inner) = match words.as_slice() {
+            ["maw", "exec", ws, "--", rest @ ..] => (Some(*ws), rest),
+            _ => (None, words.as_slice()),
+        };
+
+        if policy.deny_jj && scope.maw && inner.first() == Some(&"jj") {
+            return Denial::new(
+                "deny_jj",
+                "this project uses Git + maw; do not run jj commands",
+            );
+        }
+
+        if let (true, Some(claimed), Some(ws)) = (policy.workspace_only, scope.workspace, exec_ws)
+            && ws != claimed
+            && ws != "default"
+        {
+            return Denial::new(
+                "workspace_only",
+                format!("workspace {ws} is not yours; work in {claimed}"),
+            );
+        }
+    }
+
+    for denied in &policy.deny_commands {
+        // Invalid patterns are reported by `edict hooks audit`
+        if Regex::new(&denied.pattern).is_ok_and(|re| re.is_match(command)) {
+            let reason = denied
+                .message
+                .clone()
+                .unwrap_or_else(|| format!("command matches denied pattern `{}`", denied.pattern));
+            return Denial::new("deny_commands", reason);
+        }
+    }
+    None
+}
+
+fn check_path(policy: &ToolPolicyConfig, path: &str, scope: &Scope) -> Option<Denial> {
+    let root = scope.root?;
+    let target = normalize(&scope.cwd.join(path));
+    // Files outside the project are not this policy's business
+    let rel = target.strip_prefix(normalize(root)).ok()?;
+
+    let mut components = rel.components();
+    let (workspace, inner) = match (scope.maw, components.next(), components.next()) {
+        (true, Some(Component::Normal(first)), Some(Component::Normal(ws))) if first == "ws" => {
+            (ws.to_str(), components.as_path())
+        }
+        _ => (None, rel),
+    };
+
+    if let (true, true, Some(claimed)) = (policy.workspace_only, scope.maw, scope.workspace)
+        && workspace != Some(claimed)
+    {
+        return Denial::new(
+            "workspace_only",
+            format!(
+                "{} is outside your workspace; edit files under ws/{claimed}/",
+                rel.display()
+            ),
+        );
+    }
+
+    for pattern in &policy.protected_paths {
+        if Glob::new(pattern).is_ok_and(|g| g.compile_matcher().is_match(inner)) {
+            return Denial::new(
+                "protected_paths",
+                format!("{} is protected (matches `{pattern}`)", inner.display()),
+            );
+        }
+    }
+    None
+}
+
+/// Split a shell command into simple commands on `;`, `&&`, `||`, `|` and newlines.
+fn command_segments(command: &str) -> Vec<&str> {
+    command
+        .split(['\n', ';', '|', '&'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Resolve `.` and `..` without touching the filesystem (the file may not exist yet).
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Problems with the policy, for `hooks audit`.
+pub fn validate(policy: &ToolPolicyConfig) -> Vec<String> {
+    let mut problems = Vec::new();
+    for denied in &policy.deny_commands {
+        if let Err(e) = Regex::new(&denied.pattern) {
+            problems.push(format!(
+                "invalid deny_commands pattern {:?}: {e}",
+                denied.pattern
+            ));
+        }
+    }
+    for pattern in &policy.protected_paths {
+        if let Err(e) = Glob::new(pattern) {
+            problems.push(format!("invalid protected_paths pattern {pattern:?}: {e}"));
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DeniedCommand;
+    use serde_json::json;
+
+    fn scope<'a>(workspace: Option<&'a str>, cwd: &'a Path) -> Scope<'a> {
+        Scope {
+            root: Some(Path::new("/repo")),
+            maw: true,
+            workspace,
+            cwd,
+        }
+    }
+
+    fn bash(command: &str) -> ToolCall {
+        ToolCall::from_input("Bash", &json!({ "command": command }))
+    }
+
+    fn edit(path: &str) -> ToolCall {
+        ToolCall::from_input("Edit", &json!({ "file_path": path, "old_string": "a" }))
+    }
+
+    fn rule(denial: Option<Denial>) -> Option<&'static str> {
+        denial.map(|d| d.rule)
+    }
+
+    #[test]
+    fn tool_inputs_from_claude_and_pi() {
+        let pi = ToolCall::from_input("write", &json!({ "path": "src/lib.rs" }));
+        assert_eq!(pi.path.as_deref(), Some("src/lib.rs"));
+        let opencode = ToolCall::from_input("edit", &json!({ "filePath": "src/lib.rs" }));
+        assert_eq!(opencode.path.as_deref(), Some("src/lib.rs"));
+        let gemini = ToolCall::from_input("run_shell_command", &json!({ "command": "jj log" }));
+        assert_eq!(gemini.command.as_deref(), Some("jj log"));
+        let claude = bash("ls");
+        assert_eq!(claude.command.as_deref(), Some("ls"));
+        assert!(
+            ToolCall::from_input("Read", &json!({ "file_path": "a" }))
+                .path
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn policy_input_keeps_only_what_the_policy_reads() {
+        let input = json!({ "file_path": "src/lib.rs", "old_string": "a", "new_string": "b" });
+        assert_eq!(policy_input(&input), json!({ "file_path": "src/lib.rs" }));
+        assert!(is_guarded("Bash") && is_guarded("write_file") && !is_guarded("Read"));
+    }
+
+    #[test]
+    fn jj_and_denied_commands() {
+        let mut policy = ToolPolicyConfig::default();
+        policy.deny_commands.push(DeniedCommand {
+            pattern: "git push .*--force".into(),
+            message: Some("force pushes need a human".into()),
+        });
+        let cwd = Path::new("/repo/ws/frost");
+        let s = scope(None, cwd);
+
+        assert_eq!(
+            rule(evaluate(&policy, &bash("jj log"), &s)),
+            Some("deny_jj")
+        );
+        assert_eq!(
+            rule(evaluate(
+                &policy,
+                &bash("cargo test && maw exec frost -- jj status"),
+                &s
+            )),
+            Some("deny_jj")
+        );
+        assert_eq!(evaluate(&policy, &bash("echo jj; git status"), &s), None);
+        let denial = evaluate(&policy, &bash("git push origin main --force"), &s).unwrap();
+        assert_eq!(denial.rule, "deny_commands");
+        assert_eq!(denial.reason, "force pushes need a human");
+
+        let non_maw = Scope { maw: false, ..s };
+        assert_eq!(evaluate(&policy, &bash("jj log"), &non_maw), None);
+        policy.deny_jj = false;
+        assert_eq!(evaluate(&policy, &bash("jj log"), &scope(None, cwd)), None);
+    }
+
+    #[test]
+    fn edits_stay_in_claimed_workspace() {
+        let policy = ToolPolicyConfig::default();
+        let cwd = Path::new("/repo/ws/frost");
+        let s = scope(Some("frost"), cwd);
+
+        assert_eq!(evaluate(&policy, &edit("src/main.rs"), &s), None);
+        assert_eq!(evaluate(&policy, &edit("/tmp/scratch.txt"), &s), None);
+        assert_eq!(
+            rule(evaluate(&policy, &edit("../default/src/main.rs"), &s)),
+            Some("workspace_only")
+        );
+        assert_eq!(
+            rule(evaluate(&policy, &edit("/repo/.edict.toml"), &s)),
+            Some("workspace_only")
+        );
+        assert_eq!(
+            rule(evaluate(&policy, &bash("maw exec amber -- cargo test"), &s)),
+            Some("workspace_only")
+        );
+        assert_eq!(
+            evaluate(&policy, &bash("maw exec default -- bn show bn-1"), &s),
+            None
+        );
+        // No claimed workspace, no restriction
+        assert_eq!(
+            evaluate(&policy, &edit("/repo/ws/default/a.rs"), &scope(None, cwd)),
+            None
+        );
+    }
+
+    #[test]
+    fn protected_paths_match_inside_workspaces() {
+        let policy = ToolPolicyConfig {
+            protected_paths: vec![".edict.toml".into(), "migrations/**".into()],
+            ..ToolPolicyConfig::default()
+        };
+        let cwd = Path::new("/repo/ws/frost");
+        let s = scope(Some("frost"), cwd);
+
+        let denial = evaluate(&policy, &edit("migrations/001_init.sql"), &s).unwrap();
+        assert_eq!(denial.rule, "protected_paths");
+        assert!(denial.reason.contains("migrations/001_init.sql"));
+        assert_eq!(
+            rule(evaluate(&policy, &edit("/repo/ws/frost/.edict.toml"), &s)),
+            Some("protected_paths")
+        );
+        assert_eq!(evaluate(&policy, &edit("src/migrations.rs"), &s), None);
+    }
+
+    #[test]
+    fn validation_problems() {
+        let policy = ToolPolicyConfig {
+            deny_commands: vec![DeniedCommand {
+                pattern: "(".into(),
+                message: None,
+            }],
+            protected_paths: vec!["[".into()],
+            ..ToolPolicyConfig::default()
+        };
+        assert_eq!(validate(&policy).len(), 2);
+        assert!(validate(&ToolPolicyConfig::default()).is_empty());
+    }
+}
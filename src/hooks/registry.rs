@@ -3,6 +3,7 @@
 pub enum HookEvent {
     SessionStart,
     PreCompact,
+    PreToolUse,
     PostToolUse,
     SessionEnd,
 }
@@ -12,6 +13,7 @@ impl HookEvent {
         match self {
             HookEvent::SessionStart => "SessionStart",
             HookEvent::PreCompact => "PreCompact",
+            HookEvent::PreToolUse => "PreToolUse",
             HookEvent::PostToolUse => "PostToolUse",
             HookEvent::SessionEnd => "SessionEnd",
         }
@@ -37,7 +39,15 @@ impl HookRegistry {
         vec![
             HookEntry {
                 name: "session-start",
-                events: &[HookEvent::SessionStart, HookEvent::PreCompact],
+                events: &[HookEvent::SessionStart],
+            },
+            HookEntry {
+                name: "pre-compact",
+                events: &[HookEvent::PreCompact],
+            },
+            HookEntry {
+                name: "pre-tool-call",
+                events: &[HookEvent::PreToolUse],
             },
             HookEntry {
                 name: "post-tool-call",
@@ -58,8 +68,10 @@ mod tests {
     #[test]
     fn all_hooks_registered() {
         let hooks = HookRegistry::all();
-        assert_eq!(hooks.len(), 3);
+        assert_eq!(hooks.len(), 5);
         assert!(hooks.iter().any(|h| h.name == "session-start"));
+        assert!(hooks.iter().any(|h| h.name == "pre-compact"));
+        assert!(hooks.iter().any(|h| h.name == "pre-tool-call"));
         assert!(hooks.iter().any(|h| h.name == "post-tool-call"));
         assert!(hooks.iter().any(|h| h.name == "session-end"));
     }
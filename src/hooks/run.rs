@@ -1,7 +1,9 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 
+use super::state::{self, HookState};
+use super::{compaction, policy, user};
 use crate::config::Config;
 use crate::subprocess::run_command;
 
@@ -10,14 +12,28 @@ use crate::subprocess::run_command;
 struct HookContext {
     /// If in a maw repo, the path containing .manifold
     maw_root: Option<std::path::PathBuf>,
+    /// Path of the edict config file, if in an edict project
+    config_path: Option<PathBuf>,
     /// If in an edict project, the loaded config
     edict_config: Option<Config>,
     /// Agent name from $AGENT or $RITE_AGENT
     agent: Option<String>,
+    /// Directory the hook runs in
+    cwd: PathBuf,
 }
 
 impl HookContext {
     fn detect() -> Self {
+        let mut ctx = Self::locate();
+        ctx.edict_config = ctx
+            .config_path
+            .as_deref()
+            .and_then(|p| Config::load(p).ok());
+        ctx
+    }
+
+    /// Detect the agent and project without loading the config.
+    fn locate() -> Self {
         let cwd = std::env::current_dir().unwrap_or_default();
 
         let agent = std::env::var("AGENT")
@@ -25,92 +41,303 @@ impl HookContext {
             .ok()
             .filter(|a| validate_agent_name(a));
 
-        let maw_root = find_ancestor_with(&cwd, ".manifold");
-
-        let edict_config = find_edict_config(&cwd)
-            .and_then(|p| Config::load(&p).ok());
-
         Self {
-            maw_root,
-            edict_config,
+            maw_root: find_ancestor_with(&cwd, ".manifold"),
+            config_path: find_edict_config(&cwd),
+            edict_config: None,
             agent,
+            cwd,
+        }
+    }
+
+    /// Maw guidance and agent identity, for session start and after compaction.
+    fn orientation(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        // 1. Maw repo guidance
+        if self.maw_root.is_some() {
+            lines.push(
+                "This project uses Git + maw for version control. \
+                Source files live in workspaces under ws/, not at the project root. \
+                Use `maw exec <workspace> -- <command>` to run commands. \
+                Run `maw --help` for more info. Do NOT run jj commands."
+                    .to_string(),
+            );
+        }
+
+        // 2. Agent identity + project channel (if edict project and agent set)
+        if let Some(ref agent) = self.agent {
+            if let Some(ref config) = self.edict_config {
+                lines.push(format!("Agent ID for use with rite/seal/bn: {agent}"));
+                lines.push(format!("Project channel: {}", config.channel()));
+            }
+        }
+
+        lines
+    }
+
+    fn project_root(&self) -> Option<PathBuf> {
+        project_root(&self.cwd)
+    }
+
+    /// The agent's work state, for the context after compaction.
+    fn work_state(&self) -> Option<String> {
+        match (&self.agent, &self.edict_config, self.project_root()) {
+            (Some(agent), Some(config), Some(root)) => {
+                compaction::WorkState::collect(&root, agent, config).render()
+            }
+            _ => None,
         }
     }
 
     fn channel(&self) -> Option<String> {
         self.edict_config.as_ref().map(|c| c.channel())
     }
+
+    /// Run the project's `[[hooks.<event>]]` actions. Returns context to inject.
+    fn run_user_hooks(&self, event: &str, tool: Option<&str>) -> Vec<String> {
+        let Some(ref config) = self.edict_config else {
+            return Vec::new();
+        };
+        let hooks = config.hooks.for_event(event);
+        if hooks.is_empty() {
+            return Vec::new();
+        }
+        let roles = self
+            .agent
+            .as_deref()
+            .map(|a| user::agent_roles(a, config, std::env::var_os("EDICT_BONE").is_some()))
+            .unwrap_or_default();
+        let channel = config.channel();
+        user::run(
+            hooks,
+            &user::Invocation {
+                event,
+                tool,
+                agent: self.agent.as_deref(),
+                roles,
+                project: &config.project.name,
+                channel: &channel,
+            },
+        )
+    }
 }
 
 /// Run session-start hook: maw guidance + agent identity + stake claim
-pub fn run_session_start() -> Result<()> {
+///
+/// With `json`, the context is wrapped in a SessionStart `hookSpecificOutput` object for
+/// harnesses that only read JSON hook output (Codex CLI, Gemini CLI).
+///
+/// Claude Code doesn't inject PreCompact output; it starts the compacted session with a
+/// SessionStart whose `source` is `compact`. That start also gets the work state.
+pub fn run_session_start(hook_input: Option<&str>, json: bool) -> Result<()> {
     let ctx = HookContext::detect();
+    let mut lines = ctx.orientation();
 
-    // 1. Maw repo guidance
-    if ctx.maw_root.is_some() {
-        println!(
-            "This project uses Git + maw for version control. \
-            Source files live in workspaces under ws/, not at the project root. \
-            Use `maw exec <workspace> -- <command>` to run commands. \
-            Run `maw --help` for more info. Do NOT run jj commands."
-        );
+    // 3. Stake claim (if agent set)
+    if let Some(ref agent) = ctx.agent {
+        stake_claim(agent);
     }
 
-    // 2. Agent identity + project channel (if edict project and agent set)
-    if let Some(ref agent) = ctx.agent {
-        if let Some(ref config) = ctx.edict_config {
-            println!("Agent ID for use with rite/seal/bn: {agent}");
-            println!("Project channel: {}", config.channel());
-        }
+    if hook_input.is_some_and(|input| session_source(input).as_deref() == Some("compact")) {
+        lines.extend(ctx.work_state());
     }
 
-    // 3. Stake claim (if agent set)
-    if let Some(ref agent) = ctx.agent {
-        stake_claim(agent);
+    // 4. Project-defined hooks
+    lines.extend(ctx.run_user_hooks("session-start", None));
+
+    print_context(&lines, json, "SessionStart")
+}
+
+/// Run pre-compact hook: session-start orientation plus the agent's work state (claimed
+/// bones, workspace, reviews, last protocol guidance, mission env), so it survives
+/// compaction. For harnesses that inject pre-compaction output (Pi, opencode).
+pub fn run_pre_compact(json: bool) -> Result<()> {
+    let ctx = HookContext::detect();
+    let mut lines = ctx.orientation();
+    lines.extend(ctx.work_state());
+    print_context(&lines, json, "PreCompact")
+}
+
+/// `source` of a SessionStart payload: `startup`, `resume`, `clear` or `compact`.
+fn session_source(hook_input: &str) -> Option<String> {
+    let data: serde_json::Value = serde_json::from_str(hook_input).ok()?;
+    data["source"].as_str().map(str::to_string)
+}
+
+/// Print injected context as plain lines, or as `hookSpecificOutput` JSON.
+fn print_context(lines: &[String], json: bool, event: &str) -> Result<()> {
+    if !json {
+        for line in lines {
+            println!("{line}");
+        }
+    } else if !lines.is_empty() {
+        let hook_output = serde_json::json!({
+            "hookSpecificOutput": {
+                "hookEventName": event,
+                "additionalContext": lines.join("\n")
+            }
+        });
+        println!("{}", serde_json::to_string(&hook_output)?);
     }
 
     Ok(())
 }
 
-/// Run post-tool-call hook: check rite inbox + refresh claim
-pub fn run_post_tool_call(hook_input: Option<&str>) -> Result<()> {
+/// Run pre-tool-call hook: refuse Bash/edit calls that break the `[hooks.policy]` rules.
+///
+/// Prints a PreToolUse `deny` decision (with the matched rule under `edictPolicy`) and
+/// nothing when the call is allowed. Input that can't be parsed is refused unless the
+/// tool is known not to be guarded, so a malformed payload can't slip past the policy.
+pub fn run_pre_tool_call(hook_input: Option<&str>, tool: Option<&str>) -> Result<()> {
+    let data = match hook_input.map(serde_json::from_str::<serde_json::Value>) {
+        Some(Ok(data)) => data,
+        Some(Err(e)) => {
+            if tool.is_none_or(policy::is_guarded) {
+                print_denial(&policy::Denial {
+                    rule: "unparseable_input",
+                    reason: format!("could not parse the tool input: {e}"),
+                })?;
+            }
+            return Ok(());
+        }
+        None => {
+            if tool.is_some_and(policy::is_guarded) {
+                print_denial(&policy::Denial {
+                    rule: "unparseable_input",
+                    reason: "no tool input on stdin".to_string(),
+                })?;
+            }
+            return Ok(());
+        }
+    };
+    let Some(tool) = tool.or_else(|| data["tool_name"].as_str()) else {
+        return Ok(());
+    };
+    let call = policy::ToolCall::from_input(tool, &data["tool_input"]);
+
     let ctx = HookContext::detect();
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let config_dir = find_edict_config(&cwd).and_then(|p| p.parent().map(Path::to_path_buf));
+    let workspace = std::env::var("EDICT_WORKSPACE").ok();
+    let scope = policy::Scope {
+        root: ctx.maw_root.as_deref().or(config_dir.as_deref()),
+        maw: ctx.maw_root.is_some(),
+        workspace: workspace.as_deref().filter(|w| !w.is_empty()),
+        cwd: &cwd,
+    };
+    let rules = ctx
+        .edict_config
+        .as_ref()
+        .map(|c| c.hooks.policy.clone())
+        .unwrap_or_default();
 
-    let Some(ref agent) = ctx.agent else {
-        return Ok(());
+    if let Some(denial) = policy::evaluate(&rules, &call, &scope) {
+        print_denial(&denial)?;
+    }
+
+    Ok(())
+}
+
+/// Print a PreToolUse `deny` decision for `denial`.
+fn print_denial(denial: &policy::Denial) -> Result<()> {
+    let reason = format!("edict policy ({}): {}", denial.rule, denial.reason);
+    let hook_output = serde_json::json!({
+        "hookSpecificOutput": {
+            "hookEventName": "PreToolUse",
+            "permissionDecision": "deny",
+            "permissionDecisionReason": reason
+        },
+        // Top-level form for harnesses without hookSpecificOutput (Gemini CLI)
+        "decision": "block",
+        "reason": reason,
+        "edictPolicy": denial
+    });
+    println!("{}", serde_json::to_string(&hook_output)?);
+    Ok(())
+}
+
+/// Run post-tool-call hook: check rite inbox + refresh claim + project hooks.
+///
+/// Inbox and claim checks run at most once per `[hooks] post_tool_call_interval` per
+/// agent, and only announce messages the agent hasn't been told about yet.
+/// `tool` overrides the tool name from the hook input (`tool_name` in Claude's payload).
+pub fn run_post_tool_call(hook_input: Option<&str>, tool: Option<&str>) -> Result<()> {
+    let mut ctx = HookContext::locate();
+    let mut state = ctx
+        .agent
+        .as_deref()
+        .zip(ctx.project_root())
+        .map(|(agent, root)| HookState::load(&root, agent));
+    ctx.edict_config = match (&mut state, ctx.config_path.as_deref()) {
+        (Some(state), Some(path)) => state.config(path),
+        (None, Some(path)) => Config::load(path).ok(),
+        (_, None) => None,
     };
+    let tool = tool
+        .map(str::to_string)
+        .or_else(|| hook_input.and_then(tool_name_from_input));
+    let mut context = Vec::new();
 
-    // 1. Check rite inbox
-    check_rite_inbox(&ctx, agent, hook_input)?;
+    if let Some(ref agent) = ctx.agent {
+        let interval = ctx
+            .edict_config
+            .as_ref()
+            .and_then(|c| c.hooks.post_tool_call_interval)
+            .unwrap_or(state::DEFAULT_INTERVAL_SECS);
+        let now = state::unix_now();
+        if state.as_ref().is_none_or(|s| s.due(now, interval)) {
+            // 1. Check rite inbox
+            context.extend(check_rite_inbox(&ctx, agent, state.as_mut())?);
+
+            // 2. Refresh claim if expiring
+            refresh_claim_if_needed(agent);
+
+            if let Some(ref mut state) = state {
+                state.checked(now);
+            }
+        }
+    }
+    if let Some(ref state) = state {
+        state.save();
+    }
 
-    // 2. Refresh claim if expiring
-    refresh_claim_if_needed(agent);
+    // 3. Project-defined hooks
+    context.extend(ctx.run_user_hooks("post-tool-call", tool.as_deref()));
+
+    if context.is_empty() {
+        return Ok(());
+    }
+    let hook_output = serde_json::json!({
+        "hookSpecificOutput": {
+            "hookEventName": "PostToolUse",
+            "additionalContext": context.join("\n\n")
+        }
+    });
+    println!("{}", serde_json::to_string(&hook_output)?);
 
     Ok(())
 }
 
-/// Run session-end hook: release claim + clear status
+/// Run session-end hook: release claim + clear status + project hooks
 pub fn run_session_end() -> Result<()> {
-    let agent = std::env::var("AGENT")
-        .or_else(|_| std::env::var("RITE_AGENT"))
-        .ok()
-        .filter(|a| validate_agent_name(a));
+    let ctx = HookContext::detect();
 
-    let Some(agent) = agent else {
-        return Ok(());
-    };
+    if let Some(ref agent) = ctx.agent {
+        let claim_uri = format!("agent://{agent}");
+        let _ = run_command(
+            "rite",
+            &["claims", "release", "--agent", agent, &claim_uri, "-q"],
+            None,
+        );
+        let _ = run_command(
+            "rite",
+            &["statuses", "clear", "--agent", agent, "-q"],
+            None,
+        );
+    }
 
-    let claim_uri = format!("agent://{agent}");
-    let _ = run_command(
-        "rite",
-        &["claims", "release", "--agent", &agent, &claim_uri, "-q"],
-        None,
-    );
-    let _ = run_command(
-        "rite",
-        &["statuses", "clear", "--agent", &agent, "-q"],
-        None,
-    );
+    ctx.run_user_hooks("session-end", None);
 
     Ok(())
 }
@@ -163,10 +390,22 @@ fn refresh_claim_if_needed(agent: &str) {
     }
 }
 
-fn check_rite_inbox(ctx: &HookContext, agent: &str, _hook_input: Option<&str>) -> Result<()> {
+/// Tool name from a Claude hook payload.
+fn tool_name_from_input(input: &str) -> Option<String> {
+    let data: serde_json::Value = serde_json::from_str(input).ok()?;
+    data["tool_name"].as_str().map(str::to_string)
+}
+
+/// Unread-message nudge for the agent, if it has unread mentions it hasn't been told
+/// about yet.
+fn check_rite_inbox(
+    ctx: &HookContext,
+    agent: &str,
+    state: Option<&mut HookState>,
+) -> Result<Option<String>> {
     let channel = match ctx.channel() {
         Some(ch) => ch,
-        None => return Ok(()), // No edict project, skip inbox check
+        None => return Ok(None), // No edict project, skip inbox check
     };
 
     let agent_flag = format!("--agent={agent}");
@@ -192,7 +431,7 @@ fn check_rite_inbox(ctx: &HookContext, agent: &str, _hook_input: Option<&str>) -
         .unwrap_or(0);
 
     if count == 0 {
-        return Ok(());
+        return Ok(None);
     }
 
     // Fetch messages as JSON
@@ -213,24 +452,34 @@ fn check_rite_inbox(ctx: &HookContext, agent: &str, _hook_input: Option<&str>) -
     )
     .unwrap_or_default();
 
-    let messages = parse_inbox_previews(&inbox_json, Some(agent));
-
-    let mark_read_cmd = format!("rite inbox --agent {agent} --mentions --channels {channel} --mark-read");
-
-    let context = format!(
-        "STOP: You have {count} unread rite message(s) in #{channel}. Check if any need a response:\n{messages}\n\nTo read and respond: `{mark_read_cmd}`"
-    );
-
-    let hook_output = serde_json::json!({
-        "hookSpecificOutput": {
-            "hookEventName": "PostToolUse",
-            "additionalContext": context
+    let messages = parse_inbox_messages(&inbox_json);
+    let fresh: Vec<&InboxMessage> = match state {
+        Some(state) => {
+            let fresh: Vec<&InboxMessage> = messages
+                .iter()
+                .filter(|m| m.id.as_deref().is_none_or(|id| !state.seen(id)))
+                .collect();
+            state.mark_seen(fresh.iter().filter_map(|m| m.id.as_deref()));
+            fresh
         }
-    });
+        None => messages.iter().collect(),
+    };
+    // Everything unread was already announced
+    if !messages.is_empty() && fresh.is_empty() {
+        return Ok(None);
+    }
 
-    println!("{}", serde_json::to_string(&hook_output)?);
+    let previews = fresh
+        .iter()
+        .map(|m| format!("  - {}", m.preview(Some(agent))))
+        .collect::<Vec<_>>()
+        .join("\n");
 
-    Ok(())
+    let mark_read_cmd = format!("rite inbox --agent {agent} --mentions --channels {channel} --mark-read");
+
+    Ok(Some(format!(
+        "STOP: You have {count} unread rite message(s) in #{channel}. Check if any need a response:\n{previews}\n\nTo read and respond: `{mark_read_cmd}`"
+    )))
 }
 
 /// Walk up from `start` looking for a directory containing `marker`.
@@ -250,6 +499,18 @@ fn find_ancestor_with(start: &Path, marker: &str) -> Option<std::path::PathBuf>
     }
 }
 
+/// Project root of `dir`: the maw repo root, else the directory holding the config.
+///
+/// Hooks and protocol commands key per-project state (the last-guidance store) on it.
+pub fn project_root(dir: &Path) -> Option<PathBuf> {
+    find_ancestor_with(dir, ".manifold").or_else(|| {
+        find_edict_config(dir)
+            .as_deref()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+    })
+}
+
 /// Walk up from `start` looking for an edict/botbox config file.
 /// Returns the config file path if found.
 fn find_edict_config(start: &Path) -> Option<std::path::PathBuf> {
@@ -289,14 +550,35 @@ fn validate_agent_name(name: &str) -> bool {
         && !name.starts_with('/')
 }
 
-fn parse_inbox_previews(inbox_json: &str, agent: Option<&str>) -> String {
+/// An inbox message, from `rite inbox --format json`.
+struct InboxMessage {
+    id: Option<String>,
+    sender: String,
+    body: String,
+}
+
+impl InboxMessage {
+    fn preview(&self, agent: Option<&str>) -> String {
+        let tag = match agent {
+            Some(a) if self.body.contains(&format!("@{a}")) => "[MENTIONS YOU] ",
+            _ => "",
+        };
+
+        let mut preview = format!("{tag}{}: {}", self.sender, self.body);
+        if preview.len() > 100 {
+            preview.truncate(97);
+            preview.push_str("...");
+        }
+        preview
+    }
+}
+
+fn parse_inbox_messages(inbox_json: &str) -> Vec<InboxMessage> {
     let data: serde_json::Value = match serde_json::from_str(inbox_json) {
         Ok(v) => v,
-        Err(_) => return String::new(),
+        Err(_) => return Vec::new(),
     };
 
-    let mut previews = Vec::new();
-
     let messages: Vec<&serde_json::Map<String, serde_json::Value>> =
         if let Some(arr) = data["mentions"].as_array() {
             arr.iter()
@@ -308,33 +590,17 @@ fn parse_inbox_previews(inbox_json: &str, agent: Option<&str>) -> String {
             Vec::new()
         };
 
-    for msg in messages {
-        let sender = msg
-            .get("agent")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
-        let body = msg.get("body").and_then(|v| v.as_str()).unwrap_or("");
-
-        let tag = if let Some(a) = agent {
-            if body.contains(&format!("@{a}")) {
-                "[MENTIONS YOU] "
-            } else {
-                ""
+    messages
+        .into_iter()
+        .map(|msg| {
+            let field = |key: &str| msg.get(key).and_then(|v| v.as_str());
+            InboxMessage {
+                id: field("id").map(str::to_string),
+                sender: field("agent").unwrap_or("unknown").to_string(),
+                body: field("body").unwrap_or("").to_string(),
             }
-        } else {
-            ""
-        };
-
-        let mut preview = format!("{tag}{sender}: {body}");
-        if preview.len() > 100 {
-            preview.truncate(97);
-            preview.push_str("...");
-        }
-
-        previews.push(format!("  - {preview}"));
-    }
-
-    previews.join("\n")
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -342,6 +608,19 @@ mod tests {
     use super::*;
     use std::fs;
 
+    #[test]
+    fn session_source_from_payload() {
+        assert_eq!(
+            session_source(r#"{"hook_event_name":"SessionStart","source":"compact"}"#).as_deref(),
+            Some("compact")
+        );
+        assert_eq!(
+            session_source(r#"{"hook_event_name":"SessionStart"}"#),
+            None
+        );
+        assert_eq!(session_source("not json"), None);
+    }
+
     #[test]
     fn find_ancestor_with_direct() {
         let tmp = tempfile::tempdir().unwrap();
@@ -402,39 +681,51 @@ mod tests {
     }
 
     #[test]
-    fn parse_inbox_previews_empty() {
+    fn parse_inbox_messages_empty() {
         let json = r#"{"mentions":[]}"#;
-        let result = parse_inbox_previews(json, None);
-        assert_eq!(result, "");
+        assert!(parse_inbox_messages(json).is_empty());
+        assert!(parse_inbox_messages("not json").is_empty());
     }
 
     #[test]
-    fn parse_inbox_previews_with_messages() {
+    fn parse_inbox_messages_with_mentions() {
         let json = r#"{
             "mentions": [
                 {
                     "message": {
+                        "id": "msg-1",
                         "agent": "alice",
                         "body": "Hey @bob, check this out"
                     }
                 }
             ]
         }"#;
-        let result = parse_inbox_previews(json, Some("bob"));
-        assert!(result.contains("[MENTIONS YOU]"));
-        assert!(result.contains("alice"));
+        let messages = parse_inbox_messages(json);
+        assert_eq!(messages[0].id.as_deref(), Some("msg-1"));
+        let preview = messages[0].preview(Some("bob"));
+        assert!(preview.contains("[MENTIONS YOU]"));
+        assert!(preview.contains("alice"));
     }
 
     #[test]
-    fn parse_inbox_previews_truncation() {
+    fn inbox_preview_truncation() {
         let long_body = "a".repeat(200);
         let json = format!(
-            r#"{{"mentions": [{{"message": {{"agent": "sender", "body": "{}"}}}}]}}"#,
+            r#"{{"messages": [{{"agent": "sender", "body": "{}"}}]}}"#,
             long_body
         );
-        let result = parse_inbox_previews(&json, None);
-        assert!(result.len() < 150);
-        assert!(result.ends_with("..."));
+        let messages = parse_inbox_messages(&json);
+        assert!(messages[0].id.is_none());
+        let preview = messages[0].preview(None);
+        assert!(preview.len() <= 100);
+        assert!(preview.ends_with("..."));
+    }
+
+    #[test]
+    fn tool_name_from_claude_payload() {
+        let input = r#"{"session_id":"s1","tool_name":"Edit","tool_input":{"file_path":"a.rs"}}"#;
+        assert_eq!(tool_name_from_input(input).as_deref(), Some("Edit"));
+        assert_eq!(tool_name_from_input("not json"), None);
     }
 
     #[test]
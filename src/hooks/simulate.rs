@@ -0,0 +1,439 @@
+//! Offline hook simulator (`edict hooks simulate <event>`).
+//!
+//! Runs the real `edict hooks run <event>` as a child process, the way Claude Code or
+//! the Pi extension would invoke it, with a synthetic payload, a fake agent identity and
+//! companion tools (rite, bn, maw, seal, vessel) replaced by stub scripts on `PATH`. The
+//! stubs log every call and answer the queries the hooks make (unread count, inbox,
+//! claims) from canned data, so nothing touches a live rite instance. Project `shell`
+//! hook actions still run for real.
+
+use std::fs;
+use std::io::Write;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::json;
+
+/// Companion tools replaced by stubs.
+const STUBBED_TOOLS: &[&str] = &["rite", "bn", "maw", "seal", "vessel"];
+
+/// Log separators: unit separator between arguments, record separator between calls.
+const ARG_SEP: char = '\u{1f}';
+const CALL_SEP: char = '\u{1e}';
+
+const STUB_SCRIPT: &str = r#"#!/bin/sh
+{ printf '%s' "$(basename "$0")"; for a in "$@"; do printf '\037%s' "$a"; done; printf '\036'; } >> "$EDICT_SIM_DIR/calls.log"
+case "$(basename "$0") $*" in
+  "rite inbox"*"--count-only"*) cat "$EDICT_SIM_DIR/inbox-count" ;;
+  "rite inbox"*"--format json"*) cat "$EDICT_SIM_DIR/inbox.json" ;;
+  "rite claims list"*) cat "$EDICT_SIM_DIR/claims.json" ;;
+esac
+exit 0
+"#;
+
+/// Which integration's invocation to reproduce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SimHarness {
+    /// Claude Code: hook payload JSON on stdin
+    Claude,
+    /// Pi extension: `--tool` argument, or the policy fields of the tool input on stdin
+    Pi,
+}
+
+/// What to simulate.
+pub struct Scenario {
+    /// Hook name (`session-start`, `pre-compact`, `pre-tool-call`, `post-tool-call`,
+    /// `session-end`).
+    pub event: String,
+    pub harness: SimHarness,
+    pub tool: String,
+    pub tool_input: serde_json::Value,
+    pub session_id: String,
+    pub agent: String,
+    /// Claimed workspace (`$EDICT_WORKSPACE`).
+    pub workspace: Option<String>,
+    /// Directory the hook runs in.
+    pub cwd: PathBuf,
+    /// Unread mentions the stubbed inbox reports.
+    pub unread: u32,
+    /// Seconds until the agent claim expires, as reported by the stubbed `rite claims list`.
+    pub claim_expires_in: i64,
+}
+
+/// What the hook did.
+#[derive(Debug, Serialize)]
+pub struct Outcome {
+    pub event: String,
+    pub agent: String,
+    /// Arguments after `edict`.
+    pub args: Vec<String>,
+    /// Payload written to stdin, if any.
+    pub stdin: Option<String>,
+    /// Context the harness would inject.
+    pub injected: Option<String>,
+    /// Refusal reason, if the tool call would be blocked.
+    pub blocked: Option<String>,
+    /// Companion calls that change state (claims, statuses, messages).
+    pub side_effects: Vec<String>,
+    /// Read-only companion calls.
+    pub queries: Vec<String>,
+    pub exit_code: i32,
+    pub stderr: String,
+}
+
+/// Default tool input for a tool name.
+pub fn default_tool_input(tool: &str) -> serde_json::Value {
+    match tool.to_ascii_lowercase().as_str() {
+        "bash" => json!({ "command": "cargo test" }),
+        "edit" => json!({ "file_path": "src/main.rs", "old_string": "a", "new_string": "b" }),
+        "write" => json!({ "file_path": "src/main.rs", "content": "fn main() {}\n" }),
+        _ => json!({}),
+    }
+}
+
+/// Claude Code's name for a hook's event.
+fn claude_event(event: &str) -> &'static str {
+    match event {
+        "session-start" => "SessionStart",
+        "pre-compact" => "PreCompact",
+        "pre-tool-call" => "PreToolUse",
+        "post-tool-call" => "PostToolUse",
+        _ => "SessionEnd",
+    }
+}
+
+/// Arguments after `edict` and the stdin payload, as the harness would send them.
+pub fn invocation(scenario: &Scenario) -> (Vec<String>, Option<String>) {
+    let mut args = vec![
+        "hooks".to_string(),
+        "run".to_string(),
+        scenario.event.clone(),
+    ];
+    let tool_event = scenario.event.ends_with("tool-call");
+    match scenario.harness {
+        SimHarness::Claude => {
+            let mut payload = json!({
+                "session_id": scenario.session_id,
+                "transcript_path": format!("/tmp/{}.jsonl", scenario.session_id),
+                "cwd": scenario.cwd.display().to_string(),
+                "hook_event_name": claude_event(&scenario.event),
+            });
+            if tool_event {
+                payload["tool_name"] = json!(scenario.tool);
+                payload["tool_input"] = scenario.tool_input.clone();
+            }
+            if scenario.event == "post-tool-call" {
+                payload["tool_response"] = json!({});
+            }
+            if scenario.event == "session-start" {
+                payload["source"] = json!("startup");
+            }
+            if scenario.event == "pre-compact" {
+                payload["trigger"] = json!("auto");
+            }
+            (args, Some(payload.to_string()))
+        }
+        SimHarness::Pi => {
+            if scenario.event == "pre-tool-call" {
+                let payload = json!({
+                    "tool_name": scenario.tool,
+                    "tool_input": super::policy::policy_input(&scenario.tool_input),
+                });
+                return (args, Some(payload.to_string()));
+            } else if scenario.event == "post-tool-call" {
+                args.extend(["--tool".to_string(), scenario.tool.clone()]);
+            }
+            (args, None)
+        }
+    }
+}
+
+/// Write the stub tools and their canned answers into `dir`.
+fn write_stubs(dir: &Path, scenario: &Scenario) -> Result<()> {
+    let bin = dir.join("bin");
+    fs::create_dir_all(&bin)?;
+    for tool in STUBBED_TOOLS {
+        let path = bin.join(tool);
+        fs::write(&path, STUB_SCRIPT)?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    let messages: Vec<serde_json::Value> = (1..=scenario.unread)
+        .map(|i| {
+            json!({
+                "id": format!("sim-msg-{i}"),
+                "agent": "sim-human",
+                "body": format!("@{} simulated message {i}", scenario.agent),
+            })
+        })
+        .collect();
+    fs::write(dir.join("inbox-count"), scenario.unread.to_string())?;
+    fs::write(
+        dir.join("inbox.json"),
+        json!({ "messages": messages }).to_string(),
+    )?;
+    fs::write(
+        dir.join("claims.json"),
+        json!({
+            "claims": [{
+                "agent": scenario.agent,
+                "patterns": [format!("agent://{}", scenario.agent)],
+                "active": true,
+                "expires_in_secs": scenario.claim_expires_in,
+            }]
+        })
+        .to_string(),
+    )?;
+    fs::write(dir.join("calls.log"), "")?;
+    Ok(())
+}
+
+/// Split logged calls into side effects and queries.
+fn classify_calls(log: &str) -> (Vec<String>, Vec<String>) {
+    let mut side_effects = Vec::new();
+    let mut queries = Vec::new();
+    for call in log.split(CALL_SEP).filter(|c| !c.is_empty()) {
+        let argv: Vec<&str> = call.split(ARG_SEP).collect();
+        let line = argv.join(" ");
+        let changes_state = matches!(
+            argv.as_slice(),
+            ["rite", "claims", "stake" | "refresh" | "release", ..]
+                | ["rite", "statuses", "set" | "clear", ..]
+                | ["rite", "send", ..]
+        ) || argv.contains(&"--mark-read");
+        if changes_state {
+            side_effects.push(line);
+        } else {
+            queries.push(line);
+        }
+    }
+    (side_effects, queries)
+}
+
+/// Read what the harness would do with the hook's stdout.
+fn interpret(event: &str, harness: SimHarness, stdout: &str) -> (Option<String>, Option<String>) {
+    let stdout = stdout.trim();
+    if stdout.is_empty() {
+        return (None, None);
+    }
+    let hook_output = serde_json::from_str::<serde_json::Value>(stdout)
+        .ok()
+        .map(|v| v["hookSpecificOutput"].clone());
+    match (event, hook_output) {
+        ("pre-tool-call", Some(out)) if out["permissionDecision"] == "deny" => (
+            None,
+            Some(
+                out["permissionDecisionReason"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+        ),
+        ("pre-tool-call", _) => (None, None),
+        // Pi injects the hook's stdout as-is
+        (_, _) if harness == SimHarness::Pi => (Some(stdout.to_string()), None),
+        (_, Some(out)) if out["additionalContext"].is_string() => {
+            (out["additionalContext"].as_str().map(str::to_string), None)
+        }
+        _ => (Some(stdout.to_string()), None),
+    }
+}
+
+/// Run the hook against stubs and report what happened.
+pub fn simulate(scenario: &Scenario) -> Result<Outcome> {
+    // Scratch dir for stubs and their call log, removed on drop. Created fresh with a
+    // random name, so nothing else can plant stubs in it first.
+    let sim_dir = tempfile::Builder::new()
+        .prefix("edict-hooks-sim-")
+        .tempdir()
+        .context("creating simulation scratch dir")?;
+    write_stubs(sim_dir.path(), scenario)?;
+
+    let path = match std::env::var_os("PATH") {
+        Some(path) => {
+            let mut dirs = vec![sim_dir.path().join("bin")];
+            dirs.extend(std::env::split_paths(&path));
+            std::env::join_paths(dirs)?
+        }
+        None => sim_dir.path().join("bin").into_os_string(),
+    };
+
+    let (args, stdin) = invocation(scenario);
+    let exe = std::env::current_exe().context("locating the edict binary")?;
+    let mut cmd = Command::new(exe);
+    cmd.args(&args)
+        .current_dir(&scenario.cwd)
+        .env("PATH", path)
+        .env("AGENT", &scenario.agent)
+        .env("EDICT_SIM_DIR", sim_dir.path())
+        // Fresh hook state: no throttling or already-announced messages carried over
+        .env("XDG_CACHE_HOME", sim_dir.path().join("cache"))
+        .env_remove("RITE_AGENT")
+        .env_remove("EDICT_BONE")
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    match &scenario.workspace {
+        Some(ws) => cmd.env("EDICT_WORKSPACE", ws),
+        None => cmd.env_remove("EDICT_WORKSPACE"),
+    };
+
+    let mut child = cmd.spawn().context("running edict hooks run")?;
+    if let (Some(payload), Some(mut pipe)) = (&stdin, child.stdin.take()) {
+        pipe.write_all(payload.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (injected, blocked) = interpret(&scenario.event, scenario.harness, &stdout);
+    let log = fs::read_to_string(sim_dir.path().join("calls.log")).unwrap_or_default();
+    let (side_effects, queries) = classify_calls(&log);
+
+    Ok(Outcome {
+        event: scenario.event.clone(),
+        agent: scenario.agent.clone(),
+        args,
+        stdin,
+        injected,
+        blocked,
+        side_effects,
+        queries,
+        exit_code: output.status.code().unwrap_or(-1),
+        stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    })
+}
+
+impl Outcome {
+    /// Human-readable report.
+    pub fn render(&self) -> String {
+        let mut out = format!("Simulated {} as {}\n", self.event, self.agent);
+        out.push_str(&format!("  command: edict {}\n", self.args.join(" ")));
+        if let Some(stdin) = &self.stdin {
+            out.push_str(&format!("  stdin:   {stdin}\n"));
+        }
+
+        out.push_str("\nInjected:\n");
+        match &self.injected {
+            Some(text) => {
+                for line in text.lines() {
+                    out.push_str(&format!("  {line}\n"));
+                }
+            }
+            None => out.push_str("  (nothing)\n"),
+        }
+        if self.event == "pre-tool-call" {
+            out.push_str("\nDecision:\n");
+            match &self.blocked {
+                Some(reason) => out.push_str(&format!("  BLOCKED: {reason}\n")),
+                None => out.push_str("  allowed\n"),
+            }
+        }
+
+        out.push_str("\nSide effects:\n");
+        if self.side_effects.is_empty() {
+            out.push_str("  (none)\n");
+        }
+        for call in &self.side_effects {
+            out.push_str(&format!("  {call}\n"));
+        }
+        if !self.queries.is_empty() {
+            out.push_str("\nQueries (stubbed):\n");
+            for call in &self.queries {
+                out.push_str(&format!("  {call}\n"));
+            }
+        }
+        if self.exit_code != 0 || !self.stderr.is_empty() {
+            out.push_str(&format!("\nExit code {}\n", self.exit_code));
+            for line in self.stderr.lines() {
+                out.push_str(&format!("  {line}\n"));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario(event: &str, harness: SimHarness) -> Scenario {
+        Scenario {
+            event: event.to_string(),
+            harness,
+            tool: "Bash".to_string(),
+            tool_input: default_tool_input("Bash"),
+            session_id: "sim-session".to_string(),
+            agent: "myapp-dev".to_string(),
+            workspace: None,
+            cwd: PathBuf::from("/repo"),
+            unread: 1,
+            claim_expires_in: 600,
+        }
+    }
+
+    #[test]
+    fn invocations_match_each_harness() {
+        let (args, stdin) = invocation(&scenario("pre-tool-call", SimHarness::Claude));
+        assert_eq!(args, vec!["hooks", "run", "pre-tool-call"]);
+        let payload: serde_json::Value = serde_json::from_str(&stdin.unwrap()).unwrap();
+        assert_eq!(payload["hook_event_name"], "PreToolUse");
+        assert_eq!(payload["tool_input"]["command"], "cargo test");
+
+        let (args, stdin) = invocation(&scenario("post-tool-call", SimHarness::Pi));
+        assert_eq!(
+            args,
+            vec!["hooks", "run", "post-tool-call", "--tool", "Bash"]
+        );
+        assert!(stdin.is_none());
+        let (args, stdin) = invocation(&scenario("pre-tool-call", SimHarness::Pi));
+        assert_eq!(args, vec!["hooks", "run", "pre-tool-call"]);
+        let payload: serde_json::Value = serde_json::from_str(&stdin.unwrap()).unwrap();
+        assert_eq!(payload["tool_input"], json!({ "command": "cargo test" }));
+    }
+
+    #[test]
+    fn calls_split_into_side_effects_and_queries() {
+        let log = "rite\u{1f}inbox\u{1f}--count-only\u{1e}\
+                   rite\u{1f}claims\u{1f}refresh\u{1f}--agent\u{1f}a\u{1e}\
+                   rite\u{1f}send\u{1f}--agent\u{1f}a\u{1f}ch\u{1f}multi\nline\u{1e}";
+        let (effects, queries) = classify_calls(log);
+        assert_eq!(queries, vec!["rite inbox --count-only"]);
+        assert_eq!(
+            effects,
+            vec![
+                "rite claims refresh --agent a",
+                "rite send --agent a ch multi\nline"
+            ]
+        );
+    }
+
+    #[test]
+    fn hook_output_interpretation() {
+        let deny = r#"{"hookSpecificOutput":{"hookEventName":"PreToolUse","permissionDecision":"deny","permissionDecisionReason":"no"}}"#;
+        assert_eq!(
+            interpret("pre-tool-call", SimHarness::Claude, deny),
+            (None, Some("no".to_string()))
+        );
+        let nudge =
+            r#"{"hookSpecificOutput":{"hookEventName":"PostToolUse","additionalContext":"STOP"}}"#;
+        assert_eq!(
+            interpret("post-tool-call", SimHarness::Claude, nudge),
+            (Some("STOP".to_string()), None)
+        );
+        assert_eq!(
+            interpret("session-start", SimHarness::Claude, "Agent ID: a\n"),
+            (Some("Agent ID: a".to_string()), None)
+        );
+        assert_eq!(
+            interpret("post-tool-call", SimHarness::Claude, ""),
+            (None, None)
+        );
+    }
+}
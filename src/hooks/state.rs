@@ -0,0 +1,184 @@
+//! Per-agent state for the `post-tool-call` hook.
+//!
+//! Harnesses fire `post-tool-call` after every tool call, so each agent keeps a small
+//! JSON file in the project cache directory: when its inbox and claim were last checked,
+//! which messages it has already been told about, and the parsed config (reused until
+//! the config file's mtime changes).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::journal::cache_dir;
+use crate::config::Config;
+
+/// Seconds between inbox and claim checks when `[hooks] post_tool_call_interval` is unset.
+pub const DEFAULT_INTERVAL_SECS: u64 = 30;
+
+/// Announced message IDs kept; older ones have long since been read or scrolled away.
+const SEEN_LIMIT: usize = 50;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HookState {
+    /// Unix seconds of the last inbox and claim check.
+    #[serde(default)]
+    last_check: u64,
+    /// IDs of messages already announced, oldest first.
+    #[serde(default)]
+    seen_messages: Vec<String>,
+    #[serde(default)]
+    config: Option<CachedConfig>,
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedConfig {
+    path: PathBuf,
+    /// Config file mtime, nanoseconds since the epoch.
+    modified: u64,
+    config: Config,
+}
+
+impl HookState {
+    /// Load `agent`'s state for the project, or start fresh.
+    pub fn load(project_root: &Path, agent: &str) -> Self {
+        let path = cache_dir(project_root)
+            .join("hooks")
+            .join(format!("{}.json", agent.replace('/', "-")));
+        let mut state: Self = fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        state.path = path;
+        state
+    }
+
+    /// Parsed config at `path`, from the cache when the file hasn't changed.
+    pub fn config(&mut self, path: &Path) -> Option<Config> {
+        let modified = mtime(path)?;
+        if let Some(cached) = &self.config
+            && cached.path == path
+            && cached.modified == modified
+        {
+            return Some(cached.config.clone());
+        }
+        let config = Config::load(path).ok()?;
+        self.config = Some(CachedConfig {
+            path: path.to_path_buf(),
+            modified,
+            config: config.clone(),
+        });
+        self.dirty = true;
+        Some(config)
+    }
+
+    /// Whether `interval` seconds have passed since the last check.
+    pub fn due(&self, now: u64, interval: u64) -> bool {
+        now.saturating_sub(self.last_check) >= interval
+    }
+
+    pub fn checked(&mut self, now: u64) {
+        self.last_check = now;
+        self.dirty = true;
+    }
+
+    pub fn seen(&self, id: &str) -> bool {
+        self.seen_messages.iter().any(|s| s == id)
+    }
+
+    pub fn mark_seen<'a>(&mut self, ids: impl IntoIterator<Item = &'a str>) {
+        for id in ids {
+            if !self.seen(id) {
+                self.seen_messages.push(id.to_string());
+                self.dirty = true;
+            }
+        }
+        let excess = self.seen_messages.len().saturating_sub(SEEN_LIMIT);
+        self.seen_messages.drain(..excess);
+    }
+
+    /// Write the state back if anything changed. Failures only cost the throttling.
+    pub fn save(&self) {
+        if !self.dirty {
+            return;
+        }
+        if let Some(dir) = self.path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        if let Ok(json) = serde_json::to_string(self) {
+            let _ = fs::write(&self.path, json);
+        }
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn mtime(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let nanos = modified.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+    u64::try_from(nanos).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttle_and_seen_messages() {
+        let mut state = HookState::default();
+        assert!(state.due(1_000, DEFAULT_INTERVAL_SECS));
+        state.checked(1_000);
+        assert!(!state.due(1_010, 30));
+        assert!(state.due(1_030, 30));
+        assert!(state.due(1_000, 0));
+
+        state.mark_seen(["m1", "m2"]);
+        state.mark_seen(["m2"]);
+        assert!(state.seen("m1"));
+        assert!(!state.seen("m3"));
+        assert_eq!(state.seen_messages.len(), 2);
+
+        let ids: Vec<String> = (0..SEEN_LIMIT).map(|i| format!("x{i}")).collect();
+        state.mark_seen(ids.iter().map(String::as_str));
+        assert_eq!(state.seen_messages.len(), SEEN_LIMIT);
+        assert!(!state.seen("m1"));
+        assert!(state.seen(&format!("x{}", SEEN_LIMIT - 1)));
+    }
+
+    #[test]
+    fn config_cached_until_file_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".edict.toml");
+        fs::write(&path, "version = \"1.0.0\"\n[project]\nname = \"one\"\n").unwrap();
+
+        let mut state = HookState {
+            path: dir.path().join("state.json"),
+            ..HookState::default()
+        };
+        assert_eq!(state.config(&path).unwrap().project.name, "one");
+        state.save();
+
+        let mut reloaded: HookState =
+            serde_json::from_str(&fs::read_to_string(dir.path().join("state.json")).unwrap())
+                .unwrap();
+        // Stale cache entry: served while the mtime matches
+        reloaded.config.as_mut().unwrap().config.project.name = "cached".into();
+        assert_eq!(reloaded.config(&path).unwrap().project.name, "cached");
+        assert!(!reloaded.dirty);
+
+        reloaded.config.as_mut().unwrap().modified -= 1;
+        assert_eq!(reloaded.config(&path).unwrap().project.name, "one");
+        assert!(reloaded.dirty);
+        assert!(state.config(&dir.path().join("missing.toml")).is_none());
+    }
+}
@@ -0,0 +1,303 @@
+//! Project-defined hook actions (`[[hooks.<event>]]` in .edict.toml).
+//!
+//! They run after the built-in behavior of `edict hooks run <event>`. Failures are
+//! reported on stderr and never fail the hook: a broken reminder script must not
+//! interrupt an agent's tool call.
+
+use std::time::Duration;
+
+use globset::Glob;
+use minijinja::Environment;
+use serde::Serialize;
+
+use crate::config::{Config, UserHookConfig};
+use crate::subprocess::Tool;
+
+/// Who and what a hook fires for.
+pub struct Invocation<'a> {
+    pub event: &'a str,
+    pub tool: Option<&'a str>,
+    pub agent: Option<&'a str>,
+    pub roles: Vec<String>,
+    pub project: &'a str,
+    pub channel: &'a str,
+}
+
+#[derive(Serialize)]
+struct Vars<'a> {
+    event: &'a str,
+    tool: &'a str,
+    agent: &'a str,
+    project: &'a str,
+    channel: &'a str,
+}
+
+impl Invocation<'_> {
+    fn vars(&self) -> Vars<'_> {
+        Vars {
+            event: self.event,
+            tool: self.tool.unwrap_or_default(),
+            agent: self.agent.unwrap_or_default(),
+            project: self.project,
+            channel: self.channel,
+        }
+    }
+}
+
+/// Roles for an agent name: `lead` for the project's dev agent and its numbered
+/// slots, `worker` for anything spawned below it (or with `EDICT_BONE` set), and
+/// `reviewer` plus the role name for `{project}-{reviewer}`.
+pub fn agent_roles(agent: &str, config: &Config, dispatched_worker: bool) -> Vec<String> {
+    let mut segments = agent.split('/');
+    let base = segments.next().unwrap_or_default();
+    let nested: Vec<&str> = segments.collect();
+    let mut roles = Vec::new();
+
+    let is_worker = dispatched_worker
+        || nested
+            .iter()
+            .any(|s| !s.bytes().all(|b| b.is_ascii_digit()));
+    if is_worker {
+        roles.push("worker".to_string());
+    } else if base == config.default_agent() {
+        roles.push("lead".to_string());
+    }
+
+    if let Some(role) = base.strip_prefix(&format!("{}-", config.project.name))
+        && nested.is_empty()
+        && config.review.all_roles().iter().any(|r| r == role)
+    {
+        roles.push("reviewer".to_string());
+        roles.push(role.to_string());
+    }
+    roles
+}
+
+/// Whether a hook's tool and role matchers accept this invocation.
+pub fn matches(hook: &UserHookConfig, inv: &Invocation) -> bool {
+    let tool_ok = hook.tools.is_empty()
+        || inv.tool.is_some_and(|tool| {
+            hook.tools
+                .iter()
+                .any(|pattern| Glob::new(pattern).is_ok_and(|g| g.compile_matcher().is_match(tool)))
+        });
+    let role_ok = hook.roles.is_empty() || hook.roles.iter().any(|r| inv.roles.contains(r));
+    tool_ok && role_ok
+}
+
+fn render(template: &str, inv: &Invocation) -> anyhow::Result<String> {
+    Ok(Environment::new().render_str(template, inv.vars())?)
+}
+
+/// Run the matching hooks. Returns text to inject into the agent's context.
+pub fn run(hooks: &[UserHookConfig], inv: &Invocation) -> Vec<String> {
+    let mut injections = Vec::new();
+    for (i, hook) in hooks.iter().enumerate() {
+        if !matches(hook, inv) {
+            continue;
+        }
+        let label = format!("hooks.{}[{i}]", inv.event);
+        let timeout = Duration::from_secs(hook.timeout);
+
+        if let Some(cmd) = &hook.shell {
+            let result = Tool::new("sh")
+                .args(&["-c", cmd])
+                .env("EDICT_HOOK_EVENT", inv.event)
+                .env("EDICT_HOOK_TOOL", inv.tool.unwrap_or_default())
+                .env("AGENT", inv.agent.unwrap_or_default())
+                .timeout(timeout)
+                .run();
+            match result {
+                Ok(out) if out.success() => {
+                    if !out.stdout.trim().is_empty() {
+                        injections.push(out.stdout.trim().to_string());
+                    }
+                }
+                Ok(out) => eprintln!(
+                    "edict: {label} shell exited {}: {}",
+                    out.exit_code,
+                    out.stderr.trim()
+                ),
+                Err(e) => eprintln!("edict: {label} shell failed: {e}"),
+            }
+        }
+
+        if let Some(template) = &hook.rite_post {
+            match (render(template, inv), inv.agent) {
+                (Ok(message), Some(agent)) => {
+                    if let Err(e) = Tool::new("rite")
+                        .args(&[
+                            "send",
+                            "--agent",
+                            agent,
+                            inv.channel,
+                            &message,
+                            "-L",
+                            "hook",
+                        ])
+                        .timeout(timeout)
+                        .run_ok()
+                    {
+                        eprintln!("edict: {label} rite post failed: {e}");
+                    }
+                }
+                (Ok(_), None) => eprintln!("edict: {label} rite post skipped (no agent)"),
+                (Err(e), _) => eprintln!("edict: {label} rite_post template: {e}"),
+            }
+        }
+
+        if let Some(template) = &hook.inject {
+            match render(template, inv) {
+                Ok(text) if !text.trim().is_empty() => injections.push(text.trim().to_string()),
+                Ok(_) => {}
+                Err(e) => eprintln!("edict: {label} inject template: {e}"),
+            }
+        }
+    }
+    injections
+}
+
+/// Problems with a hook definition, for `hooks audit`.
+pub fn validate(event: &str, hook: &UserHookConfig) -> Vec<String> {
+    let mut problems = Vec::new();
+    if hook.shell.is_none() && hook.rite_post.is_none() && hook.inject.is_none() {
+        problems.push("no action (set shell, rite_post or inject)".to_string());
+    }
+    let env = Environment::new();
+    for (field, template) in [("rite_post", &hook.rite_post), ("inject", &hook.inject)] {
+        if let Some(template) = template
+            && let Err(e) = env.template_from_str(template)
+        {
+            problems.push(format!("{field} is not a valid template: {e}"));
+        }
+    }
+    for pattern in &hook.tools {
+        if let Err(e) = Glob::new(pattern) {
+            problems.push(format!("invalid tools pattern {pattern:?}: {e}"));
+        }
+    }
+    if !hook.tools.is_empty() && event != "post-tool-call" {
+        problems.push("tools only applies to post-tool-call".to_string());
+    }
+    if hook.inject.is_some() && event == "session-end" {
+        problems.push("inject has no effect on session-end".to_string());
+    }
+    if hook.timeout == 0 {
+        problems.push("timeout must be at least 1 second".to_string());
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook() -> UserHookConfig {
+        UserHookConfig {
+            shell: None,
+            rite_post: None,
+            inject: Some("{{ agent }} used {{ tool }} in {{ project }}".to_string()),
+            tools: Vec::new(),
+            roles: Vec::new(),
+            timeout: 10,
+        }
+    }
+
+    fn invocation(tool: Option<&'static str>, roles: &[&str]) -> Invocation<'static> {
+        Invocation {
+            event: "post-tool-call",
+            tool,
+            agent: Some("myapp-dev/0/amber-reef"),
+            roles: roles.iter().map(|r| r.to_string()).collect(),
+            project: "myapp",
+            channel: "myapp",
+        }
+    }
+
+    fn config() -> Config {
+        Config::parse_toml(
+            r#"
+version = "1.0.16"
+
+[project]
+name = "myapp"
+
+[review]
+reviewers = ["security"]
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn roles_from_agent_names() {
+        let config = config();
+        assert_eq!(agent_roles("myapp-dev", &config, false), vec!["lead"]);
+        assert_eq!(agent_roles("myapp-dev/1", &config, false), vec!["lead"]);
+        assert_eq!(
+            agent_roles("myapp-dev/0/amber-reef", &config, false),
+            vec!["worker"]
+        );
+        assert_eq!(agent_roles("myapp-dev/1", &config, true), vec!["worker"]);
+        assert_eq!(
+            agent_roles("myapp-security", &config, false),
+            vec!["reviewer", "security"]
+        );
+        assert!(agent_roles("someone-else", &config, false).is_empty());
+    }
+
+    #[test]
+    fn tool_and_role_matchers() {
+        let mut h = hook();
+        assert!(matches(&h, &invocation(None, &[])));
+        h.tools = vec!["Edit".into(), "mcp__*".into()];
+        assert!(matches(&h, &invocation(Some("Edit"), &[])));
+        assert!(matches(&h, &invocation(Some("mcp__fs__read"), &[])));
+        assert!(!matches(&h, &invocation(Some("Bash"), &[])));
+        assert!(!matches(&h, &invocation(None, &[])));
+        h.roles = vec!["worker".into()];
+        assert!(matches(&h, &invocation(Some("Edit"), &["worker"])));
+        assert!(!matches(&h, &invocation(Some("Edit"), &["lead"])));
+    }
+
+    #[test]
+    fn runs_inject_and_shell_actions() {
+        let mut echo = hook();
+        echo.inject = None;
+        echo.shell = Some("echo \"$EDICT_HOOK_EVENT:$EDICT_HOOK_TOOL\"".into());
+        let mut failing = hook();
+        failing.inject = None;
+        failing.shell = Some("exit 3".into());
+        let mut other_tool = hook();
+        other_tool.tools = vec!["Bash".into()];
+
+        let injected = run(
+            &[hook(), echo, failing, other_tool],
+            &invocation(Some("Edit"), &[]),
+        );
+        assert_eq!(
+            injected,
+            vec![
+                "myapp-dev/0/amber-reef used Edit in myapp",
+                "post-tool-call:Edit"
+            ]
+        );
+    }
+
+    #[test]
+    fn validation_problems() {
+        assert!(validate("post-tool-call", &hook()).is_empty());
+        let mut bad = hook();
+        bad.inject = Some("{{ unclosed".into());
+        bad.tools = vec!["[".into()];
+        bad.timeout = 0;
+        let problems = validate("session-end", &bad);
+        assert_eq!(problems.len(), 5, "{problems:?}");
+        let mut empty = hook();
+        empty.inject = None;
+        assert_eq!(
+            validate("session-start", &empty),
+            vec!["no action (set shell, rite_post or inject)"]
+        );
+    }
+}
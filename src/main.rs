@@ -13,6 +13,8 @@ use clap::{Parser, Subcommand};
 use commands::doctor::DoctorArgs;
 use commands::hooks::HooksCommand;
 use commands::init::InitArgs;
+use commands::journal::JournalCommand;
+use commands::kb::KbCommand;
 use commands::protocol::ProtocolCommand;
 use commands::run::RunCommand;
 use commands::status::StatusArgs;
@@ -49,6 +51,16 @@ enum Commands {
         #[command(subcommand)]
         command: HooksCommand,
     },
+    /// Inspect loop iteration journals and the responder's processed messages
+    Journal {
+        #[command(subcommand)]
+        command: JournalCommand,
+    },
+    /// Search the project knowledge base of past learnings
+    Kb {
+        #[command(subcommand)]
+        command: KbCommand,
+    },
     /// Check protocol state and output guidance commands
     Protocol {
         #[command(subcommand)]
@@ -69,6 +81,8 @@ impl Commands {
             Self::Doctor(_) => "doctor",
             Self::Status(_) => "status",
             Self::Hooks { .. } => "hooks",
+            Self::Journal { .. } => "journal",
+            Self::Kb { .. } => "kb",
             Self::Protocol { .. } => "protocol",
             Self::Triage => "triage",
             Self::Schema => "schema",
@@ -90,6 +104,8 @@ fn main() -> ExitCode {
         Commands::Doctor(args) => args.execute(),
         Commands::Status(args) => args.execute(),
         Commands::Hooks { command } => command.execute(),
+        Commands::Journal { command } => command.execute(),
+        Commands::Kb { command } => command.execute(),
         Commands::Protocol { command } => command.execute(),
         Commands::Triage => commands::triage::run_triage(),
         Commands::Schema => commands::schema::run_schema(),
@@ -38,6 +38,8 @@ pub struct Tool {
     args: Vec<String>,
     timeout: Option<Duration>,
     maw_workspace: Option<String>,
+    /// Extra environment variables for the subprocess.
+    envs: Vec<(String, String)>,
     /// When true, spawn the subprocess in a new process group (process_group(0)) so
     /// it survives a SIGTERM directed at the parent's process group.  Use this for
     /// cleanup subprocesses that must outlive the signal that triggered them.
@@ -52,6 +54,7 @@ impl Tool {
             args: Vec::new(),
             timeout: None,
             maw_workspace: None,
+            envs: Vec::new(),
             new_process_group: false,
         }
     }
@@ -78,8 +81,13 @@ impl Tool {
         self
     }
 
+    /// Set an environment variable for the subprocess.
+    pub fn env(mut self, key: &str, value: &str) -> Self {
+        self.envs.push((key.to_string(), value.to_string()));
+        self
+    }
+
     /// Set a timeout for the subprocess.
-    #[allow(dead_code)]
     pub fn timeout(mut self, duration: Duration) -> Self {
         self.timeout = Some(duration);
         self
@@ -114,6 +122,7 @@ impl Tool {
 
         let mut cmd = Command::new(&program);
         cmd.args(&args)
+            .envs(self.envs.iter().map(|(k, v)| (k, v)))
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
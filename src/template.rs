@@ -1,6 +1,6 @@
 //! Template rendering for docs, prompts, and AGENTS.md managed section.
 
-use minijinja::Environment;
+use minijinja::{Environment, UndefinedBehavior};
 use serde::Serialize;
 
 use crate::config::{Config, ReviewConfig, ToolsConfig};
@@ -28,6 +28,16 @@ pub struct TemplateContext {
     pub release_instructions: Option<String>,
     /// Check command run before merging (optional)
     pub check_command: Option<String>,
+    /// Effective project channel (project.channel or project.name)
+    pub channel: String,
+    /// Effective lead agent name (project.default_agent or "{name}-dev")
+    pub default_agent: String,
+    /// Prefix that runs a command in the default workspace (`maw exec default -- `);
+    /// empty without maw, where commands run in the project checkout
+    pub exec_default: String,
+    /// Prefix that runs a command in the agent's workspace (`maw exec $WS -- `); empty
+    /// without maw
+    pub exec_ws: String,
     /// Workflow docs with descriptions
     pub workflow_docs: Vec<DocEntry>,
     /// Design docs with descriptions (filtered by project type)
@@ -70,12 +80,24 @@ impl TemplateContext {
                 .as_deref()
                 .map(dedent_and_trim),
             check_command: config.project.check_command.clone(),
+            channel: config.channel(),
+            default_agent: config.default_agent(),
+            exec_default: maw_exec(config, "default"),
+            exec_ws: maw_exec(config, "$WS"),
             workflow_docs,
             design_docs,
         }
     }
 }
 
+fn maw_exec(config: &Config, workspace: &str) -> String {
+    if config.tools.maw {
+        format!("maw exec {workspace} -- ")
+    } else {
+        String::new()
+    }
+}
+
 /// List all workflow docs with descriptions
 fn list_workflow_docs() -> Vec<DocEntry> {
     vec![
@@ -175,6 +197,31 @@ pub fn render_managed_section(ctx: &TemplateContext) -> anyhow::Result<String> {
     Ok(rendered)
 }
 
+/// Render embedded workflow or design docs for the project.
+///
+/// Block tags on their own line leave no blank line behind; an inline block that ends a
+/// line needs `{% endif +%}` to keep the newline. Undefined variables are errors, so a
+/// typo in a doc fails the tests rather than rendering as empty.
+pub fn render_docs(
+    docs: &[(&str, &str)],
+    ctx: &TemplateContext,
+) -> anyhow::Result<Vec<(String, String)>> {
+    let mut env = Environment::new();
+    env.set_keep_trailing_newline(true);
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+
+    docs.iter()
+        .map(|(name, source)| {
+            let rendered = env
+                .render_named_str(name, source, ctx)
+                .map_err(|e| anyhow::anyhow!("rendering {name}: {e}"))?;
+            Ok(((*name).to_string(), rendered))
+        })
+        .collect()
+}
+
 /// Render a complete AGENTS.md file for a new project
 pub fn render_agents_md(config: &Config) -> anyhow::Result<String> {
     let ctx = TemplateContext::from_config(config);
@@ -187,10 +234,11 @@ pub fn render_agents_md(config: &Config) -> anyhow::Result<String> {
         .collect::<Vec<_>>()
         .join(", ");
 
-    let reviewer_line = if config.review.reviewers.is_empty() {
+    let reviewer_roles = config.review.all_roles();
+    let reviewer_line = if reviewer_roles.is_empty() {
         String::new()
     } else {
-        format!("\nReviewer roles: {}", config.review.reviewers.join(", "))
+        format!("\nReviewer roles: {}", reviewer_roles.join(", "))
     };
 
     let managed = render_managed_section(&ctx)?;
@@ -291,6 +339,7 @@ mod tests {
                 check_command: Some("true".to_string()),
                 languages: vec![],
                 critical_approvers: None,
+                trunk: None,
             },
             tools: ToolsConfig {
                 bones: true,
@@ -302,10 +351,17 @@ mod tests {
             review: ReviewConfig {
                 enabled: true,
                 reviewers: vec!["security".to_string()],
+                roles: [("perf".to_string(), Default::default())].into(),
+                rules: Default::default(),
+                critical: Default::default(),
+                policy: Default::default(),
+                checks: Default::default(),
             },
             push_main: false,
             agents: Default::default(),
             models: Default::default(),
+            secrets: Default::default(),
+            hooks: Default::default(),
             env: Default::default(),
         };
 
@@ -313,7 +369,7 @@ mod tests {
 
         assert!(result.contains("# test-project"));
         assert!(result.contains("Tools: `bones`, `maw`, `seal`, `rite`, `vessel`"));
-        assert!(result.contains("Reviewer roles: security"));
+        assert!(result.contains("Reviewer roles: security, perf"));
         assert!(result.contains(MANAGED_START));
         assert!(result.contains(MANAGED_END));
         assert!(result.contains("## Edict Workflow"));
@@ -344,6 +400,7 @@ More custom content.
                 check_command: None,
                 languages: vec![],
                 critical_approvers: None,
+                trunk: None,
             },
             tools: ToolsConfig {
                 bones: true,
@@ -355,10 +412,17 @@ More custom content.
             review: ReviewConfig {
                 enabled: false,
                 reviewers: vec![],
+                roles: Default::default(),
+                rules: Default::default(),
+                critical: Default::default(),
+                policy: Default::default(),
+                checks: Default::default(),
             },
             push_main: false,
             agents: Default::default(),
             models: Default::default(),
+            secrets: Default::default(),
+            hooks: Default::default(),
             env: Default::default(),
         };
 
@@ -398,6 +462,7 @@ Old botbox-era managed content
                 check_command: None,
                 languages: vec![],
                 critical_approvers: None,
+                trunk: None,
             },
             tools: ToolsConfig {
                 bones: true,
@@ -409,10 +474,17 @@ Old botbox-era managed content
             review: ReviewConfig {
                 enabled: false,
                 reviewers: vec![],
+                roles: Default::default(),
+                rules: Default::default(),
+                critical: Default::default(),
+                policy: Default::default(),
+                checks: Default::default(),
             },
             push_main: false,
             agents: Default::default(),
             models: Default::default(),
+            secrets: Default::default(),
+            hooks: Default::default(),
             env: Default::default(),
         };
 
@@ -429,4 +501,87 @@ Old botbox-era managed content
         assert!(result.contains(MANAGED_START));
         assert!(result.contains(MANAGED_END));
     }
+
+    #[test]
+    fn test_render_docs_for_enabled_tools() {
+        use crate::commands::sync::{DESIGN_DOCS, WORKFLOW_DOCS};
+
+        let config = Config::parse_toml(
+            r#"
+version = "1.0.0"
+[project]
+name = "acme"
+channel = "acme-chan"
+check_command = "cargo test"
+[tools]
+bones = true
+maw = true
+seal = true
+rite = true
+"#,
+        )
+        .unwrap();
+        let ctx = TemplateContext::from_config(&config);
+
+        let docs = render_docs(WORKFLOW_DOCS, &ctx).unwrap();
+        assert_eq!(docs.len(), WORKFLOW_DOCS.len());
+        for (name, content) in &docs {
+            assert!(!content.contains("{{") && !content.contains("{%"), "{name}");
+            assert!(content.ends_with('\n'), "{name}");
+        }
+        let doc = |name: &str| &docs.iter().find(|(n, _)| n == name).unwrap().1;
+        assert!(doc("start.md").contains("bone://acme-chan/<bone-id>"));
+        assert!(doc("finish.md").contains("`maw exec $WS -- cargo test`"));
+        assert!(doc("review-request.md").contains("seal reviews create"));
+        assert!(doc("preflight.md").contains("adopt `acme-dev`"));
+        render_docs(DESIGN_DOCS, &ctx).unwrap();
+    }
+
+    /// Whether a rendered doc tells the agent to run `tool`, inline or in a code block
+    fn runs_tool(doc: &str, tool: &str) -> bool {
+        let command = format!("{tool} ");
+        doc.contains(&format!("`{command}"))
+            || doc.contains(&format!("-- {command}"))
+            || doc
+                .lines()
+                .any(|line| line.trim_start().starts_with(&command))
+    }
+
+    #[test]
+    fn test_render_docs_drops_disabled_tools() {
+        use crate::commands::sync::WORKFLOW_DOCS;
+
+        let render = |tools: &str| {
+            let config = Config::parse_toml(&format!(
+                "version = \"1.0.0\"\n[project]\nname = \"acme\"\n[tools]\n{tools}"
+            ))
+            .unwrap();
+            render_docs(WORKFLOW_DOCS, &TemplateContext::from_config(&config)).unwrap()
+        };
+
+        let docs = render("bones = true\n");
+        assert_eq!(docs.len(), WORKFLOW_DOCS.len());
+        for (name, content) in &docs {
+            for tool in ["maw", "seal", "rite"] {
+                assert!(!runs_tool(content, tool), "{name} runs {tool}");
+            }
+        }
+        let doc = |name: &str| &docs.iter().find(|(n, _)| n == name).unwrap().1;
+        assert!(doc("review-request.md").contains("Code review is not enabled"));
+        assert!(doc("merge-check.md").contains("doesn't use maw"));
+        assert!(doc("start.md").contains("3. Work in the project checkout"));
+        assert!(doc("finish.md").contains("`git push origin main`"));
+        assert!(doc("finish.md").contains("The project channel is `acme`."));
+
+        let docs = render("bones = true\nmaw = true\n");
+        for (name, content) in &docs {
+            for tool in ["seal", "rite"] {
+                assert!(!runs_tool(content, tool), "{name} runs {tool}");
+            }
+        }
+        let doc = |name: &str| &docs.iter().find(|(n, _)| n == name).unwrap().1;
+        assert!(doc("start.md").contains("4. **All file edits"));
+        assert!(doc("start.md").contains("\n5. **No `jj`**"));
+        assert!(doc("preflight.md").contains("3. `maw doctor`"));
+    }
 }
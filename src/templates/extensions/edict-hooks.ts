@@ -1,5 +1,13 @@
+import { spawn } from "node:child_process";
 import type { ExtensionAPI } from "@mariozechner/pi-coding-agent";
 
+// Tools the edict policy checks, and the input fields it reads (the rest is not sent)
+const GUARDED_TOOLS = new Set([
+	"bash", "shell", "run_shell_command",
+	"edit", "write", "multiedit", "notebookedit", "replace", "write_file",
+]);
+const POLICY_FIELDS = ["command", "file_path", "filePath", "path", "notebook_path"];
+
 async function runHook(
 	pi: ExtensionAPI,
 	hookName: string,
@@ -19,6 +27,36 @@ async function runHook(
 	}
 }
 
+// Run the pre-tool-call hook with the payload on stdin. Resolves to its stdout, or
+// null when the hook could not run or failed.
+function runPolicyHook(payload: string): Promise<string | null> {
+	return new Promise((resolve) => {
+		const child = spawn("edict", ["hooks", "run", "pre-tool-call"], {
+			stdio: ["pipe", "pipe", "ignore"],
+		});
+		let stdout = "";
+		child.stdout.on("data", (chunk) => {
+			stdout += chunk;
+		});
+		child.on("error", () => resolve(null));
+		child.on("close", (code) => resolve(code === 0 ? stdout.trim() : null));
+		child.stdin.on("error", () => {});
+		child.stdin.end(payload);
+	});
+}
+
+function policyInput(input: unknown): Record<string, unknown> {
+	const fields: Record<string, unknown> = {};
+	if (input && typeof input === "object") {
+		for (const key of POLICY_FIELDS) {
+			if (key in input) {
+				fields[key] = (input as Record<string, unknown>)[key];
+			}
+		}
+	}
+	return fields;
+}
+
 function injectMessage(pi: ExtensionAPI, stdout: string) {
 	pi.sendMessage({
 		customType: "edict-hook",
@@ -28,7 +66,6 @@ function injectMessage(pi: ExtensionAPI, stdout: string) {
 }
 
 export default function edictHooksExtension(pi: ExtensionAPI) {
-	let toolResultCount = 0;
 	let pendingSessionStartContext = "";
 
 	pi.on("session_start", async () => {
@@ -51,20 +88,45 @@ export default function edictHooksExtension(pi: ExtensionAPI) {
 		};
 	});
 
-	pi.on("tool_result", async () => {
-		toolResultCount += 1;
-		if (toolResultCount % 5 !== 0) {
+	pi.on("tool_call", async (event) => {
+		const { toolName, input } = event as { toolName?: string; input?: unknown };
+		if (!toolName) {
 			return;
 		}
 
-		const stdout = await runHook(pi, "post-tool-call");
+		const guarded = GUARDED_TOOLS.has(toolName.toLowerCase());
+		const payload = JSON.stringify({ tool_name: toolName, tool_input: policyInput(input) });
+		const stdout = await runPolicyHook(payload);
+		if (stdout === null) {
+			// Fail closed: a guarded call is only allowed once the policy has passed it
+			return guarded ? { block: true, reason: "edict policy check failed to run" } : undefined;
+		}
+		if (!stdout) {
+			return;
+		}
+
+		try {
+			const decision = JSON.parse(stdout).hookSpecificOutput;
+			if (decision?.permissionDecision === "deny") {
+				return { block: true, reason: decision.permissionDecisionReason };
+			}
+		} catch {
+			if (guarded) {
+				return { block: true, reason: "edict policy check returned unreadable output" };
+			}
+		}
+	});
+
+	pi.on("tool_result", async (event) => {
+		const toolName = (event as { toolName?: string }).toolName;
+		const stdout = await runHook(pi, "post-tool-call", toolName ? ["--tool", toolName] : []);
 		if (stdout) {
 			injectMessage(pi, stdout);
 		}
 	});
 
 	pi.on("session_before_compact", async () => {
-		const stdout = await runHook(pi, "session-start");
+		const stdout = await runHook(pi, "pre-compact");
 		if (stdout) {
 			injectMessage(pi, stdout);
 		}
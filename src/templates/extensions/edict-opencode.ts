@@ -0,0 +1,118 @@
+import type { Plugin } from "@opencode-ai/plugin";
+
+type Shell = Parameters<Plugin>[0]["$"];
+
+// Tools the edict policy checks, and the input fields it reads (the rest is not sent)
+const GUARDED_TOOLS = new Set([
+	"bash", "shell", "run_shell_command",
+	"edit", "write", "multiedit", "notebookedit", "replace", "write_file",
+]);
+const POLICY_FIELDS = ["command", "file_path", "filePath", "path", "notebook_path"];
+
+async function runHook(
+	$: Shell,
+	hookName: string,
+	extraArgs: string[] = [],
+): Promise<string | null> {
+	try {
+		const result = await $`edict hooks run ${hookName} ${extraArgs}`.quiet().nothrow();
+		if (result.exitCode !== 0) {
+			return null;
+		}
+
+		const stdout = result.stdout.toString().trim();
+		return stdout.length > 0 ? stdout : null;
+	} catch {
+		// Graceful degradation when edict is not installed or hook execution fails.
+		return null;
+	}
+}
+
+// Run the pre-tool-call hook with the payload on stdin. Resolves to its stdout, or
+// null when the hook could not run or failed.
+async function runPolicyHook($: Shell, payload: string): Promise<string | null> {
+	try {
+		const result = await $`edict hooks run pre-tool-call < ${new Response(payload)}`
+			.quiet()
+			.nothrow();
+		return result.exitCode === 0 ? result.stdout.toString().trim() : null;
+	} catch {
+		return null;
+	}
+}
+
+function policyInput(args: Record<string, unknown> | undefined): Record<string, unknown> {
+	const fields: Record<string, unknown> = {};
+	for (const key of POLICY_FIELDS) {
+		if (args && key in args) {
+			fields[key] = args[key];
+		}
+	}
+	return fields;
+}
+
+function hookOutput(stdout: string): Record<string, unknown> | null {
+	try {
+		return JSON.parse(stdout).hookSpecificOutput ?? null;
+	} catch {
+		return null;
+	}
+}
+
+export const EdictHooks: Plugin = async ({ $ }) => {
+	let sessionContext = "";
+
+	return {
+		event: async ({ event }) => {
+			if (event.type === "session.created") {
+				sessionContext = (await runHook($, "session-start")) ?? "";
+			} else if (event.type === "session.deleted" || event.type === "server.instance.disposed") {
+				await runHook($, "session-end");
+			}
+		},
+
+		"experimental.chat.system.transform": async (_input, output) => {
+			if (sessionContext) {
+				output.system.push(sessionContext);
+			}
+		},
+
+		"experimental.session.compacting": async (_input, output) => {
+			const stdout = await runHook($, "pre-compact");
+			if (stdout) {
+				output.context.push(stdout);
+			}
+		},
+
+		"tool.execute.before": async (input, output) => {
+			const guarded = GUARDED_TOOLS.has(input.tool.toLowerCase());
+			const payload = JSON.stringify({ tool_name: input.tool, tool_input: policyInput(output.args) });
+			const stdout = await runPolicyHook($, payload);
+			if (stdout === null) {
+				// Fail closed: a guarded call is only allowed once the policy has passed it
+				if (guarded) {
+					throw new Error("edict policy check failed to run");
+				}
+				return;
+			}
+			if (!stdout) {
+				return;
+			}
+			const decision = hookOutput(stdout);
+			if (decision?.permissionDecision === "deny") {
+				throw new Error(String(decision.permissionDecisionReason));
+			}
+			if (!decision && guarded) {
+				throw new Error("edict policy check returned unreadable output");
+			}
+		},
+
+		"tool.execute.after": async (input, output) => {
+			const stdout = await runHook($, "post-tool-call", ["--tool", input.tool]);
+			const context = stdout ? hookOutput(stdout)?.additionalContext : null;
+			if (context) {
+				output.output = `${output.output}\n\n${context}`;
+			}
+		},
+	};
+};
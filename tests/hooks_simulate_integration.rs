@@ -0,0 +1,86 @@
+use assert_cmd::Command;
+use std::fs;
+
+fn project() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(
+        tmp.path().join(".edict.toml"),
+        "version = \"1.0.16\"\n\n[project]\nname = \"simproj\"\n",
+    )
+    .unwrap();
+    tmp
+}
+
+fn simulate(dir: &std::path::Path, args: &[&str]) -> serde_json::Value {
+    let output = Command::cargo_bin("edict")
+        .unwrap()
+        .current_dir(dir)
+        .args(["hooks", "simulate"])
+        .args(args)
+        .args(["--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn simulate_post_tool_call_nudges_and_refreshes() {
+    let tmp = project();
+    let outcome = simulate(
+        tmp.path(),
+        &[
+            "post-tool-call",
+            "--unread",
+            "2",
+            "--claim-expires-in",
+            "30",
+        ],
+    );
+    assert_eq!(outcome["agent"], "simproj-dev");
+    let injected = outcome["injected"].as_str().unwrap();
+    assert!(injected.contains("2 unread rite message(s) in #simproj"));
+    assert_eq!(
+        outcome["side_effects"],
+        serde_json::json!([
+            "rite claims refresh --agent simproj-dev agent://simproj-dev --ttl 600 -q"
+        ])
+    );
+
+    let quiet = simulate(tmp.path(), &["post-tool-call", "--unread", "0"]);
+    assert!(quiet["injected"].is_null());
+    assert_eq!(quiet["side_effects"], serde_json::json!([]));
+}
+
+#[test]
+fn simulate_session_end_releases_claim() {
+    let tmp = project();
+    let outcome = simulate(
+        tmp.path(),
+        &["session-end", "--harness", "pi", "--agent", "simproj-dev/1"],
+    );
+    let effects: Vec<&str> = outcome["side_effects"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|e| e.as_str())
+        .collect();
+    assert_eq!(
+        effects,
+        vec![
+            "rite claims release --agent simproj-dev/1 agent://simproj-dev/1 -q",
+            "rite statuses clear --agent simproj-dev/1 -q"
+        ]
+    );
+}
+
+#[test]
+fn simulate_rejects_unknown_hook() {
+    let tmp = project();
+    Command::cargo_bin("edict")
+        .unwrap()
+        .current_dir(tmp.path())
+        .args(["hooks", "simulate", "bogus"])
+        .assert()
+        .failure();
+}